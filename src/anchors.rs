@@ -0,0 +1,129 @@
+//! Additional anchors that vtable offsets can be taken relative to, for binaries whose code is split across loaded objects.
+//!
+//! By default a vtable is (de)serialized as its offset from a single anchor, a static trait object in this crate, which is constant across invocations of the binary as long as the vtable lies within the same loaded object as the anchor. Where the trait objects exchanged have their vtables in other shared objects, which the dynamic linker can map at different positions relative to one another in each invocation, [`register`] adds an anchor within such an object, identified by a name. A vtable lying within the object of a registered anchor is then (de)serialized as its offset from that anchor, along with the anchor's name, such that it resolves correctly wherever the object is mapped.
+//!
+//! The object an anchor lies within is determined from the loaded segments, on platforms where they can be determined. On others, offsets are always taken from the default anchor.
+//!
+//! On Linux and Android with the `symbols` feature enabled, vtables are instead resolved via the loaded object containing them, so registered anchors are ignored.
+//!
+//! # Example
+//! ```
+//! extern crate bincode;
+//! extern crate serde_traitobject as s;
+//!
+//! static PLUGIN_ANCHOR: () = ();
+//!
+//! // Typically called from within the shared object, so that the anchor's vtable lies there too.
+//! unsafe { s::anchors::register("plugin", &PLUGIN_ANCHOR) };
+//! assert!(s::anchors::registered("plugin"));
+//!
+//! let message: s::Box<dyn s::Debug> = s::Box::new(String::from("anchored"));
+//! let serialized = bincode::serialize(&message).unwrap();
+//! let deserialized: s::Box<dyn s::Debug> = bincode::deserialize(&serialized).unwrap();
+//! assert_eq!(format!("{:?}", deserialized), "\"anchored\"");
+//!
+//! s::anchors::unregister("plugin");
+//! assert!(!s::anchors::registered("plugin"));
+//! ```
+
+use std::{
+	any, convert::TryFrom, sync::{PoisonError, RwLock}
+};
+
+use super::{cipher, image, type_coerce, vtable_base};
+
+/// The registered anchors: the id derived from their name, their name, and the address of their vtable.
+static ANCHORS: RwLock<Vec<(u16, String, usize)>> = RwLock::new(Vec::new());
+
+/// Offsets from a registered anchor carry its id in their upper 16 bits, and are sign-extended from the lower 48.
+const OFFSET_BITS: u32 = 48;
+
+/// Register `anchor`, whose vtable lies within the loaded object that the unsizing coercion to `dyn Any + Sync` was made in, under `name`, replacing any anchor registered under it before.
+///
+/// # Safety
+///
+/// Every binary exchanging trait objects with this one must register an anchor under `name` at the same position within the same object. Otherwise deserializing trait objects whose vtables lie within it is undefined behavior.
+///
+/// # Panics
+///
+/// If another anchor is registered under a different name with the same 16-bit id derived from it.
+pub unsafe fn register(name: &str, anchor: &'static (dyn any::Any + Sync)) {
+	let id = id(name);
+	let trait_object: metatype::TraitObject = type_coerce(metatype::Type::meta(anchor));
+	let vtable = std::ptr::from_ref(trait_object.vtable) as usize;
+	let mut anchors = ANCHORS.write().unwrap_or_else(PoisonError::into_inner);
+	anchors.retain(|(_, n, _)| n != name);
+	assert!(
+		anchors.iter().all(|&(i, _, _)| i != id),
+		"anchor {:?} collides with another registered anchor; try a different name",
+		name
+	);
+	anchors.push((id, name.to_owned(), vtable));
+}
+
+/// Unregister the anchor registered under `name`, if any.
+pub fn unregister(name: &str) {
+	ANCHORS
+		.write()
+		.unwrap_or_else(PoisonError::into_inner)
+		.retain(|(_, n, _)| n != name);
+}
+
+/// Whether an anchor is registered under `name`.
+pub fn registered(name: &str) -> bool {
+	ANCHORS
+		.read()
+		.unwrap_or_else(PoisonError::into_inner)
+		.iter()
+		.any(|(_, n, _)| n == name)
+}
+
+/// The 16-bit id of the anchor registered under `name`, excluding the all-zeros and all-ones upper bits of offsets from the default anchor.
+fn id(name: &str) -> u16 {
+	let hash = name
+		.bytes()
+		.fold(0, |hash, byte| cipher::mix(hash ^ u64::from(byte)));
+	u16::try_from(hash % u64::from(u16::MAX - 1)).unwrap() + 1
+}
+
+/// The offset of `vtable` from the registered anchor within the same loaded object, if it doesn't lie within the default anchor's, and it's within 47 bits of it.
+pub(crate) fn offset(vtable: usize) -> Option<u64> {
+	if image::contains(vtable_base(), vtable, size_of::<[usize; 3]>()) {
+		return None;
+	}
+	let anchors = ANCHORS.read().unwrap_or_else(PoisonError::into_inner);
+	let &(id, _, anchor) = anchors
+		.iter()
+		.find(|&&(_, _, anchor)| image::contains(anchor, vtable, size_of::<[usize; 3]>()))?;
+	let offset = i64::try_from(vtable.wrapping_sub(anchor).cast_signed()).ok()?;
+	let limit = 1 << (OFFSET_BITS - 1);
+	(-limit..limit).contains(&offset).then(|| {
+		(u64::from(id) << OFFSET_BITS) | (offset.cast_unsigned() & ((1 << OFFSET_BITS) - 1))
+	})
+}
+
+/// Whether `offset` is relative to a registered anchor, rather than the default one.
+#[cfg_attr(
+	all(feature = "symbols", any(target_os = "linux", target_os = "android")),
+	allow(dead_code)
+)]
+pub(crate) fn anchored(offset: u64) -> bool {
+	let id = offset >> OFFSET_BITS;
+	id != 0 && id != u64::from(u16::MAX)
+}
+
+/// The address of the registered anchor `offset` is relative to, and the address it refers to, or `None` if no anchor with its id is registered.
+#[cfg_attr(
+	all(feature = "symbols", any(target_os = "linux", target_os = "android")),
+	allow(dead_code)
+)]
+pub(crate) fn resolve(offset: u64) -> Option<(usize, usize)> {
+	let id = offset >> OFFSET_BITS;
+	let anchors = ANCHORS.read().unwrap_or_else(PoisonError::into_inner);
+	let &(_, _, anchor) = anchors.iter().find(|&&(i, _, _)| u64::from(i) == id)?;
+	let signed = (offset << (64 - OFFSET_BITS)).cast_signed() >> (64 - OFFSET_BITS);
+	Some((
+		anchor,
+		anchor.wrapping_add_signed(isize::try_from(signed).ok()?),
+	))
+}
@@ -0,0 +1,39 @@
+//! (De)serialize a [`std::sync::Arc<T>`](std::sync::Arc), where `T` may be unsized, such as a trait object.
+//!
+//! Unlike using [`serialize`](crate::serialize) and [`deserialize`](crate::deserialize) directly, `T` is always inferred from the field type.
+//!
+//! This is intended to enable:
+//! ```
+//! # use serde_derive::{Serialize, Deserialize};
+//! use std::sync::Arc;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct MyStruct {
+//!     #[serde(with = "serde_traitobject::arc")]
+//!     field: Arc<dyn serde_traitobject::Any>,
+//! }
+//! ```
+
+use std::sync;
+
+use super::{serialize_ref, Deserialize, Serialize};
+
+/// Serialize an `Arc<T>`.
+pub fn serialize<T: Serialize + ?Sized + 'static, S>(
+	t: &sync::Arc<T>, serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	serialize_ref(&**t, serializer)
+}
+
+/// Deserialize an `Arc<T>`.
+pub fn deserialize<'de, T: Deserialize + ?Sized + 'static, D>(
+	deserializer: D,
+) -> Result<sync::Arc<T>, D::Error>
+where
+	D: serde::Deserializer<'de>,
+{
+	super::deserialize::<T, Box<T>, D>(deserializer).map(sync::Arc::from)
+}
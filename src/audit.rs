@@ -0,0 +1,124 @@
+//! An audit log of the vtables reconstructed when deserializing trait objects.
+//!
+//! With the `audit` feature enabled, a [`Sink`], installed with [`set_sink`], is invoked on every vtable resolved while deserializing a trait object, whether or not it resolves, with a [`Resolution`] describing the build it came from, the type ids involved, the address it resolved to and the outcome. Without the feature, none of this is compiled in.
+//!
+//! # Example
+//! ```
+//! extern crate bincode;
+//! extern crate serde_traitobject as s;
+//!
+//! use s::audit::Resolution;
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//!
+//! static RESOLVED: AtomicUsize = AtomicUsize::new(0);
+//!
+//! s::audit::set_sink(|resolution: &Resolution| {
+//!     if resolution.address().is_some() {
+//!         let _ = RESOLVED.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! });
+//!
+//! let message: s::Box<dyn s::Debug> = s::Box::new(String::from("audited"));
+//! let serialized = bincode::serialize(&message).unwrap();
+//! let _: s::Box<dyn s::Debug> = bincode::deserialize(&serialized).unwrap();
+//! assert_eq!(RESOLVED.load(Ordering::Relaxed), 1);
+//! ```
+
+use relative::Vtable;
+use std::{
+	any, fmt, ptr, sync::{Arc, PoisonError, RwLock}
+};
+
+use super::error::DeserializeError;
+
+/// A recipient of the [`Resolution`] of each vtable reconstructed.
+pub trait Sink: Send + Sync {
+	/// Called after a vtable is resolved, or fails to resolve.
+	fn record(&self, resolution: &Resolution<'_>);
+}
+impl<F> Sink for F
+where
+	F: Fn(&Resolution<'_>) + Send + Sync,
+{
+	fn record(&self, resolution: &Resolution<'_>) {
+		self(resolution);
+	}
+}
+
+/// A description of a vtable reconstructed, as passed to a [`Sink`].
+#[derive(Clone, Copy, Debug)]
+pub struct Resolution<'a> {
+	trait_object: &'static str,
+	trait_type_id: u64,
+	build: Option<u128>,
+	offset: Option<u64>,
+	type_id: Option<u64>,
+	outcome: Result<usize, &'a DeserializeError>,
+}
+impl Resolution<'_> {
+	/// The name of the trait object type, like `dyn Any`.
+	pub fn trait_object(&self) -> &'static str {
+		self.trait_object
+	}
+	/// The type id of the trait object type.
+	pub fn trait_type_id(&self) -> u64 {
+		self.trait_type_id
+	}
+	/// The build id, or schema version hash with [`compat`](crate::compat), of the binary the trait object came from, if known.
+	pub fn build(&self) -> Option<u128> {
+		self.build
+	}
+	/// The vtable offset, shifted into this binary, it was resolved from, or `None` if it was resolved from the type id of the concrete type via the [`tagged`](crate::tagged) registry, or the offset couldn't be read.
+	pub fn offset(&self) -> Option<u64> {
+		self.offset
+	}
+	/// The type id of the concrete type, if it's known at this point.
+	pub fn type_id(&self) -> Option<u64> {
+		self.type_id
+	}
+	/// The address of the vtable it resolved to, if it resolved.
+	pub fn address(&self) -> Option<usize> {
+		self.outcome.ok()
+	}
+	/// The address of the vtable it resolved to, or the error it failed to resolve with.
+	pub fn outcome(&self) -> Result<usize, &DeserializeError> {
+		self.outcome
+	}
+}
+
+static SINK: RwLock<Option<Arc<dyn Sink>>> = RwLock::new(None);
+
+/// Install `sink` for all threads, replacing any installed before.
+pub fn set_sink<S: Sink + 'static>(sink: S) {
+	*SINK.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(sink));
+}
+
+/// Remove the sink installed with [`set_sink`], if any.
+pub fn remove_sink() {
+	*SINK.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Invoke the installed [`Sink`], if any, on the `result` of resolving a `T` vtable.
+pub(crate) fn record<T: ?Sized + 'static, E: fmt::Display>(
+	build: Option<u128>, offset: Option<u64>, type_id: Option<u64>, result: &Result<Vtable<T>, E>,
+) {
+	let Some(sink) = SINK.read().unwrap_or_else(PoisonError::into_inner).clone() else {
+		return;
+	};
+	let error;
+	let outcome = match result {
+		Ok(vtable) => Ok(ptr::from_ref(vtable.to()) as usize),
+		Err(e) => {
+			error = DeserializeError::failed(e);
+			Err(&error)
+		}
+	};
+	sink.record(&Resolution {
+		trait_object: any::type_name::<T>(),
+		trait_type_id: metatype::type_id::<T>(),
+		build,
+		offset,
+		type_id,
+		outcome,
+	});
+}
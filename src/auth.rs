@@ -0,0 +1,104 @@
+//! Authentication of trait objects with a secret key.
+//!
+//! The validation data serialized alongside a trait object guards against accidental misuse, but not against tampering: a modified vtable offset can still lead to a jump to an arbitrary address. With the `hmac` feature enabled and a key installed, either globally with [`set_key`] or for the duration of a closure with [`with_key`], the fixed-width header that formats that aren't human-readable, like `bincode`, use by default, and that the [`bytes`](crate::bytes) module uses for all formats, is followed by an HMAC-SHA256 tag. On deserialization the tag is verified before the vtable is used.
+//!
+//! While a key is installed, trait objects that aren't authenticated are rejected, including those in encodings that can't be authenticated, like the named encoding that human-readable formats use by default.
+//!
+//! # Example
+//! ```
+//! extern crate bincode;
+//! extern crate serde_traitobject as s;
+//! # s::policy::set_policy(s::policy::Policy::Error);
+//!
+//! let message: s::Box<dyn s::Debug> = s::Box::new(String::from("authenticated"));
+//!
+//! let serialized = s::auth::with_key(b"secret", || bincode::serialize(&message)).unwrap();
+//! let deserialized: s::Box<dyn s::Debug> =
+//!     s::auth::with_key(b"secret", || bincode::deserialize(&serialized)).unwrap();
+//! assert_eq!(format!("{:?}", deserialized), "\"authenticated\"");
+//!
+//! let result: Result<s::Box<dyn s::Debug>, _> =
+//!     s::auth::with_key(b"other", || bincode::deserialize(&serialized));
+//! assert!(result.is_err());
+//! let unauthenticated = bincode::serialize(&message).unwrap();
+//! let result: Result<s::Box<dyn s::Debug>, _> =
+//!     s::auth::with_key(b"secret", || bincode::deserialize(&unauthenticated));
+//! assert!(result.is_err());
+//! ```
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::{
+	cell::RefCell, sync::{PoisonError, RwLock}
+};
+
+use super::{error::DeserializeError, Header};
+
+/// The length of the tag following an authenticated [`Header`].
+pub(crate) const TAG_LEN: usize = 32;
+
+static KEY: RwLock<Option<Vec<u8>>> = RwLock::new(None);
+
+thread_local! {
+	static SCOPED_KEY: RefCell<Option<Vec<u8>>> = const { RefCell::new(None) };
+}
+
+/// Install `key` for all threads, or remove it if `None`, such that trait objects are authenticated with it.
+///
+/// A key installed with [`with_key`] takes precedence.
+pub fn set_key(key: Option<&[u8]>) {
+	*KEY.write().unwrap_or_else(PoisonError::into_inner) = key.map(<[u8]>::to_vec);
+}
+
+/// Run `f` with `key` installed on this thread, such that the trait objects (de)serialized by it are authenticated with it.
+///
+/// This takes precedence over a key installed with [`set_key`], and any key installed by an enclosing `with_key`.
+pub fn with_key<F, R>(key: &[u8], f: F) -> R
+where
+	F: FnOnce() -> R,
+{
+	struct Guard(Option<Vec<u8>>);
+	impl Drop for Guard {
+		fn drop(&mut self) {
+			SCOPED_KEY.with(|scoped| *scoped.borrow_mut() = self.0.take());
+		}
+	}
+	let _guard = Guard(SCOPED_KEY.with(|scoped| scoped.borrow_mut().replace(key.to_vec())));
+	f()
+}
+
+/// The key trait objects are currently authenticated with, if any.
+fn key() -> Option<Vec<u8>> {
+	SCOPED_KEY
+		.with(|scoped| scoped.borrow().clone())
+		.or_else(|| KEY.read().unwrap_or_else(PoisonError::into_inner).clone())
+}
+
+/// Whether a key is installed, such that trait objects that can't be authenticated must be rejected.
+pub(crate) fn enabled() -> bool {
+	key().is_some()
+}
+
+fn mac(key: &[u8], header: &[u8; Header::LEN]) -> Hmac<Sha256> {
+	let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("HMAC accepts keys of any length");
+	mac.update(header);
+	mac
+}
+
+/// The tag to write after `header`, if a key is installed.
+pub(crate) fn sign(header: &[u8; Header::LEN]) -> Option<[u8; TAG_LEN]> {
+	key().map(|key| mac(&key, header).finalize().into_bytes().into())
+}
+
+/// Verify the tag read after `header`, if any, erroring if a key is installed and the tag is missing or doesn't match.
+pub(crate) fn verify(
+	header: &[u8; Header::LEN], tag: Option<&[u8]>,
+) -> Result<(), DeserializeError> {
+	match (key(), tag) {
+		(None, _) => Ok(()),
+		(Some(_), None) => Err(DeserializeError::Unauthenticated),
+		(Some(key), Some(tag)) => mac(&key, header)
+			.verify_slice(tag)
+			.map_err(|_| DeserializeError::AuthenticationFailed),
+	}
+}
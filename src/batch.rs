@@ -0,0 +1,205 @@
+//! (De)serialize many trait objects of the same type at once.
+//!
+//! Each trait object serialized individually carries its own validation data: the build id, the type id of the trait object and so on. A [`Batch`] instead writes this once, followed by just the vtable offset, concrete type id and value of each element, which for many small trait objects makes up most of the payload. Each element is checked against its concrete type id as individually serialized trait objects are.
+//!
+//! # Example
+//! ```
+//! extern crate bincode;
+//! extern crate serde_traitobject as s;
+//!
+//! let batch: s::batch::Batch<dyn s::Debug> =
+//!     (0..1000_u32).map(|i| -> Box<dyn s::Debug> { Box::new(i) }).collect();
+//! let serialized = bincode::serialize(&batch).unwrap();
+//!
+//! let individually: Vec<s::Box<dyn s::Debug>> =
+//!     (0..1000_u32).map(|i| s::Box::new(i) as s::Box<dyn s::Debug>).collect();
+//! assert!(serialized.len() < bincode::serialize(&individually).unwrap().len() / 3);
+//!
+//! let deserialized: s::batch::Batch<dyn s::Debug> = bincode::deserialize(&serialized).unwrap();
+//! assert_eq!(format!("{:?}", deserialized[999]), "999");
+//! ```
+
+use serde::ser::SerializeTuple;
+use std::{any, cell::Cell, fmt, iter, marker, ops};
+
+use super::{
+	builds, check_origin, check_version, error::DeserializeError, Deserialize, Deserializer, DeserializerTrait, Encoding, Hex, Serialize, Serializer, SerializerTrait, FORMAT_VERSION
+};
+
+/// A `Vec` of boxed trait objects that are (de)serialized with their validation data written once for the whole batch.
+pub struct Batch<T: ?Sized>(Vec<Box<T>>);
+impl<T: ?Sized> Batch<T> {
+	/// Create an empty `Batch`.
+	pub fn new() -> Self {
+		Self(Vec::new())
+	}
+}
+impl<T: ?Sized> Default for Batch<T> {
+	fn default() -> Self {
+		Self::new()
+	}
+}
+impl<T: ?Sized> ops::Deref for Batch<T> {
+	type Target = Vec<Box<T>>;
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+impl<T: ?Sized> ops::DerefMut for Batch<T> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.0
+	}
+}
+impl<T: ?Sized> From<Vec<Box<T>>> for Batch<T> {
+	fn from(t: Vec<Box<T>>) -> Self {
+		Self(t)
+	}
+}
+impl<T: ?Sized> From<Batch<T>> for Vec<Box<T>> {
+	fn from(v: Batch<T>) -> Self {
+		v.0
+	}
+}
+impl<T: ?Sized> iter::FromIterator<Box<T>> for Batch<T> {
+	fn from_iter<I: IntoIterator<Item = Box<T>>>(iter: I) -> Self {
+		Self(iter.into_iter().collect())
+	}
+}
+impl<T: ?Sized> IntoIterator for Batch<T> {
+	type Item = Box<T>;
+	type IntoIter = std::vec::IntoIter<Box<T>>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Batch<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		self.0.fmt(f)
+	}
+}
+impl<T: Serialize + ?Sized + 'static> serde::ser::Serialize for Batch<T> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		struct Element<'a, T: ?Sized>(&'a T);
+		impl<T: Serialize + ?Sized + 'static> serde::ser::Serialize for Element<'_, T> {
+			fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+			where
+				S: serde::Serializer,
+			{
+				Serializer::<T>::serialize(self.0, serializer, Encoding::Batched)
+			}
+		}
+		struct Elements<'a, T: ?Sized>(&'a [Box<T>]);
+		impl<T: Serialize + ?Sized + 'static> serde::ser::Serialize for Elements<'_, T> {
+			fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+			where
+				S: serde::Serializer,
+			{
+				serializer.collect_seq(self.0.iter().map(|t| Element(&**t)))
+			}
+		}
+		let human_readable = serializer.is_human_readable();
+		let mut tup = serializer.serialize_tuple(4)?;
+		tup.serialize_element::<u8>(&FORMAT_VERSION)?;
+		if human_readable {
+			tup.serialize_element::<String>(&build_id::get().to_string())?;
+		} else {
+			tup.serialize_element::<u128>(&build_id::get().as_u128())?;
+		}
+		tup.serialize_element::<Hex>(&Hex(metatype::type_id::<T>()))?;
+		tup.serialize_element::<Elements<'_, T>>(&Elements(&self.0))?;
+		tup.end()
+	}
+}
+impl<'de, T: Deserialize + ?Sized + 'static> serde::de::Deserialize<'de> for Batch<T> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		struct Element<T: ?Sized>(Box<T>);
+		impl<'de, T: Deserialize + ?Sized + 'static> serde::de::Deserialize<'de> for Element<T> {
+			fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+			where
+				D: serde::Deserializer<'de>,
+			{
+				Deserializer::<T>::deserialize(deserializer, Encoding::Batched).map(Self)
+			}
+		}
+		struct Visitor<T: ?Sized>(bool, marker::PhantomData<fn() -> T>);
+		impl<'de, T: Deserialize + ?Sized + 'static> serde::de::Visitor<'de> for Visitor<T> {
+			type Value = Batch<T>;
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				write!(
+					formatter,
+					"a batch of \"{}\" trait objects",
+					any::type_name::<T>()
+				)
+			}
+			fn visit_seq<A>(self, mut seq: A) -> Result<Batch<T>, A::Error>
+			where
+				A: serde::de::SeqAccess<'de>,
+			{
+				let Some(version) = seq.next_element::<u8>()? else {
+					return Err(serde::de::Error::invalid_length(0, &self));
+				};
+				if check_version(version)? {
+					return Err(DeserializeError::UnsupportedVersion { version }.raise());
+				}
+				let build = if self.0 {
+					seq.next_element::<String>()?
+						.map(|build| builds::parse(&build))
+				} else {
+					seq.next_element::<u128>()?.map(Some)
+				};
+				let Some(build) = build else {
+					return Err(serde::de::Error::invalid_length(1, &self));
+				};
+				let shift = build.and_then(builds::shift);
+				let Some(Hex(trait_type_id)) = seq.next_element()? else {
+					return Err(serde::de::Error::invalid_length(2, &self));
+				};
+				check_origin::<T, A::Error>(shift.is_some(), trait_type_id)?;
+				let elements = with_shift(shift.unwrap_or(0), build, || {
+					seq.next_element::<Vec<Element<T>>>()
+				})?;
+				let Some(elements) = elements else {
+					return Err(serde::de::Error::invalid_length(3, &self));
+				};
+				Ok(Batch(elements.into_iter().map(|t| t.0).collect()))
+			}
+		}
+		let human_readable = deserializer.is_human_readable();
+		deserializer.deserialize_tuple(4, Visitor(human_readable, marker::PhantomData))
+	}
+}
+
+thread_local! {
+	static SHIFT: Cell<(i64, Option<u128>)> = const { Cell::new((0, None)) };
+}
+
+/// Run `f` with the vtable offsets of the elements of the batch being deserialized shifted by `shift`, as [registered](builds::register) for the binary it came from, with build id `build`.
+fn with_shift<F, R>(shift: i64, build: Option<u128>, f: F) -> R
+where
+	F: FnOnce() -> R,
+{
+	struct Guard((i64, Option<u128>));
+	impl Drop for Guard {
+		fn drop(&mut self) {
+			SHIFT.with(|scoped| scoped.set(self.0));
+		}
+	}
+	let _guard = Guard(SHIFT.with(|scoped| scoped.replace((shift, build))));
+	f()
+}
+
+/// The shift to apply to the vtable offsets of the elements of the batch being deserialized.
+pub(crate) fn shift() -> i64 {
+	SHIFT.with(Cell::get).0
+}
+
+/// The build id of the binary the batch being deserialized came from, if it could be read.
+pub(crate) fn build() -> Option<u128> {
+	SHIFT.with(Cell::get).1
+}
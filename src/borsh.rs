@@ -0,0 +1,77 @@
+//! (De)serialize trait objects with `borsh`.
+//!
+//! With the `borsh` feature enabled, the [`Box`], [`Rc`] and [`Arc`] wrappers implement `BorshSerialize` and `BorshDeserialize`. A trait object is written as the same fixed-width header as the [`bytes`](crate::bytes) encoding, which is validated before anything else is read, followed by its value serialized with `bincode` as a `borsh` byte vector. The concrete types therefore still need to implement `serde`'s traits, rather than `borsh`'s.
+//!
+//! # Example
+//! ```
+//! extern crate borsh;
+//! extern crate serde_traitobject as s;
+//! # s::policy::set_policy(s::policy::Policy::Error);
+//!
+//! let value: s::Box<dyn s::Debug> = s::Box::new(String::from("borsh"));
+//! let serialized = borsh::to_vec(&value).unwrap();
+//! let deserialized: s::Box<dyn s::Debug> = borsh::from_slice(&serialized).unwrap();
+//! assert_eq!(format!("{:?}", deserialized), "\"borsh\"");
+//! # let shared: s::Arc<dyn s::Debug> = s::Arc::new(1_u8);
+//! # let shared: s::Arc<dyn s::Debug> = borsh::from_slice(&borsh::to_vec(&shared).unwrap()).unwrap();
+//! # assert_eq!(format!("{:?}", shared), "1");
+//! # assert!(borsh::from_slice::<s::Box<dyn s::Display>>(&serialized).is_err());
+//! ```
+
+use borsh::{BorshDeserialize, BorshSerialize};
+use std::{boxed, io, rc, sync};
+
+use super::{codec, Arc, Box, Deserialize, Header, Rc, Serialize};
+
+fn invalid_data(error: bincode::Error) -> io::Error {
+	io::Error::new(io::ErrorKind::InvalidData, error)
+}
+
+fn serialize<T: Serialize + ?Sized + 'static, W: io::Write>(
+	t: &T, writer: &mut W,
+) -> io::Result<()> {
+	let (header, value) = codec::to_header(t).map_err(invalid_data)?;
+	writer.write_all(&header)?;
+	BorshSerialize::serialize(&value, writer)
+}
+
+fn deserialize<T: Deserialize + ?Sized + 'static, R: io::Read>(
+	reader: &mut R,
+) -> io::Result<boxed::Box<T>> {
+	let mut header = [0; Header::LEN];
+	reader.read_exact(&mut header)?;
+	codec::check_header::<T>(&header).map_err(invalid_data)?;
+	let value = <Vec<u8> as BorshDeserialize>::deserialize_reader(reader)?;
+	codec::from_header(&header, &value).map_err(invalid_data)
+}
+
+impl<T: Serialize + ?Sized + 'static> BorshSerialize for Box<T> {
+	fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+		serialize::<T, W>(AsRef::<T>::as_ref(self), writer)
+	}
+}
+impl<T: Deserialize + ?Sized + 'static> BorshDeserialize for Box<T> {
+	fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+		deserialize::<T, R>(reader).map(Self::from)
+	}
+}
+impl<T: Serialize + ?Sized + 'static> BorshSerialize for Rc<T> {
+	fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+		serialize::<T, W>(AsRef::<T>::as_ref(self), writer)
+	}
+}
+impl<T: Deserialize + ?Sized + 'static> BorshDeserialize for Rc<T> {
+	fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+		deserialize::<T, R>(reader).map(|t| Self::from(rc::Rc::from(t)))
+	}
+}
+impl<T: Serialize + ?Sized + 'static> BorshSerialize for Arc<T> {
+	fn serialize<W: io::Write>(&self, writer: &mut W) -> io::Result<()> {
+		serialize::<T, W>(AsRef::<T>::as_ref(self), writer)
+	}
+}
+impl<T: Deserialize + ?Sized + 'static> BorshDeserialize for Arc<T> {
+	fn deserialize_reader<R: io::Read>(reader: &mut R) -> io::Result<Self> {
+		deserialize::<T, R>(reader).map(|t| Self::from(sync::Arc::from(t)))
+	}
+}
@@ -0,0 +1,71 @@
+//! Equivalences between this binary and others it exchanges trait objects with.
+//!
+//! By default a trait object can only be deserialized by the binary that serialized it, as identified by its [build id](https://github.com/alecmocatta/build_id). Binaries built from the same workspace, like a driver and its workers, can however share the code of the trait objects they exchange, laid out identically up to a shift. [`register`] declares another binary's build id as trusted, along with that shift, such that its trait objects are accepted, with their vtable offsets shifted into this binary.
+//!
+//! Each binary registers the others it trusts; the shifts registered by a pair of binaries for each other are negations of one another.
+//!
+//! # Example
+//! ```
+//! extern crate serde_traitobject as s;
+//!
+//! // The build id of the worker binary, and the offset of its vtables from this binary's.
+//! let worker = 0x6c9b_a0e0_4f6e_4c2a_8f0b_7a7d_9e3b_1c42;
+//! unsafe { s::builds::register(worker, 0x1000) };
+//! assert_eq!(s::builds::registered(worker), Some(0x1000));
+//! s::builds::unregister(worker);
+//! assert_eq!(s::builds::registered(worker), None);
+//! ```
+
+use std::sync::{PoisonError, RwLock};
+
+use super::constant_time;
+
+static BUILDS: RwLock<Vec<(u128, i64)>> = RwLock::new(Vec::new());
+
+/// Trust trait objects serialized by the binary with build id `build`, replacing any shift registered for it before.
+///
+/// The vtable offsets of its trait objects are shifted by `shift` to give the vtable offsets of the same types in this binary.
+///
+/// # Safety
+///
+/// The vtable of every trait object the binary serializes, shifted by `shift`, must be the vtable of the same type in this binary. Otherwise deserializing its trait objects is undefined behavior.
+pub unsafe fn register(build: u128, shift: i64) {
+	let mut builds = BUILDS.write().unwrap_or_else(PoisonError::into_inner);
+	builds.retain(|&(b, _)| b != build);
+	builds.push((build, shift));
+}
+
+/// Stop trusting trait objects serialized by the binary with build id `build`.
+pub fn unregister(build: u128) {
+	BUILDS
+		.write()
+		.unwrap_or_else(PoisonError::into_inner)
+		.retain(|&(b, _)| b != build);
+}
+
+/// The shift registered for the binary with build id `build`, if any.
+pub fn registered(build: u128) -> Option<i64> {
+	BUILDS
+		.read()
+		.unwrap_or_else(PoisonError::into_inner)
+		.iter()
+		.find(|&&(b, _)| constant_time::eq_u128(b, build))
+		.map(|&(_, shift)| shift)
+}
+
+/// The shift to apply to the vtable offsets of trait objects serialized by the binary with build id `build`, if they can be trusted: zero if it's this binary.
+pub(crate) fn shift(build: u128) -> Option<i64> {
+	if constant_time::eq_u128(build, build_id::get().as_u128()) {
+		return Some(0);
+	}
+	registered(build)
+}
+
+/// Parse a build id as written by human-readable formats, with or without hyphens.
+pub(crate) fn parse(build: &str) -> Option<u128> {
+	let build = build.replace('-', "");
+	if build.len() != 32 {
+		return None;
+	}
+	u128::from_str_radix(&build, 16).ok()
+}
@@ -0,0 +1,51 @@
+//! (De)serialize a trait object with a fixed-width header, for minimal formats.
+//!
+//! The default encoding's header is made up of tuples and integers of varying widths. This encoding instead packs the format version, target, build id, type ids and vtable offset into a single 52 byte array, longer with the `paranoid` and `wide_type_id` features enabled, written with `serialize_bytes`, followed by the value. This is the default for formats that aren't human-readable; this module forces it for all formats.
+//!
+//! This is intended to enable:
+//! ```
+//! # use serde_derive::{Serialize, Deserialize};
+//! extern crate bincode;
+//! extern crate serde_traitobject as s;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct MyStruct {
+//!     #[serde(with = "serde_traitobject::bytes")]
+//!     field: Box<dyn serde_traitobject::Debug>,
+//! }
+//!
+//! let my_struct = MyStruct {
+//!     field: Box::new(String::from("fixed")),
+//! };
+//! let serialized = bincode::serialize(&my_struct).unwrap();
+//! let deserialized: MyStruct = bincode::deserialize(&serialized).unwrap();
+//!
+//! println!("{:?}", deserialized.field);
+//! # assert_eq!(format!("{:?}", deserialized.field), "\"fixed\"");
+//! // "fixed"
+//! ```
+
+use super::{
+	Deserialize, Deserializer, DeserializerTrait, Encoding, Serialize, Serializer, SerializerTrait
+};
+
+/// Serialize a value by reference, with a fixed-width header.
+pub fn serialize<T: Serialize + ?Sized + 'static, B: AsRef<T> + ?Sized, S>(
+	t: &B, serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	Serializer::<T>::serialize(t.as_ref(), serializer, Encoding::Bytes)
+}
+
+/// Deserialize a value `T` into `B` where `Box<T>: Into<B>`, from a fixed-width header.
+pub fn deserialize<'de, T: Deserialize + ?Sized + 'static, B, D>(
+	deserializer: D,
+) -> Result<B, D::Error>
+where
+	D: serde::Deserializer<'de>,
+	Box<T>: Into<B>,
+{
+	Deserializer::<T>::deserialize(deserializer, Encoding::Bytes).map(<Box<T> as Into<B>>::into)
+}
@@ -0,0 +1,92 @@
+//! (De)serialize a trait object as a tagged CBOR data item.
+//!
+//! With the `cbor` feature enabled, this module writes a trait object in the [`bytes`](crate::bytes) encoding under the CBOR tag [`TAG`], such that generic CBOR tooling can at least recognize these values, and skip them. Both tagged and untagged values are accepted on deserialization. It relies on the tagging support of `ciborium`, so it should only be used with `ciborium`.
+//!
+//! This is intended to enable:
+//! ```
+//! # use serde_derive::{Serialize, Deserialize};
+//! extern crate ciborium;
+//! extern crate serde_traitobject as s;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct MyStruct {
+//!     #[serde(with = "serde_traitobject::cbor")]
+//!     field: Box<dyn serde_traitobject::Debug>,
+//! }
+//!
+//! let my_struct = MyStruct {
+//!     field: Box::new(String::from("tagged")),
+//! };
+//! let mut serialized = Vec::new();
+//! ciborium::ser::into_writer(&my_struct, &mut serialized).unwrap();
+//! # assert!(serialized.windows(5).any(|w| w == [0xda, 0x53, 0x54, 0x4f, 0x42]));
+//! let deserialized: MyStruct = ciborium::de::from_reader(&serialized[..]).unwrap();
+//!
+//! println!("{:?}", deserialized.field);
+//! # assert_eq!(format!("{:?}", deserialized.field), "\"tagged\"");
+//! // "tagged"
+//!
+//! #[derive(Serialize)]
+//! struct Untagged {
+//!     #[serde(with = "serde_traitobject::bytes")]
+//!     field: Box<dyn serde_traitobject::Debug>,
+//! }
+//! let mut untagged = Vec::new();
+//! ciborium::ser::into_writer(&Untagged { field: deserialized.field }, &mut untagged).unwrap();
+//! assert_ne!(untagged, serialized);
+//! let deserialized: MyStruct = ciborium::de::from_reader(&untagged[..]).unwrap();
+//! # assert_eq!(format!("{:?}", deserialized.field), "\"tagged\"");
+//! ```
+
+use super::{
+	Deserialize, Deserializer, DeserializerTrait, Encoding, Serialize, Serializer, SerializerTrait
+};
+
+/// The CBOR tag trait objects are written under, from the first come first served range of the IANA registry.
+pub const TAG: u64 = 0x5354_4f42;
+
+struct Untagged<'a, T: ?Sized>(&'a T);
+impl<T: Serialize + ?Sized + 'static> serde::ser::Serialize for Untagged<'_, T> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		Serializer::<T>::serialize(self.0, serializer, Encoding::Bytes)
+	}
+}
+struct UntaggedBox<T: ?Sized>(Box<T>);
+impl<'de, T: Deserialize + ?Sized + 'static> serde::de::Deserialize<'de> for UntaggedBox<T> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		Deserializer::<T>::deserialize(deserializer, Encoding::Bytes).map(Self)
+	}
+}
+
+/// Serialize a value by reference, under the CBOR tag [`TAG`].
+pub fn serialize<T: Serialize + ?Sized + 'static, B: AsRef<T> + ?Sized, S>(
+	t: &B, serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	serde::ser::Serialize::serialize(
+		&ciborium::tag::Required::<_, TAG>(Untagged(t.as_ref())),
+		serializer,
+	)
+}
+
+/// Deserialize a value `T` into `B` where `Box<T>: Into<B>`, accepting it with or without the CBOR tag [`TAG`].
+pub fn deserialize<'de, T: Deserialize + ?Sized + 'static, B, D>(
+	deserializer: D,
+) -> Result<B, D::Error>
+where
+	D: serde::Deserializer<'de>,
+	Box<T>: Into<B>,
+{
+	<ciborium::tag::Accepted<UntaggedBox<T>, TAG> as serde::de::Deserialize>::deserialize(
+		deserializer,
+	)
+	.map(|t| (t.0).0.into())
+}
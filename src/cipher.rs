@@ -0,0 +1,46 @@
+//! A keyed permutation of 128-bit blocks, used to mix the vtable offset of a [`Header`](super::Header) with its validation data.
+//!
+//! This isn't cryptographically secure, and so doesn't protect against malicious tampering. It only ensures that corruption of any part of a header is vanishingly unlikely to leave it valid.
+
+/// The number of rounds of the Feistel network.
+const ROUNDS: usize = 8;
+
+/// A Feistel network over pairs of `u64`s, with round keys derived from the validation data.
+pub(crate) struct Cipher([u64; ROUNDS]);
+impl Cipher {
+	pub(crate) fn new(key: &[u8]) -> Self {
+		let mut state = 0x9e37_79b9_7f4a_7c15_u64;
+		for chunk in key.chunks(8) {
+			let mut word = [0; 8];
+			word[..chunk.len()].copy_from_slice(chunk);
+			state = mix(state ^ u64::from_le_bytes(word));
+		}
+		let mut keys = [0; ROUNDS];
+		for key in &mut keys {
+			state = mix(state.wrapping_add(0x9e37_79b9_7f4a_7c15));
+			*key = state;
+		}
+		Self(keys)
+	}
+
+	pub(crate) fn encrypt(&self, [mut left, mut right]: [u64; 2]) -> [u64; 2] {
+		for &key in &self.0 {
+			(left, right) = (right, left ^ mix(right ^ key));
+		}
+		[left, right]
+	}
+
+	pub(crate) fn decrypt(&self, [mut left, mut right]: [u64; 2]) -> [u64; 2] {
+		for &key in self.0.iter().rev() {
+			(left, right) = (right ^ mix(left ^ key), left);
+		}
+		[left, right]
+	}
+}
+
+/// The finalizer of `SplitMix64`, a bijection that thoroughly mixes the bits of a `u64`.
+pub(crate) fn mix(mut x: u64) -> u64 {
+	x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+	x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+	x ^ (x >> 31)
+}
@@ -0,0 +1,147 @@
+//! Helpers that (de)serialize a trait object with a preselected format, for when the format doesn't otherwise matter.
+
+#[cfg(any(feature = "borsh", feature = "rkyv"))]
+use bincode::Options;
+#[cfg(any(feature = "borsh", feature = "rkyv"))]
+use std::{any, boxed};
+
+#[cfg(feature = "json")]
+use super::FIELDS;
+#[cfg(any(feature = "borsh", feature = "rkyv"))]
+use super::{
+	check_unauthenticated, check_version, deserialize, error::DeserializeError, hook, notify_deserialize, serialize, trait_object, type_coerce, validate::HeaderInfo, vtable_offset, DeserializeErased, Header, SerializeErased, FORMAT_VERSION
+};
+use super::{Box, Deserialize, Serialize, SerializeRef};
+
+/// Serialize a trait object to bytes, using `bincode`.
+///
+/// The bytes can be deserialized with [`from_bytes`] by the same binary.
+///
+/// # Example
+/// ```
+/// extern crate serde_traitobject as s;
+///
+/// let bytes = s::to_bytes::<dyn s::Debug>(&String::from("over a pipe")).unwrap();
+/// let value: s::Box<dyn s::Debug> = s::from_bytes(&bytes).unwrap();
+/// assert_eq!(format!("{:?}", value), "\"over a pipe\"");
+/// ```
+#[cfg(feature = "bincode")]
+pub fn to_bytes<T: Serialize + ?Sized + 'static>(t: &T) -> Result<Vec<u8>, bincode::Error> {
+	bincode::serialize(&SerializeRef(t))
+}
+
+/// Deserialize a trait object from bytes written by [`to_bytes`], validating that they were written by this binary.
+#[cfg(feature = "bincode")]
+pub fn from_bytes<T: Deserialize + ?Sized + 'static>(
+	bytes: &[u8],
+) -> Result<Box<T>, bincode::Error> {
+	bincode::deserialize(bytes)
+}
+
+/// Convert a trait object to a `serde_json::Value`.
+///
+/// The vtable offset and type ids are written as hex strings rather than numbers, so the value survives JSON implementations that parse all numbers as `f64`, like JavaScript's.
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// let value = s::to_value::<dyn s::Debug>(&String::from("enveloped")).unwrap();
+/// let envelope = serde_json::json!({ "id": 1, "payload": value });
+///
+/// let serialized = envelope.to_string();
+/// let mut envelope: serde_json::Value = serde_json::from_str(&serialized).unwrap();
+/// let payload: s::Box<dyn s::Debug> = s::from_value(envelope["payload"].take()).unwrap();
+/// assert_eq!(format!("{:?}", payload), "\"enveloped\"");
+/// ```
+#[cfg(feature = "json")]
+pub fn to_value<T: Serialize + ?Sized + 'static>(
+	t: &T,
+) -> Result<serde_json::Value, serde_json::Error> {
+	serde_json::to_value(SerializeRef(t))
+}
+
+/// Convert a `serde_json::Value` written by [`to_value`] back to a trait object, validating that it was written by this binary.
+///
+/// Unless `serde_json`'s `preserve_order` feature is enabled, `Value` sorts the fields of objects by name, whereas the `value` field must come last. The fields are therefore put back in order before deserializing.
+#[cfg(feature = "json")]
+pub fn from_value<T: Deserialize + ?Sized + 'static>(
+	value: serde_json::Value,
+) -> Result<Box<T>, serde_json::Error> {
+	let value = match value {
+		serde_json::Value::Object(mut map) => serde_json::Value::Array(
+			FIELDS
+				.iter()
+				.filter_map(|field| map.remove(*field))
+				.collect(),
+		),
+		value => value,
+	};
+	serde_json::from_value(value)
+}
+
+/// The `bincode` configuration values are (de)serialized with by [`to_header`] and [`from_header`], matching `bincode::serialize`.
+#[cfg(any(feature = "borsh", feature = "rkyv"))]
+fn options() -> impl Options {
+	bincode::DefaultOptions::new()
+		.with_fixint_encoding()
+		.allow_trailing_bytes()
+}
+
+/// Split a trait object into the fixed-width header of [`Encoding::Bytes`](super::Encoding::Bytes) and its value serialized with `bincode`, for formats other than serde's to embed.
+#[cfg(any(feature = "borsh", feature = "rkyv"))]
+pub(crate) fn to_header<T: Serialize + ?Sized + 'static>(
+	t: &T,
+) -> Result<([u8; Header::LEN], Vec<u8>), bincode::Error> {
+	let metatype::MetaType::TraitObject = metatype::Type::meta_type(t) else {
+		return Err(serde::ser::Error::custom(format_args!(
+			"\"{}\" isn't a trait object",
+			any::type_name::<T>()
+		)));
+	};
+	let meta: metatype::TraitObject = type_coerce(metatype::Type::meta(t));
+	let type_id = <T as serialize::Sealed>::type_id(t);
+	if let Some(hook) = hook::hook() {
+		hook.on_serialize(&HeaderInfo::new::<T>(
+			vtable_offset(meta.vtable),
+			Some(type_id),
+			Some(<T as serialize::Sealed>::serialize_type_name(t)),
+		));
+	}
+	let header = Header::new(FORMAT_VERSION, t).0;
+	Ok((header, options().serialize(&SerializeErased(t))?))
+}
+
+/// Validate a header written by [`to_header`] that it was written by this binary, as a `T`.
+#[cfg(any(feature = "borsh", feature = "rkyv"))]
+pub(crate) fn check_header<T: ?Sized + 'static>(
+	header: &[u8; Header::LEN],
+) -> Result<(), bincode::Error> {
+	check_unauthenticated::<bincode::Error>()?;
+	let header = Header(*header);
+	let _ = check_version::<bincode::Error>(header.version())?;
+	header.parse::<T, bincode::Error>().map(drop)
+}
+
+/// Validate a header and deserialize the value written by [`to_header`].
+#[cfg(any(feature = "borsh", feature = "rkyv"))]
+pub(crate) fn from_header<T: Deserialize + ?Sized + 'static>(
+	header: &[u8; Header::LEN], value: &[u8],
+) -> Result<boxed::Box<T>, bincode::Error> {
+	DeserializeError::clear();
+	let result = (|| {
+		check_unauthenticated::<bincode::Error>()?;
+		let header = Header(*header);
+		let _ = check_version::<bincode::Error>(header.version())?;
+		let (vtable, type_id) = header.parse::<T, bincode::Error>()?;
+		let object = trait_object::<T, bincode::Error>(vtable, type_id)?;
+		header.check_wide_type_id::<T, bincode::Error>(object)?;
+		options().deserialize_seed(DeserializeErased(object, &mut deserialize::heap), value)
+	})();
+	notify_deserialize(&result);
+	if result.is_ok() {
+		DeserializeError::clear();
+	}
+	Ok(unsafe { boxed::Box::from_raw(result?) })
+}
@@ -0,0 +1,78 @@
+//! Schema-version mode, for exchanging trait objects between different builds that are asserted to be compatible.
+//!
+//! During a rolling upgrade old and new binaries coexist, and as their build ids differ, every trait object exchanged between them is rejected. With a schema version set by [`set_schema_version`], typically at startup, the fixed-width header that formats that aren't human-readable, like `bincode`, use by default, and that the [`bytes`](crate::bytes) module uses for all formats, carries a hash of the schema version in place of the build id. A trait object whose header carries the same hash is accepted from any build, and rather than from its vtable offset, which is meaningless to a different build, its concrete type is resolved from the type id of the concrete type via the [`tagged`](crate::tagged) registry, such that only [registered](crate::tagged::register) types can be deserialized.
+//!
+//! Setting the same schema version on two builds asserts that the trait objects they exchange, and the type ids of their concrete types, are compatible. Trait objects in other encodings, like the named encoding that human-readable formats use by default, are unaffected.
+//!
+//! # Example
+//! ```
+//! extern crate bincode;
+//! extern crate serde_traitobject as s;
+//! # s::policy::set_policy(s::policy::Policy::Error);
+//!
+//! s::compat::set_schema_version(Some("orders-v3"));
+//! s::tagged::register::<dyn s::Debug, String>();
+//!
+//! let message: s::Box<dyn s::Debug> = s::Box::new(String::from("from the old build"));
+//! let serialized = bincode::serialize(&message).unwrap();
+//! let deserialized: s::Box<dyn s::Debug> = bincode::deserialize(&serialized).unwrap();
+//! assert_eq!(format!("{:?}", deserialized), "\"from the old build\"");
+//!
+//! let unregistered: s::Box<dyn s::Debug> = s::Box::new(123_u32);
+//! let serialized = bincode::serialize(&unregistered).unwrap();
+//! let result: Result<s::Box<dyn s::Debug>, _> = bincode::deserialize(&serialized);
+//! assert!(result.is_err());
+//! ```
+
+use std::sync::{PoisonError, RwLock};
+
+use super::{cipher::Cipher, constant_time};
+
+/// The schema version, and its hash.
+static SCHEMA_VERSION: RwLock<Option<(String, u128)>> = RwLock::new(None);
+
+/// Set the schema version for all threads, or unset it if `None`, such that trait objects are exchanged with builds that set the same schema version.
+pub fn set_schema_version(schema_version: Option<&str>) {
+	*SCHEMA_VERSION
+		.write()
+		.unwrap_or_else(PoisonError::into_inner) =
+		schema_version.map(|schema_version| (schema_version.to_owned(), hash(schema_version)));
+}
+
+/// The schema version set with [`set_schema_version`], if any.
+pub fn schema_version() -> Option<String> {
+	SCHEMA_VERSION
+		.read()
+		.unwrap_or_else(PoisonError::into_inner)
+		.as_ref()
+		.map(|(schema_version, _)| schema_version.clone())
+}
+
+/// A 128-bit hash of `schema_version`, distinguished from build ids only by improbability.
+fn hash(schema_version: &str) -> u128 {
+	let key = [
+		b"serde_traitobject schema version ",
+		schema_version.as_bytes(),
+	]
+	.concat();
+	let [high, low] = Cipher::new(&key).encrypt([0, 0]);
+	(u128::from(high) << 64) | u128::from(low)
+}
+
+/// What to write in place of the build id: the hash of the schema version if one is set, otherwise the build id.
+pub(crate) fn origin() -> u128 {
+	SCHEMA_VERSION
+		.read()
+		.unwrap_or_else(PoisonError::into_inner)
+		.as_ref()
+		.map_or_else(|| build_id::get().as_u128(), |&(_, hash)| hash)
+}
+
+/// Whether `build`, read in place of the build id, is the hash of the schema version set.
+pub(crate) fn matches(build: u128) -> bool {
+	SCHEMA_VERSION
+		.read()
+		.unwrap_or_else(PoisonError::into_inner)
+		.as_ref()
+		.is_some_and(|&(_, hash)| constant_time::eq_u128(hash, build))
+}
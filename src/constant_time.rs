@@ -0,0 +1,24 @@
+//! Equality of the validation fields of trait objects, which with the `security` feature enabled takes time independent of how much of them matches, such that the time taken to reject a forged header doesn't reveal how much of it was correct.
+
+/// Whether `a` and `b` are equal, in constant time for a given length with the `security` feature enabled.
+pub(crate) fn eq(a: &[u8], b: &[u8]) -> bool {
+	#[cfg(feature = "security")]
+	{
+		let diff = a.iter().zip(b).fold(0, |diff, (a, b)| diff | (a ^ b));
+		a.len() == b.len() && std::hint::black_box(diff) == 0
+	}
+	#[cfg(not(feature = "security"))]
+	{
+		a == b
+	}
+}
+
+/// Whether the `u64`s `a` and `b` are equal, as [`eq`].
+pub(crate) fn eq_u64(a: u64, b: u64) -> bool {
+	eq(&a.to_le_bytes(), &b.to_le_bytes())
+}
+
+/// Whether the `u128`s `a` and `b` are equal, as [`eq`].
+pub(crate) fn eq_u128(a: u128, b: u128) -> bool {
+	eq(&a.to_le_bytes(), &b.to_le_bytes())
+}
@@ -1,116 +1,452 @@
 use std::{
-	any, borrow::{Borrow, BorrowMut}, boxed, error, fmt, marker, marker::Tuple, ops::{self, Deref, DerefMut}, rc, sync
+	alloc::{self as std_alloc, Allocator, Global}, any, borrow::{Borrow, BorrowMut}, boxed, clone, cmp, error, fmt, future, hash, io, iter, marker, marker::Tuple, ops::{self, Deref, DerefMut}, pin::Pin, rc, slice, sync, vec
 };
 
-use super::{deserialize, serialize, Deserialize, Serialize};
+use super::{deserialize, deserialize_in, serialize, serialize_ref, Deserialize, Serialize};
 
 /// Convenience wrapper around [std::boxed::Box<T>](std::boxed::Box) that automatically uses `serde_traitobject` for (de)serialization.
-#[derive(Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub struct Box<T: ?Sized>(boxed::Box<T>);
+///
+/// Like `std::boxed::Box`, it's generic over an [`Allocator`](std::alloc::Allocator), which is used for deserialization with [`Box::deserialize_in`].
+pub struct Box<T: ?Sized, A: Allocator = Global>(boxed::Box<T, A>);
 impl<T> Box<T> {
 	/// Create a new Box wrapper
 	pub fn new(t: T) -> Self {
 		Self(boxed::Box::new(t))
 	}
 }
-impl<T: ?Sized> Box<T> {
+impl<T, A: Allocator> Box<T, A> {
+	/// Create a new Box wrapper in the provided allocator
+	pub fn new_in(t: T, alloc: A) -> Self {
+		Self(boxed::Box::new_in(t, alloc))
+	}
+}
+impl<T: ?Sized, A: Allocator> Box<T, A> {
 	/// Convert to a regular `std::Boxed::Box<T>`. Coherence rules prevent currently prevent `impl Into<std::boxed::Box<T>> for Box<T>`.
-	pub fn into_box(self) -> boxed::Box<T> {
+	pub fn into_box(self) -> boxed::Box<T, A> {
 		self.0
 	}
+	/// Convert to a pinned `std::boxed::Box<T>`, for example to poll a deserialized [`Future`].
+	pub fn into_pin(self) -> Pin<boxed::Box<T, A>>
+	where
+		A: 'static,
+	{
+		boxed::Box::into_pin(self.0)
+	}
+	/// Convert to a `Box` of a supertrait object, or of any other type `T` unsizes to.
+	///
+	/// This is the same conversion as the implicit unsizing coercion, but usable where coercion doesn't apply, such as in generic code or method chains.
+	///
+	/// # Example
+	/// ```
+	/// extern crate serde_traitobject as s;
+	///
+	/// trait Shape: s::Debug {
+	///     fn area(&self) -> f64;
+	/// }
+	/// impl Shape for f64 {
+	///     fn area(&self) -> f64 {
+	///         self * self
+	///     }
+	/// }
+	///
+	/// let shape: s::Box<dyn Shape> = s::Box::new(3.0);
+	/// let debug: s::Box<dyn s::Debug> = shape.upcast();
+	/// assert_eq!(format!("{:?}", debug), "3.0");
+	/// ```
+	pub fn upcast<U: ?Sized>(self) -> Box<U, A>
+	where
+		T: marker::Unsize<U>,
+	{
+		Box(self.0)
+	}
+}
+impl<T: Deserialize + ?Sized + 'static, A: Allocator> Box<T, A> {
+	/// Deserialize a value, allocating it in the provided allocator rather than the global one.
+	///
+	/// # Example
+	/// ```
+	/// #![feature(allocator_api)]
+	/// extern crate serde_json;
+	/// extern crate serde_traitobject as s;
+	///
+	/// use std::alloc::System;
+	///
+	/// let serialized = serde_json::to_string(&(s::Box::new(123u8) as s::Box<dyn s::Debug>)).unwrap();
+	/// let mut deserializer = serde_json::Deserializer::from_str(&serialized);
+	/// let deserialized: s::Box<dyn s::Debug, System> = s::Box::deserialize_in(&mut deserializer, System).unwrap();
+	///
+	/// println!("{:?}", deserialized);
+	/// # assert_eq!(format!("{:?}", deserialized), "123");
+	/// // 123
+	/// ```
+	pub fn deserialize_in<'de, D>(deserializer: D, alloc: A) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let raw: *mut T = deserialize_in(deserializer, &mut |layout| {
+			alloc
+				.allocate(layout)
+				.unwrap_or_else(|_| std_alloc::handle_alloc_error(layout))
+				.cast()
+		})?;
+		Ok(Self(unsafe { boxed::Box::from_raw_in(raw, alloc) }))
+	}
 }
 impl Box<dyn Any> {
+	/// The name of the concrete type, as given by [`Any::type_name`].
+	///
+	/// # Example
+	/// ```
+	/// extern crate serde_json;
+	/// extern crate serde_traitobject as s;
+	///
+	/// let erased: s::Box<dyn s::Any> = s::Box::new(String::from("hi there"));
+	///
+	/// let serialized = serde_json::to_string(&erased).unwrap();
+	/// let deserialized: s::Box<dyn s::Any> = serde_json::from_str(&serialized).unwrap();
+	///
+	/// assert_eq!(deserialized.type_name(), "alloc::string::String");
+	/// ```
+	pub fn type_name(&self) -> &'static str {
+		(*self.0).type_name()
+	}
+	/// The `TypeId` of the concrete type, as given by [`Any::concrete_type_id`].
+	///
+	/// # Example
+	/// ```
+	/// extern crate serde_json;
+	/// extern crate serde_traitobject as s;
+	///
+	/// use std::any::TypeId;
+	///
+	/// let erased: s::Box<dyn s::Any> = s::Box::new(123u8);
+	///
+	/// let serialized = serde_json::to_string(&erased).unwrap();
+	/// let deserialized: s::Box<dyn s::Any> = serde_json::from_str(&serialized).unwrap();
+	///
+	/// assert_eq!(deserialized.concrete_type_id(), TypeId::of::<u8>());
+	/// ```
+	pub fn concrete_type_id(&self) -> any::TypeId {
+		(*self.0).concrete_type_id()
+	}
+	/// Attempt to downcast the box to a concrete type, returning the original box if it's of a different type.
+	///
+	/// # Example
+	/// ```
+	/// extern crate serde_traitobject as s;
+	///
+	/// let erased: s::Box<dyn s::Any> = s::Box::new(String::from("hi there"));
+	///
+	/// let erased = erased.downcast::<u64>().unwrap_err();
+	/// let downcast: s::Box<String> = erased.downcast().unwrap();
+	/// assert_eq!(**downcast, "hi there");
+	/// ```
+	pub fn downcast<T: Any>(self) -> Result<Box<T>, Self> {
+		if (*self.0).as_any().is::<T>() {
+			Ok(unsafe { self.downcast_unchecked() })
+		} else {
+			Err(self)
+		}
+	}
+	/// Downcast the box to a concrete type without checking its type.
+	///
+	/// For a checked alternative, use [`downcast`](Self::downcast).
+	///
+	/// # Safety
+	///
+	/// The contained value must be of type `T`. Calling this method with the incorrect type is undefined behavior.
+	///
+	/// # Example
+	/// ```
+	/// extern crate serde_traitobject as s;
+	///
+	/// let erased: s::Box<dyn s::Any> = s::Box::new(123usize);
+	///
+	/// let downcast: s::Box<usize> = unsafe { erased.downcast_unchecked() };
+	/// assert_eq!(**downcast, 123);
+	/// ```
+	pub unsafe fn downcast_unchecked<T: Any>(self) -> Box<T> {
+		debug_assert!((*self.0).as_any().is::<T>());
+		Box(unsafe { boxed::Box::from_raw(boxed::Box::into_raw(self.0).cast::<T>()) })
+	}
 	/// Convert into a `std::boxed::Box<dyn std::any::Any>`.
 	pub fn into_any(self) -> boxed::Box<dyn any::Any> {
 		self.0.into_any()
 	}
 }
 impl Box<dyn Any + Send> {
+	/// The name of the concrete type, as given by [`Any::type_name`].
+	pub fn type_name(&self) -> &'static str {
+		(*self.0).type_name()
+	}
+	/// The `TypeId` of the concrete type, as given by [`Any::concrete_type_id`].
+	pub fn concrete_type_id(&self) -> any::TypeId {
+		(*self.0).concrete_type_id()
+	}
+	/// Attempt to downcast the box to a concrete type, returning the original box if it's of a different type.
+	pub fn downcast<T: Any>(self) -> Result<Box<T>, Self> {
+		if (*self.0).as_any().is::<T>() {
+			Ok(unsafe { self.downcast_unchecked() })
+		} else {
+			Err(self)
+		}
+	}
+	/// Downcast the box to a concrete type without checking its type.
+	///
+	/// For a checked alternative, use [`downcast`](Self::downcast).
+	///
+	/// # Safety
+	///
+	/// The contained value must be of type `T`. Calling this method with the incorrect type is undefined behavior.
+	pub unsafe fn downcast_unchecked<T: Any>(self) -> Box<T> {
+		debug_assert!((*self.0).as_any().is::<T>());
+		Box(unsafe { boxed::Box::from_raw(boxed::Box::into_raw(self.0).cast::<T>()) })
+	}
 	/// Convert into a `std::boxed::Box<dyn std::any::Any + Send>`.
+	#[allow(clippy::transmute_ptr_to_ptr)]
 	pub fn into_any_send(self) -> boxed::Box<dyn any::Any + Send> {
+		// Safety: `self` holds a `dyn Any + Send`, so the value is `Send`. Casts can't add auto traits to a trait object, so this transmutes instead.
 		unsafe {
-			boxed::Box::from_raw(boxed::Box::into_raw(<Box<dyn Any>>::into_any(self)) as *mut _)
+			boxed::Box::from_raw(core::mem::transmute::<
+				*mut dyn any::Any,
+				*mut (dyn any::Any + Send),
+			>(boxed::Box::into_raw(<Box<dyn Any>>::into_any(self))))
 		}
 	}
 }
 impl Box<dyn Any + Sync> {
+	/// The name of the concrete type, as given by [`Any::type_name`].
+	pub fn type_name(&self) -> &'static str {
+		(*self.0).type_name()
+	}
+	/// The `TypeId` of the concrete type, as given by [`Any::concrete_type_id`].
+	pub fn concrete_type_id(&self) -> any::TypeId {
+		(*self.0).concrete_type_id()
+	}
+	/// Attempt to downcast the box to a concrete type, returning the original box if it's of a different type.
+	pub fn downcast<T: Any>(self) -> Result<Box<T>, Self> {
+		if (*self.0).as_any().is::<T>() {
+			Ok(unsafe { self.downcast_unchecked() })
+		} else {
+			Err(self)
+		}
+	}
+	/// Downcast the box to a concrete type without checking its type.
+	///
+	/// For a checked alternative, use [`downcast`](Self::downcast).
+	///
+	/// # Safety
+	///
+	/// The contained value must be of type `T`. Calling this method with the incorrect type is undefined behavior.
+	pub unsafe fn downcast_unchecked<T: Any>(self) -> Box<T> {
+		debug_assert!((*self.0).as_any().is::<T>());
+		Box(unsafe { boxed::Box::from_raw(boxed::Box::into_raw(self.0).cast::<T>()) })
+	}
 	/// Convert into a `std::boxed::Box<dyn std::any::Any + Sync>`.
+	#[allow(clippy::transmute_ptr_to_ptr)]
 	pub fn into_any_sync(self) -> boxed::Box<dyn any::Any + Sync> {
+		// Safety: `self` holds a `dyn Any + Sync`, so the value is `Sync`. Casts can't add auto traits to a trait object, so this transmutes instead.
 		unsafe {
-			boxed::Box::from_raw(boxed::Box::into_raw(<Box<dyn Any>>::into_any(self)) as *mut _)
+			boxed::Box::from_raw(core::mem::transmute::<
+				*mut dyn any::Any,
+				*mut (dyn any::Any + Sync),
+			>(boxed::Box::into_raw(<Box<dyn Any>>::into_any(self))))
 		}
 	}
 }
 impl Box<dyn Any + Send + Sync> {
+	/// The name of the concrete type, as given by [`Any::type_name`].
+	pub fn type_name(&self) -> &'static str {
+		(*self.0).type_name()
+	}
+	/// The `TypeId` of the concrete type, as given by [`Any::concrete_type_id`].
+	pub fn concrete_type_id(&self) -> any::TypeId {
+		(*self.0).concrete_type_id()
+	}
+	/// Attempt to downcast the box to a concrete type, returning the original box if it's of a different type.
+	pub fn downcast<T: Any>(self) -> Result<Box<T>, Self> {
+		if (*self.0).as_any().is::<T>() {
+			Ok(unsafe { self.downcast_unchecked() })
+		} else {
+			Err(self)
+		}
+	}
+	/// Downcast the box to a concrete type without checking its type.
+	///
+	/// For a checked alternative, use [`downcast`](Self::downcast).
+	///
+	/// # Safety
+	///
+	/// The contained value must be of type `T`. Calling this method with the incorrect type is undefined behavior.
+	pub unsafe fn downcast_unchecked<T: Any>(self) -> Box<T> {
+		debug_assert!((*self.0).as_any().is::<T>());
+		Box(unsafe { boxed::Box::from_raw(boxed::Box::into_raw(self.0).cast::<T>()) })
+	}
 	/// Convert into a `std::boxed::Box<dyn std::any::Any + Send + Sync>`.
+	#[allow(clippy::transmute_ptr_to_ptr)]
 	pub fn into_any_send_sync(self) -> boxed::Box<dyn any::Any + Send + Sync> {
+		// Safety: `self` holds a `dyn Any + Send + Sync`, so the value is `Send + Sync`. Casts can't add auto traits to a trait object, so this transmutes instead.
 		unsafe {
-			boxed::Box::from_raw(boxed::Box::into_raw(<Box<dyn Any>>::into_any(self)) as *mut _)
+			boxed::Box::from_raw(core::mem::transmute::<
+				*mut dyn any::Any,
+				*mut (dyn any::Any + Send + Sync),
+			>(boxed::Box::into_raw(<Box<dyn Any>>::into_any(self))))
 		}
 	}
 }
+impl dyn Any {
+	/// Returns `true` if the concrete type is `T`.
+	pub fn is<T: Any>(&self) -> bool {
+		self.as_any().is::<T>()
+	}
+	/// Returns a reference to the concrete value if it's of type `T`.
+	///
+	/// # Example
+	/// ```
+	/// extern crate serde_json;
+	/// extern crate serde_traitobject as s;
+	///
+	/// let erased: s::Box<dyn s::Any> = s::Box::new(String::from("hi there"));
+	///
+	/// let serialized = serde_json::to_string(&erased).unwrap();
+	/// let deserialized: s::Box<dyn s::Any> = serde_json::from_str(&serialized).unwrap();
+	///
+	/// assert_eq!(deserialized.downcast_ref::<String>().unwrap(), "hi there");
+	/// assert!(deserialized.downcast_ref::<u64>().is_none());
+	/// ```
+	pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+		self.as_any().downcast_ref()
+	}
+	/// Returns a mutable reference to the concrete value if it's of type `T`.
+	pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+		self.as_any_mut().downcast_mut()
+	}
+}
 impl dyn Any + Send {
+	/// Returns `true` if the concrete type is `T`.
+	pub fn is<T: Any>(&self) -> bool {
+		self.as_any().is::<T>()
+	}
+	/// Returns a reference to the concrete value if it's of type `T`.
+	pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+		self.as_any().downcast_ref()
+	}
+	/// Returns a mutable reference to the concrete value if it's of type `T`.
+	pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+		self.as_any_mut().downcast_mut()
+	}
 	/// Convert into a `std::boxed::Box<dyn std::any::Any + Send>`.
 	pub fn into_any_send(self: boxed::Box<Self>) -> boxed::Box<dyn any::Any + Send> {
 		<Box<dyn Any + Send>>::into_any_send(Box(self))
 	}
 }
 impl dyn Any + Sync {
+	/// Returns `true` if the concrete type is `T`.
+	pub fn is<T: Any>(&self) -> bool {
+		self.as_any().is::<T>()
+	}
+	/// Returns a reference to the concrete value if it's of type `T`.
+	pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+		self.as_any().downcast_ref()
+	}
+	/// Returns a mutable reference to the concrete value if it's of type `T`.
+	pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+		self.as_any_mut().downcast_mut()
+	}
 	/// Convert into a `std::boxed::Box<dyn std::any::Any + Sync>`.
 	pub fn into_any_sync(self: boxed::Box<Self>) -> boxed::Box<dyn any::Any + Sync> {
 		<Box<dyn Any + Sync>>::into_any_sync(Box(self))
 	}
 }
 impl dyn Any + Send + Sync {
+	/// Returns `true` if the concrete type is `T`.
+	pub fn is<T: Any>(&self) -> bool {
+		self.as_any().is::<T>()
+	}
+	/// Returns a reference to the concrete value if it's of type `T`.
+	pub fn downcast_ref<T: Any>(&self) -> Option<&T> {
+		self.as_any().downcast_ref()
+	}
+	/// Returns a mutable reference to the concrete value if it's of type `T`.
+	pub fn downcast_mut<T: Any>(&mut self) -> Option<&mut T> {
+		self.as_any_mut().downcast_mut()
+	}
 	/// Convert into a `std::boxed::Box<dyn std::any::Any + Send + Sync>`.
 	pub fn into_any_send_sync(self: boxed::Box<Self>) -> boxed::Box<dyn any::Any + Send + Sync> {
 		<Box<dyn Any + Send + Sync>>::into_any_send_sync(Box(self))
 	}
 }
-impl<T: ?Sized + marker::Unsize<U>, U: ?Sized> ops::CoerceUnsized<Box<U>> for Box<T> {}
-impl<T: ?Sized> Deref for Box<T> {
-	type Target = boxed::Box<T>;
+impl<'a> Box<dyn Error + 'a> {
+	/// Convert into a `std::boxed::Box<dyn std::error::Error>`.
+	pub fn into_std_error(self) -> boxed::Box<dyn error::Error + 'a> {
+		self.0
+	}
+}
+impl<'a> Box<dyn Error + Send + 'a> {
+	/// Convert into a `std::boxed::Box<dyn std::error::Error + Send>`.
+	pub fn into_std_error(self) -> boxed::Box<dyn error::Error + Send + 'a> {
+		self.0
+	}
+}
+impl<'a> Box<dyn Error + Send + Sync + 'a> {
+	/// Convert into a `std::boxed::Box<dyn std::error::Error + Send + Sync>`.
+	pub fn into_std_error(self) -> boxed::Box<dyn error::Error + Send + Sync + 'a> {
+		self.0
+	}
+}
+// `std::boxed::Box<dyn std::error::Error>` and `std::boxed::Box<dyn std::error::Error + Send + Sync>` are covered by std's `From<E: std::error::Error>` impls.
+impl<'a> From<Box<dyn Error + Send + 'a>> for boxed::Box<dyn error::Error + Send + 'a> {
+	fn from(err: Box<dyn Error + Send + 'a>) -> Self {
+		err.into_std_error()
+	}
+}
+impl<T: ?Sized + marker::Unsize<U>, U: ?Sized, A: Allocator> ops::CoerceUnsized<Box<U, A>>
+	for Box<T, A>
+{
+}
+impl<T: ?Sized, A: Allocator> Deref for Box<T, A> {
+	type Target = boxed::Box<T, A>;
 	fn deref(&self) -> &Self::Target {
 		&self.0
 	}
 }
-impl<T: ?Sized> DerefMut for Box<T> {
+impl<T: ?Sized, A: Allocator> DerefMut for Box<T, A> {
 	fn deref_mut(&mut self) -> &mut Self::Target {
 		&mut self.0
 	}
 }
-impl<T: ?Sized> AsRef<boxed::Box<T>> for Box<T> {
-	fn as_ref(&self) -> &boxed::Box<T> {
+impl<T: ?Sized, A: Allocator> AsRef<boxed::Box<T, A>> for Box<T, A> {
+	fn as_ref(&self) -> &boxed::Box<T, A> {
 		&self.0
 	}
 }
-impl<T: ?Sized> AsMut<boxed::Box<T>> for Box<T> {
-	fn as_mut(&mut self) -> &mut boxed::Box<T> {
+impl<T: ?Sized, A: Allocator> AsMut<boxed::Box<T, A>> for Box<T, A> {
+	fn as_mut(&mut self) -> &mut boxed::Box<T, A> {
 		&mut self.0
 	}
 }
-impl<T: ?Sized> AsRef<T> for Box<T> {
+impl<T: ?Sized, A: Allocator> AsRef<T> for Box<T, A> {
 	fn as_ref(&self) -> &T {
 		&self.0
 	}
 }
-impl<T: ?Sized> AsMut<T> for Box<T> {
+impl<T: ?Sized, A: Allocator> AsMut<T> for Box<T, A> {
 	fn as_mut(&mut self) -> &mut T {
 		&mut self.0
 	}
 }
-impl<T: ?Sized> Borrow<T> for Box<T> {
+impl<T: ?Sized, A: Allocator> Borrow<T> for Box<T, A> {
 	fn borrow(&self) -> &T {
 		&self.0
 	}
 }
-impl<T: ?Sized> BorrowMut<T> for Box<T> {
+impl<T: ?Sized, A: Allocator> BorrowMut<T> for Box<T, A> {
 	fn borrow_mut(&mut self) -> &mut T {
 		&mut self.0
 	}
 }
-impl<T: ?Sized> From<boxed::Box<T>> for Box<T> {
-	fn from(t: boxed::Box<T>) -> Self {
+impl<T: ?Sized, A: Allocator> From<boxed::Box<T, A>> for Box<T, A> {
+	fn from(t: boxed::Box<T, A>) -> Self {
 		Self(t)
 	}
 }
@@ -124,55 +460,201 @@ impl<T> From<T> for Box<T> {
 		Self(boxed::Box::new(t))
 	}
 }
-impl<T: error::Error> error::Error for Box<T> {
+impl<T: clone::Clone, A: Allocator + clone::Clone> clone::Clone for Box<T, A> {
+	fn clone(&self) -> Self {
+		Self(self.0.clone())
+	}
+}
+impl<T: Default> Default for Box<T> {
+	fn default() -> Self {
+		Self(boxed::Box::default())
+	}
+}
+impl<T: ?Sized + cmp::PartialEq, A: Allocator> cmp::PartialEq for Box<T, A> {
+	fn eq(&self, other: &Self) -> bool {
+		self.0 == other.0
+	}
+}
+impl<T: ?Sized + cmp::Eq, A: Allocator> cmp::Eq for Box<T, A> {}
+impl<T: ?Sized + hash::Hash, A: Allocator> hash::Hash for Box<T, A> {
+	fn hash<H: hash::Hasher>(&self, state: &mut H) {
+		self.0.hash(state);
+	}
+}
+impl<T: ?Sized + cmp::PartialOrd, A: Allocator> cmp::PartialOrd for Box<T, A> {
+	fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+		self.0.partial_cmp(&other.0)
+	}
+}
+impl<T: ?Sized + cmp::Ord, A: Allocator> cmp::Ord for Box<T, A> {
+	fn cmp(&self, other: &Self) -> cmp::Ordering {
+		self.0.cmp(&other.0)
+	}
+}
+impl<T: error::Error + ?Sized> error::Error for Box<T> {
 	#[allow(deprecated)]
 	fn description(&self) -> &str {
-		error::Error::description(&**self)
+		error::Error::description(&*self.0)
 	}
 	#[allow(deprecated)]
 	fn cause(&self) -> Option<&dyn error::Error> {
-		error::Error::cause(&**self)
+		error::Error::cause(&*self.0)
 	}
 	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
-		error::Error::source(&**self)
+		error::Error::source(&*self.0)
 	}
 }
-impl<T: fmt::Debug + ?Sized> fmt::Debug for Box<T> {
+impl<T: fmt::Debug + ?Sized, A: Allocator> fmt::Debug for Box<T, A> {
 	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
 		self.0.fmt(f)
 	}
 }
-impl<T: fmt::Display + ?Sized> fmt::Display for Box<T> {
+impl<T: fmt::Display + ?Sized, A: Allocator> fmt::Display for Box<T, A> {
 	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
 		self.0.fmt(f)
 	}
 }
-impl<A: Tuple, F: ?Sized> ops::FnOnce<A> for Box<F>
+impl<Args: Tuple, F: ?Sized, A: Allocator> ops::FnOnce<Args> for Box<F, A>
 where
-	F: FnOnce<A>,
+	F: FnOnce<Args>,
 {
 	type Output = F::Output;
-	extern "rust-call" fn call_once(self, args: A) -> Self::Output {
+	extern "rust-call" fn call_once(self, args: Args) -> Self::Output {
 		self.0.call_once(args)
 	}
 }
-impl<A: Tuple, F: ?Sized> ops::FnMut<A> for Box<F>
+impl<Args: Tuple, F: ?Sized, A: Allocator> ops::FnMut<Args> for Box<F, A>
 where
-	F: FnMut<A>,
+	F: FnMut<Args>,
 {
-	extern "rust-call" fn call_mut(&mut self, args: A) -> Self::Output {
+	extern "rust-call" fn call_mut(&mut self, args: Args) -> Self::Output {
 		self.0.call_mut(args)
 	}
 }
-impl<A: Tuple, F: ?Sized> ops::Fn<A> for Box<F>
+impl<Args: Tuple, F: ?Sized, A: Allocator> ops::Fn<Args> for Box<F, A>
 where
-	F: Fn<A>,
+	F: Fn<Args>,
 {
-	extern "rust-call" fn call(&self, args: A) -> Self::Output {
+	extern "rust-call" fn call(&self, args: Args) -> Self::Output {
 		self.0.call(args)
 	}
 }
-impl<T: Serialize + ?Sized + 'static> serde::ser::Serialize for Box<T> {
+impl<I: iter::Iterator + ?Sized, A: Allocator> iter::Iterator for Box<I, A> {
+	type Item = I::Item;
+	fn next(&mut self) -> Option<Self::Item> {
+		self.0.next()
+	}
+	fn size_hint(&self) -> (usize, Option<usize>) {
+		self.0.size_hint()
+	}
+	fn nth(&mut self, n: usize) -> Option<Self::Item> {
+		self.0.nth(n)
+	}
+}
+impl<I: iter::DoubleEndedIterator + ?Sized, A: Allocator> iter::DoubleEndedIterator for Box<I, A> {
+	fn next_back(&mut self) -> Option<Self::Item> {
+		self.0.next_back()
+	}
+	fn nth_back(&mut self, n: usize) -> Option<Self::Item> {
+		self.0.nth_back(n)
+	}
+}
+impl<I: iter::ExactSizeIterator + ?Sized, A: Allocator> iter::ExactSizeIterator for Box<I, A> {
+	fn len(&self) -> usize {
+		self.0.len()
+	}
+}
+impl<I: iter::FusedIterator + ?Sized, A: Allocator> iter::FusedIterator for Box<I, A> {}
+impl<F: future::Future + ?Sized, A: Allocator + 'static> future::IntoFuture for Box<F, A> {
+	type Output = F::Output;
+	type IntoFuture = Pin<boxed::Box<F, A>>;
+	fn into_future(self) -> Self::IntoFuture {
+		self.into_pin()
+	}
+}
+impl<R: io::Read + ?Sized, A: Allocator> io::Read for Box<R, A> {
+	fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+		self.0.read(buf)
+	}
+	fn read_vectored(&mut self, bufs: &mut [io::IoSliceMut<'_>]) -> io::Result<usize> {
+		self.0.read_vectored(bufs)
+	}
+	fn read_to_end(&mut self, buf: &mut vec::Vec<u8>) -> io::Result<usize> {
+		self.0.read_to_end(buf)
+	}
+	fn read_to_string(&mut self, buf: &mut String) -> io::Result<usize> {
+		self.0.read_to_string(buf)
+	}
+	fn read_exact(&mut self, buf: &mut [u8]) -> io::Result<()> {
+		self.0.read_exact(buf)
+	}
+}
+impl<W: io::Write + ?Sized, A: Allocator> io::Write for Box<W, A> {
+	fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+		self.0.write(buf)
+	}
+	fn write_vectored(&mut self, bufs: &[io::IoSlice<'_>]) -> io::Result<usize> {
+		self.0.write_vectored(bufs)
+	}
+	fn flush(&mut self) -> io::Result<()> {
+		self.0.flush()
+	}
+	fn write_all(&mut self, buf: &[u8]) -> io::Result<()> {
+		self.0.write_all(buf)
+	}
+	fn write_fmt(&mut self, fmt: fmt::Arguments<'_>) -> io::Result<()> {
+		self.0.write_fmt(fmt)
+	}
+}
+impl<B: io::BufRead + ?Sized, A: Allocator> io::BufRead for Box<B, A> {
+	fn fill_buf(&mut self) -> io::Result<&[u8]> {
+		self.0.fill_buf()
+	}
+	fn consume(&mut self, amt: usize) {
+		self.0.consume(amt);
+	}
+	fn read_until(&mut self, byte: u8, buf: &mut vec::Vec<u8>) -> io::Result<usize> {
+		self.0.read_until(byte, buf)
+	}
+	fn read_line(&mut self, buf: &mut String) -> io::Result<usize> {
+		self.0.read_line(buf)
+	}
+}
+impl<S: io::Seek + ?Sized, A: Allocator> io::Seek for Box<S, A> {
+	fn seek(&mut self, pos: io::SeekFrom) -> io::Result<u64> {
+		self.0.seek(pos)
+	}
+	fn stream_position(&mut self) -> io::Result<u64> {
+		self.0.stream_position()
+	}
+}
+impl<H: hash::Hasher + ?Sized, A: Allocator> hash::Hasher for Box<H, A> {
+	fn finish(&self) -> u64 {
+		self.0.finish()
+	}
+	fn write(&mut self, bytes: &[u8]) {
+		self.0.write(bytes);
+	}
+	fn write_u8(&mut self, i: u8) {
+		self.0.write_u8(i);
+	}
+	fn write_u16(&mut self, i: u16) {
+		self.0.write_u16(i);
+	}
+	fn write_u32(&mut self, i: u32) {
+		self.0.write_u32(i);
+	}
+	fn write_u64(&mut self, i: u64) {
+		self.0.write_u64(i);
+	}
+	fn write_u128(&mut self, i: u128) {
+		self.0.write_u128(i);
+	}
+	fn write_usize(&mut self, i: usize) {
+		self.0.write_usize(i);
+	}
+}
+impl<T: Serialize + ?Sized + 'static, A: Allocator> serde::ser::Serialize for Box<T, A> {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
@@ -180,12 +662,14 @@ impl<T: Serialize + ?Sized + 'static> serde::ser::Serialize for Box<T> {
 		serialize(&self.0, serializer)
 	}
 }
-impl<'de, T: Deserialize + ?Sized + 'static> serde::de::Deserialize<'de> for Box<T> {
+impl<'de, T: Deserialize + ?Sized + 'static, A: Allocator + Default> serde::de::Deserialize<'de>
+	for Box<T, A>
+{
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where
 		D: serde::Deserializer<'de>,
 	{
-		deserialize(deserializer).map(Self)
+		Self::deserialize_in(deserializer, A::default())
 	}
 }
 
@@ -198,6 +682,95 @@ impl<T> Rc<T> {
 		Self(rc::Rc::new(t))
 	}
 }
+impl<T: ?Sized> Rc<T> {
+	/// Convert to an `Rc` of a supertrait object, or of any other type `T` unsizes to. See [`Box::upcast`].
+	pub fn upcast<U: ?Sized>(self) -> Rc<U>
+	where
+		T: marker::Unsize<U>,
+	{
+		Rc(self.0)
+	}
+}
+impl Rc<dyn Any> {
+	/// The name of the concrete type, as given by [`Any::type_name`].
+	pub fn type_name(&self) -> &'static str {
+		(*self.0).type_name()
+	}
+	/// The `TypeId` of the concrete type, as given by [`Any::concrete_type_id`].
+	pub fn concrete_type_id(&self) -> any::TypeId {
+		(*self.0).concrete_type_id()
+	}
+	/// Attempt to downcast the `Rc` to a concrete type, returning the original `Rc` if it's of a different type.
+	///
+	/// # Example
+	/// ```
+	/// extern crate serde_json;
+	/// extern crate serde_traitobject as s;
+	///
+	/// let erased: s::Rc<dyn s::Any> = s::Rc::new(String::from("hi there"));
+	///
+	/// let serialized = serde_json::to_string(&erased).unwrap();
+	/// let deserialized: s::Rc<dyn s::Any> = serde_json::from_str(&serialized).unwrap();
+	///
+	/// let downcast: s::Rc<String> = deserialized.downcast().unwrap();
+	/// assert_eq!(**downcast, "hi there");
+	/// ```
+	pub fn downcast<T: Any>(self) -> Result<Rc<T>, Self> {
+		if (*self.0).is::<T>() {
+			Ok(Rc(unsafe {
+				rc::Rc::from_raw(rc::Rc::into_raw(self.0).cast::<T>())
+			}))
+		} else {
+			Err(self)
+		}
+	}
+	/// Convert into a `std::rc::Rc<dyn std::any::Any>`.
+	pub fn into_any(self) -> rc::Rc<dyn any::Any> {
+		self.0
+	}
+}
+impl Rc<dyn Any + Send> {
+	/// The name of the concrete type, as given by [`Any::type_name`].
+	pub fn type_name(&self) -> &'static str {
+		(*self.0).type_name()
+	}
+	/// The `TypeId` of the concrete type, as given by [`Any::concrete_type_id`].
+	pub fn concrete_type_id(&self) -> any::TypeId {
+		(*self.0).concrete_type_id()
+	}
+	/// Convert into a `std::rc::Rc<dyn std::any::Any + Send>`.
+	pub fn into_any_send(self) -> rc::Rc<dyn any::Any + Send> {
+		self.0
+	}
+}
+impl Rc<dyn Any + Sync> {
+	/// The name of the concrete type, as given by [`Any::type_name`].
+	pub fn type_name(&self) -> &'static str {
+		(*self.0).type_name()
+	}
+	/// The `TypeId` of the concrete type, as given by [`Any::concrete_type_id`].
+	pub fn concrete_type_id(&self) -> any::TypeId {
+		(*self.0).concrete_type_id()
+	}
+	/// Convert into a `std::rc::Rc<dyn std::any::Any + Sync>`.
+	pub fn into_any_sync(self) -> rc::Rc<dyn any::Any + Sync> {
+		self.0
+	}
+}
+impl Rc<dyn Any + Send + Sync> {
+	/// The name of the concrete type, as given by [`Any::type_name`].
+	pub fn type_name(&self) -> &'static str {
+		(*self.0).type_name()
+	}
+	/// The `TypeId` of the concrete type, as given by [`Any::concrete_type_id`].
+	pub fn concrete_type_id(&self) -> any::TypeId {
+		(*self.0).concrete_type_id()
+	}
+	/// Convert into a `std::rc::Rc<dyn std::any::Any + Send + Sync>`.
+	pub fn into_any_send_sync(self) -> rc::Rc<dyn any::Any + Send + Sync> {
+		self.0
+	}
+}
 impl<T: ?Sized + marker::Unsize<U>, U: ?Sized> ops::CoerceUnsized<Rc<U>> for Rc<T> {}
 impl<T: ?Sized> Deref for Rc<T> {
 	type Target = rc::Rc<T>;
@@ -245,7 +818,7 @@ impl<T> From<T> for Rc<T> {
 		Self(rc::Rc::new(t))
 	}
 }
-impl<T: ?Sized> Clone for Rc<T> {
+impl<T: ?Sized> clone::Clone for Rc<T> {
 	fn clone(&self) -> Self {
 		Self(self.0.clone())
 	}
@@ -286,6 +859,81 @@ impl<T> Arc<T> {
 		Self(sync::Arc::new(t))
 	}
 }
+impl<T: ?Sized> Arc<T> {
+	/// Convert to an `Arc` of a supertrait object, or of any other type `T` unsizes to. See [`Box::upcast`].
+	pub fn upcast<U: ?Sized>(self) -> Arc<U>
+	where
+		T: marker::Unsize<U>,
+	{
+		Arc(self.0)
+	}
+}
+impl Arc<dyn Any> {
+	/// The name of the concrete type, as given by [`Any::type_name`].
+	pub fn type_name(&self) -> &'static str {
+		(*self.0).type_name()
+	}
+	/// The `TypeId` of the concrete type, as given by [`Any::concrete_type_id`].
+	pub fn concrete_type_id(&self) -> any::TypeId {
+		(*self.0).concrete_type_id()
+	}
+	/// Convert into a `std::sync::Arc<dyn std::any::Any>`.
+	pub fn into_any(self) -> sync::Arc<dyn any::Any> {
+		self.0
+	}
+}
+impl Arc<dyn Any + Send> {
+	/// The name of the concrete type, as given by [`Any::type_name`].
+	pub fn type_name(&self) -> &'static str {
+		(*self.0).type_name()
+	}
+	/// The `TypeId` of the concrete type, as given by [`Any::concrete_type_id`].
+	pub fn concrete_type_id(&self) -> any::TypeId {
+		(*self.0).concrete_type_id()
+	}
+	/// Convert into a `std::sync::Arc<dyn std::any::Any + Send>`.
+	pub fn into_any_send(self) -> sync::Arc<dyn any::Any + Send> {
+		self.0
+	}
+}
+impl Arc<dyn Any + Sync> {
+	/// The name of the concrete type, as given by [`Any::type_name`].
+	pub fn type_name(&self) -> &'static str {
+		(*self.0).type_name()
+	}
+	/// The `TypeId` of the concrete type, as given by [`Any::concrete_type_id`].
+	pub fn concrete_type_id(&self) -> any::TypeId {
+		(*self.0).concrete_type_id()
+	}
+	/// Convert into a `std::sync::Arc<dyn std::any::Any + Sync>`.
+	pub fn into_any_sync(self) -> sync::Arc<dyn any::Any + Sync> {
+		self.0
+	}
+}
+impl Arc<dyn Any + Send + Sync> {
+	/// The name of the concrete type, as given by [`Any::type_name`].
+	pub fn type_name(&self) -> &'static str {
+		(*self.0).type_name()
+	}
+	/// The `TypeId` of the concrete type, as given by [`Any::concrete_type_id`].
+	pub fn concrete_type_id(&self) -> any::TypeId {
+		(*self.0).concrete_type_id()
+	}
+	/// Attempt to downcast the `Arc` to a concrete type, returning the original `Arc` if it's of a different type.
+	pub fn downcast<T: Any + Send + Sync>(self) -> Result<Arc<T>, Self> {
+		if (*self.0).is::<T>() {
+			Ok(Arc(unsafe {
+				sync::Arc::from_raw(sync::Arc::into_raw(self.0).cast::<T>())
+			}))
+		} else {
+			Err(self)
+		}
+	}
+	/// Convert into a `std::sync::Arc<dyn std::any::Any + Send + Sync>`.
+	pub fn into_any_send_sync(self) -> sync::Arc<dyn any::Any + Send + Sync> {
+		self.0
+	}
+}
 impl<T: ?Sized + marker::Unsize<U>, U: ?Sized> ops::CoerceUnsized<Arc<U>> for Arc<T> {}
 impl<T: ?Sized> Deref for Arc<T> {
 	type Target = sync::Arc<T>;
@@ -333,7 +981,7 @@ impl<T> From<T> for Arc<T> {
 		Self(sync::Arc::new(t))
 	}
 }
-impl<T: ?Sized> Clone for Arc<T> {
+impl<T: ?Sized> clone::Clone for Arc<T> {
 	fn clone(&self) -> Self {
 		Self(self.0.clone())
 	}
@@ -365,362 +1013,554 @@ impl<'de, T: Deserialize + ?Sized + 'static> serde::de::Deserialize<'de> for Arc
 	}
 }
 
-/// A convenience trait implemented on all (de)serializable implementors of [`std::any::Any`].
-///
-/// It can be made into a trait object which is then (de)serializable.
+/// Convenience wrapper around [`std::vec::Vec<std::boxed::Box<T>>`](std::vec::Vec) that automatically uses `serde_traitobject` for (de)serialization of each element.
 ///
 /// # Example
 /// ```
 /// extern crate serde_json;
 /// extern crate serde_traitobject as s;
 ///
-/// use std::any::Any;
-///
-/// let erased: s::Box<dyn s::Any> = s::Box::new(String::from("hi there"));
+/// let mut erased: s::Vec<dyn s::Debug> = s::Vec::new();
+/// erased.push(Box::new(123u8));
+/// erased.push(Box::new(String::from("abc")));
 ///
 /// let serialized = serde_json::to_string(&erased).unwrap();
-/// let deserialized: s::Box<dyn s::Any> = serde_json::from_str(&serialized).unwrap();
+/// let deserialized: s::Vec<dyn s::Debug> = serde_json::from_str(&serialized).unwrap();
 ///
-/// let downcast: Box<String> = Box::<dyn Any>::downcast(deserialized.into_any()).unwrap();
-///
-/// println!("{}!", downcast);
-/// # assert_eq!(format!("{}!", downcast), "hi there!");
-/// // hi there!
+/// println!("{:?}", deserialized);
+/// # assert_eq!(format!("{:?}", deserialized), "[123, \"abc\"]");
+/// // [123, "abc"]
 /// ```
-pub trait Any: any::Any + Serialize + Deserialize {
-	/// Convert to a `&std::any::Any`.
-	fn as_any(&self) -> &dyn any::Any;
-	/// Convert to a `&mut std::any::Any`.
-	fn as_any_mut(&mut self) -> &mut dyn any::Any;
-	/// Convert to a `std::boxed::Box<dyn std::any::Any>`.
-	fn into_any(self: boxed::Box<Self>) -> boxed::Box<dyn any::Any>;
-}
-impl<T> Any for T
-where
-	T: any::Any + Serialize + Deserialize,
-{
-	fn as_any(&self) -> &dyn any::Any {
-		self
+#[derive(Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Vec<T: ?Sized>(vec::Vec<boxed::Box<T>>);
+impl<T: ?Sized> Vec<T> {
+	/// Create a new, empty Vec wrapper
+	pub fn new() -> Self {
+		Self(vec::Vec::new())
 	}
-	fn as_any_mut(&mut self) -> &mut dyn any::Any {
-		self
+	/// Create a new, empty Vec wrapper with the specified capacity
+	pub fn with_capacity(capacity: usize) -> Self {
+		Self(vec::Vec::with_capacity(capacity))
 	}
-	fn into_any(self: boxed::Box<Self>) -> boxed::Box<dyn any::Any> {
-		self
+	/// Append an element to the back of the Vec.
+	pub fn push(&mut self, t: boxed::Box<T>) {
+		self.0.push(t);
+	}
+	/// Remove the last element from the Vec and return it, or `None` if it is empty.
+	pub fn pop(&mut self) -> Option<boxed::Box<T>> {
+		self.0.pop()
+	}
+	/// Convert to a regular `std::vec::Vec<std::boxed::Box<T>>`.
+	pub fn into_vec(self) -> vec::Vec<boxed::Box<T>> {
+		self.0
 	}
 }
-
-impl AsRef<Self> for dyn Any {
-	fn as_ref(&self) -> &Self {
-		self
+impl<T: ?Sized> Default for Vec<T> {
+	fn default() -> Self {
+		Self::new()
 	}
 }
-impl AsRef<Self> for dyn Any + Send {
-	fn as_ref(&self) -> &Self {
-		self
+impl<T: ?Sized> Deref for Vec<T> {
+	type Target = vec::Vec<boxed::Box<T>>;
+	fn deref(&self) -> &Self::Target {
+		&self.0
 	}
 }
-
-impl serde::ser::Serialize for dyn Any {
-	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer,
-	{
-		serialize(self, serializer)
+impl<T: ?Sized> DerefMut for Vec<T> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.0
 	}
 }
-impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Any + 'static> {
-	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-	where
-		D: serde::Deserializer<'de>,
-	{
-		<Box<dyn Any + 'static>>::deserialize(deserializer).map(|x| x.0)
+impl<T: ?Sized> AsRef<vec::Vec<boxed::Box<T>>> for Vec<T> {
+	fn as_ref(&self) -> &vec::Vec<boxed::Box<T>> {
+		&self.0
 	}
 }
-impl serde::ser::Serialize for dyn Any + Send {
+impl<T: ?Sized> AsMut<vec::Vec<boxed::Box<T>>> for Vec<T> {
+	fn as_mut(&mut self) -> &mut vec::Vec<boxed::Box<T>> {
+		&mut self.0
+	}
+}
+impl<T: ?Sized> From<vec::Vec<boxed::Box<T>>> for Vec<T> {
+	fn from(t: vec::Vec<boxed::Box<T>>) -> Self {
+		Self(t)
+	}
+}
+impl<T: ?Sized> From<Vec<T>> for vec::Vec<boxed::Box<T>> {
+	fn from(v: Vec<T>) -> Self {
+		v.0
+	}
+}
+impl<T: ?Sized> iter::FromIterator<boxed::Box<T>> for Vec<T> {
+	fn from_iter<I: IntoIterator<Item = boxed::Box<T>>>(iter: I) -> Self {
+		Self(iter.into_iter().collect())
+	}
+}
+impl<T: ?Sized> Extend<boxed::Box<T>> for Vec<T> {
+	fn extend<I: IntoIterator<Item = boxed::Box<T>>>(&mut self, iter: I) {
+		self.0.extend(iter);
+	}
+}
+impl<T: ?Sized> IntoIterator for Vec<T> {
+	type Item = boxed::Box<T>;
+	type IntoIter = vec::IntoIter<boxed::Box<T>>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.into_iter()
+	}
+}
+impl<'a, T: ?Sized> IntoIterator for &'a Vec<T> {
+	type Item = &'a boxed::Box<T>;
+	type IntoIter = slice::Iter<'a, boxed::Box<T>>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.iter()
+	}
+}
+impl<'a, T: ?Sized> IntoIterator for &'a mut Vec<T> {
+	type Item = &'a mut boxed::Box<T>;
+	type IntoIter = slice::IterMut<'a, boxed::Box<T>>;
+	fn into_iter(self) -> Self::IntoIter {
+		self.0.iter_mut()
+	}
+}
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Vec<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		self.0.fmt(f)
+	}
+}
+impl<T: Serialize + ?Sized + 'static> serde::ser::Serialize for Vec<T> {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
 	{
-		serialize(self, serializer)
+		serializer.collect_seq(self.0.iter().map(SerializeBox))
 	}
 }
-impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Any + Send + 'static> {
+impl<'de, T: Deserialize + ?Sized + 'static> serde::de::Deserialize<'de> for Vec<T> {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where
 		D: serde::Deserializer<'de>,
 	{
-		<Box<dyn Any + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+		<vec::Vec<Box<T>>>::deserialize(deserializer)
+			.map(|vec| Self(vec.into_iter().map(Box::into_box).collect()))
 	}
 }
 
-/// A convenience trait implemented on all (de)serializable implementors of [`std::error::Error`].
+/// Convenience wrapper around [`std::sync::OnceLock<std::boxed::Box<T>>`](std::sync::OnceLock) that automatically uses `serde_traitobject` for (de)serialization.
 ///
-/// It can be made into a trait object which is then (de)serializable.
+/// It serializes as an `Option`: the inner value if the cell has been initialized, otherwise `None`. Deserializing reconstructs an initialized or uninitialized cell accordingly.
 ///
 /// # Example
 /// ```
-/// # extern crate serde;
-/// #[macro_use] extern crate serde_derive;
 /// extern crate serde_json;
 /// extern crate serde_traitobject as s;
 ///
-/// use std::fmt;
+/// let cell: s::OnceLock<dyn s::Debug> = s::OnceLock::new();
 ///
-/// #[derive(Serialize,Deserialize,Debug)]
-/// struct MyError(String);
-/// impl fmt::Display for MyError {
-///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-///         write!(f, "{}", self.0)
-///     }
-/// }
-/// impl std::error::Error for MyError {}
+/// let serialized = serde_json::to_string(&cell).unwrap();
+/// let deserialized: s::OnceLock<dyn s::Debug> = serde_json::from_str(&serialized).unwrap();
+/// assert!(deserialized.get().is_none());
 ///
-/// fn fallible() -> Result<(),s::Box<dyn s::Error>> {
-///     Err(Box::new(MyError(String::from("boxed error"))) as Box<dyn s::Error>)?
-/// }
+/// cell.get_or_init(|| Box::new(String::from("initialized")));
 ///
-/// let serialized = serde_json::to_string(&fallible()).unwrap();
-/// let deserialized: Result<(),s::Box<dyn s::Error>> = serde_json::from_str(&serialized).unwrap();
+/// let serialized = serde_json::to_string(&cell).unwrap();
+/// let deserialized: s::OnceLock<dyn s::Debug> = serde_json::from_str(&serialized).unwrap();
 ///
 /// println!("{:?}", deserialized);
-/// # assert_eq!(format!("{:?}", deserialized), "Err(MyError(\"boxed error\"))");
-/// // Err(MyError("boxed error"))
+/// # assert_eq!(format!("{:?}", deserialized), "OnceLock(\"initialized\")");
+/// // OnceLock("initialized")
 /// ```
-pub trait Error: error::Error + Serialize + Deserialize {}
-impl<T: ?Sized> Error for T where T: error::Error + Serialize + Deserialize {}
-
-impl<'a> AsRef<Self> for dyn Error + 'a {
-	fn as_ref(&self) -> &Self {
-		self
+pub struct OnceLock<T: ?Sized>(sync::OnceLock<boxed::Box<T>>);
+impl<T: ?Sized> OnceLock<T> {
+	/// Create a new, uninitialized `OnceLock` wrapper
+	pub const fn new() -> Self {
+		Self(sync::OnceLock::new())
+	}
+	/// Get a reference to the inner value, or `None` if the cell is uninitialized.
+	pub fn get(&self) -> Option<&T> {
+		self.0.get().map(|t| &**t)
+	}
+	/// Get a mutable reference to the inner value, or `None` if the cell is uninitialized.
+	pub fn get_mut(&mut self) -> Option<&mut T> {
+		self.0.get_mut().map(|t| &mut **t)
+	}
+	/// Initialize the cell with `t`, returning it back in `Err` if the cell was already initialized.
+	pub fn set(&self, t: boxed::Box<T>) -> Result<(), boxed::Box<T>> {
+		self.0.set(t)
+	}
+	/// Get a reference to the inner value, initializing it with `f` if the cell is uninitialized.
+	pub fn get_or_init<F>(&self, f: F) -> &T
+	where
+		F: ops::FnOnce() -> boxed::Box<T>,
+	{
+		self.0.get_or_init(f)
+	}
+	/// Take the inner value out, leaving the cell uninitialized.
+	pub fn take(&mut self) -> Option<boxed::Box<T>> {
+		self.0.take()
+	}
+	/// Convert into the inner value, or `None` if the cell is uninitialized.
+	pub fn into_inner(self) -> Option<boxed::Box<T>> {
+		self.0.into_inner()
 	}
 }
-impl<'a> AsRef<Self> for dyn Error + Send + 'a {
-	fn as_ref(&self) -> &Self {
-		self
+impl<T: ?Sized> Default for OnceLock<T> {
+	fn default() -> Self {
+		Self::new()
 	}
 }
-
-impl<'a, E: error::Error + Serialize + Deserialize + 'a> From<E> for Box<dyn Error + 'a> {
-	fn from(err: E) -> Self {
-		Box::new(err)
+impl<T: ?Sized> Deref for OnceLock<T> {
+	type Target = sync::OnceLock<boxed::Box<T>>;
+	fn deref(&self) -> &Self::Target {
+		&self.0
 	}
 }
-impl<'a, E: error::Error + Serialize + Deserialize + 'a> From<E> for boxed::Box<dyn Error + 'a> {
-	fn from(err: E) -> Self {
-		boxed::Box::new(err)
+impl<T: ?Sized> DerefMut for OnceLock<T> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.0
 	}
 }
-
-impl serde::ser::Serialize for dyn Error {
-	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer,
-	{
-		serialize(self, serializer)
+impl<T: ?Sized> From<sync::OnceLock<boxed::Box<T>>> for OnceLock<T> {
+	fn from(t: sync::OnceLock<boxed::Box<T>>) -> Self {
+		Self(t)
 	}
 }
-impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Error + 'static> {
-	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-	where
-		D: serde::Deserializer<'de>,
-	{
-		<Box<dyn Error + 'static>>::deserialize(deserializer).map(|x| x.0)
+impl<T: ?Sized> From<OnceLock<T>> for sync::OnceLock<boxed::Box<T>> {
+	fn from(v: OnceLock<T>) -> Self {
+		v.0
 	}
 }
-impl serde::ser::Serialize for dyn Error + Send {
+impl<T: ?Sized> From<boxed::Box<T>> for OnceLock<T> {
+	fn from(t: boxed::Box<T>) -> Self {
+		Self(t.into())
+	}
+}
+impl<T: fmt::Debug + ?Sized> fmt::Debug for OnceLock<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		self.0.fmt(f)
+	}
+}
+impl<T: Serialize + ?Sized + 'static> serde::ser::Serialize for OnceLock<T> {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
 	{
-		serialize(self, serializer)
+		self.0.get().map(SerializeBox).serialize(serializer)
 	}
 }
-impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Error + Send + 'static> {
+impl<'de, T: Deserialize + ?Sized + 'static> serde::de::Deserialize<'de> for OnceLock<T> {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where
 		D: serde::Deserializer<'de>,
 	{
-		<Box<dyn Error + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+		<Option<Box<T>>>::deserialize(deserializer).map(|t| match t {
+			Some(t) => Self::from(t.0),
+			None => Self::new(),
+		})
 	}
 }
 
-/// A convenience trait implemented on all (de)serializable implementors of [`std::fmt::Display`].
+/// Convenience wrapper around a shared reference `&'a T` that automatically uses `serde_traitobject` for serialization.
 ///
-/// It can be made into a trait object which is then (de)serializable.
+/// This allows borrowed trait objects to be placed in messages directly. As it's borrowed, it can only be serialized; it deserializes as a [`Box<T>`].
+///
+/// The exceptions are `Ref<str>` and `Ref<[u8]>`, which deserialize without copying by borrowing from the input. This requires a format that can lend out its input, like `bincode`; an error is returned otherwise, for example if a JSON string contains escapes. `Ref<str>` is encoded the same as [`Box<str>`]; `Ref<[u8]>` is written with `serialize_bytes`, as it's read with `deserialize_bytes`, which for `bincode` is the same as [`Box<[u8]>`](Box). Other slices can't be borrowed, as formats only lend out strings and bytes, so are out of scope; deserialize them as a [`Box<[T]>`](Box).
 ///
 /// # Example
 /// ```
-/// # extern crate serde;
-/// #[macro_use] extern crate serde_derive;
+/// # use serde_derive::{Serialize, Deserialize};
 /// extern crate serde_json;
 /// extern crate serde_traitobject as s;
 ///
-/// fn message() -> s::Box<dyn s::Display> {
-///     s::Box::new(String::from("boxed displayable"))
+/// #[derive(Serialize)]
+/// struct Message<'a> {
+///     message: s::Ref<'a, dyn s::Debug>,
+/// }
+/// #[derive(Deserialize)]
+/// struct Received {
+///     message: s::Box<dyn s::Debug>,
 /// }
 ///
-/// let serialized = serde_json::to_string(&message()).unwrap();
-/// let deserialized: s::Box<dyn s::Display> = serde_json::from_str(&serialized).unwrap();
+/// let value = String::from("borrowed");
+/// let serialized = serde_json::to_string(&Message { message: s::Ref::new(&value) }).unwrap();
 ///
-/// println!("{}", deserialized);
-/// # assert_eq!(format!("{}", deserialized), "boxed displayable");
-/// // boxed displayable
+/// let received: Received = serde_json::from_str(&serialized).unwrap();
+/// assert_eq!(format!("{:?}", received.message), "\"borrowed\"");
 /// ```
-pub trait Display: fmt::Display + Serialize + Deserialize {}
-impl<T: ?Sized> Display for T where T: fmt::Display + Serialize + Deserialize {}
-
-impl<'a> AsRef<Self> for dyn Display + 'a {
-	fn as_ref(&self) -> &Self {
-		self
+pub struct Ref<'a, T: ?Sized>(&'a T);
+impl<'a, T: ?Sized> Ref<'a, T> {
+	/// Create a new Ref wrapper
+	pub fn new(t: &'a T) -> Self {
+		Self(t)
+	}
+	/// Convert to the wrapped reference.
+	pub fn into_ref(self) -> &'a T {
+		self.0
 	}
 }
-impl<'a> AsRef<Self> for dyn Display + Send + 'a {
-	fn as_ref(&self) -> &Self {
-		self
+impl<T: ?Sized> clone::Clone for Ref<'_, T> {
+	fn clone(&self) -> Self {
+		*self
 	}
 }
-
-impl serde::ser::Serialize for dyn Display {
-	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer,
-	{
-		serialize(self, serializer)
+impl<T: ?Sized> Copy for Ref<'_, T> {}
+impl<T: ?Sized> Deref for Ref<'_, T> {
+	type Target = T;
+	fn deref(&self) -> &Self::Target {
+		self.0
 	}
 }
-impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Display + 'static> {
-	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-	where
-		D: serde::Deserializer<'de>,
-	{
-		<Box<dyn Display + 'static>>::deserialize(deserializer).map(|x| x.0)
+impl<T: ?Sized> AsRef<T> for Ref<'_, T> {
+	fn as_ref(&self) -> &T {
+		self.0
 	}
 }
-impl serde::ser::Serialize for dyn Display + Send {
-	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+impl<T: ?Sized> Borrow<T> for Ref<'_, T> {
+	fn borrow(&self) -> &T {
+		self.0
+	}
+}
+impl<'a, T: ?Sized> From<&'a T> for Ref<'a, T> {
+	fn from(t: &'a T) -> Self {
+		Self(t)
+	}
+}
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Ref<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		self.0.fmt(f)
+	}
+}
+impl<T: fmt::Display + ?Sized> fmt::Display for Ref<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		self.0.fmt(f)
+	}
+}
+impl<T: Serialize + ?Sized + 'static> serde::ser::Serialize for Ref<'_, T> {
+	default fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
 	{
-		serialize(self, serializer)
+		serialize_ref(self.0, serializer)
 	}
 }
-impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Display + Send + 'static> {
-	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+/// Writes the bytes with `serialize_bytes`, such that they can be borrowed when deserialized.
+impl serde::ser::Serialize for Ref<'_, [u8]> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
-		D: serde::Deserializer<'de>,
+		S: serde::Serializer,
 	{
-		<Box<dyn Display + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+		serializer.serialize_bytes(self.0)
 	}
 }
-
-/// A convenience trait implemented on all (de)serializable implementors of [`std::fmt::Debug`].
-///
-/// It can be made into a trait object which is then (de)serializable.
+/// Borrows the string from the input.
 ///
 /// # Example
 /// ```
-/// # extern crate serde;
-/// #[macro_use] extern crate serde_derive;
-/// extern crate serde_json;
+/// extern crate bincode;
 /// extern crate serde_traitobject as s;
 ///
-/// fn debug() -> s::Box<dyn s::Debug> {
-///     s::Box::new(String::from("boxed debuggable"))
-/// }
-///
-/// let serialized = serde_json::to_string(&debug()).unwrap();
-/// let deserialized: s::Box<dyn s::Debug> = serde_json::from_str(&serialized).unwrap();
+/// let serialized = bincode::serialize(&s::Box::<str>::from(Box::<str>::from("borrowed"))).unwrap();
+/// let deserialized: s::Ref<str> = bincode::deserialize(&serialized).unwrap();
 ///
-/// println!("{:?}", deserialized);
-/// # assert_eq!(format!("{:?}", deserialized), "\"boxed debuggable\"");
-/// // "boxed debuggable"
+/// assert_eq!(&*deserialized, "borrowed");
+/// assert!(serialized.as_ptr_range().contains(&deserialized.as_ptr()));
 /// ```
-pub trait Debug: fmt::Debug + Serialize + Deserialize {}
-impl<T: ?Sized> Debug for T where T: fmt::Debug + Serialize + Deserialize {}
-
-impl<'a> AsRef<Self> for dyn Debug + 'a {
-	fn as_ref(&self) -> &Self {
-		self
-	}
-}
-impl<'a> AsRef<Self> for dyn Debug + Send + 'a {
-	fn as_ref(&self) -> &Self {
-		self
-	}
-}
-
-impl serde::ser::Serialize for dyn Debug {
-	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer,
-	{
-		serialize(self, serializer)
-	}
-}
-impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Debug + 'static> {
+impl<'de: 'a, 'a> serde::de::Deserialize<'de> for Ref<'a, str> {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where
 		D: serde::Deserializer<'de>,
 	{
-		<Box<dyn Debug + 'static>>::deserialize(deserializer).map(|x| x.0)
-	}
-}
-impl serde::ser::Serialize for dyn Debug + Send {
-	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer,
-	{
-		serialize(self, serializer)
+		<&'a str as serde::de::Deserialize<'de>>::deserialize(deserializer).map(Self)
 	}
 }
-impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Debug + Send + 'static> {
+/// Borrows the bytes from the input.
+impl<'de: 'a, 'a> serde::de::Deserialize<'de> for Ref<'a, [u8]> {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where
 		D: serde::Deserializer<'de>,
 	{
-		<Box<dyn Debug + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+		<&'a [u8] as serde::de::Deserialize<'de>>::deserialize(deserializer).map(Self)
 	}
 }
 
-/// A convenience trait implemented on all (de)serializable implementors of [`std::ops::FnOnce`].
+/// Convenience wrapper around a mutable reference `&'a mut T` that automatically uses `serde_traitobject` for serialization.
 ///
-/// It can be made into a trait object which is then (de)serializable.
-pub trait FnOnce<Args: Tuple>: ops::FnOnce<Args> + Serialize + Deserialize {}
-impl<T: ?Sized, Args: Tuple> FnOnce<Args> for T where T: ops::FnOnce<Args> + Serialize + Deserialize {}
-
-impl<'a, Args, Output> AsRef<Self> for dyn FnOnce<Args, Output = Output> + 'a {
-	fn as_ref(&self) -> &Self {
-		self
+/// As it's borrowed, it can only be serialized; it deserializes as a [`Box<T>`].
+pub struct RefMut<'a, T: ?Sized>(&'a mut T);
+impl<'a, T: ?Sized> RefMut<'a, T> {
+	/// Create a new `RefMut` wrapper
+	pub fn new(t: &'a mut T) -> Self {
+		Self(t)
+	}
+	/// Convert to the wrapped reference.
+	pub fn into_mut(self) -> &'a mut T {
+		self.0
 	}
 }
-impl<'a, Args, Output> AsRef<Self> for dyn FnOnce<Args, Output = Output> + Send + 'a {
-	fn as_ref(&self) -> &Self {
-		self
+impl<T: ?Sized> Deref for RefMut<'_, T> {
+	type Target = T;
+	fn deref(&self) -> &Self::Target {
+		self.0
 	}
 }
-
-impl<Args: 'static, Output: 'static> serde::ser::Serialize for dyn FnOnce<Args, Output = Output> {
-	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer,
-	{
-		serialize(self, serializer)
+impl<T: ?Sized> DerefMut for RefMut<'_, T> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		self.0
 	}
 }
-impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
-	for boxed::Box<dyn FnOnce<Args, Output = Output> + 'static>
-{
-	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-	where
-		D: serde::Deserializer<'de>,
-	{
-		<Box<dyn FnOnce<Args, Output = Output> + 'static>>::deserialize(deserializer).map(|x| x.0)
+impl<T: ?Sized> AsRef<T> for RefMut<'_, T> {
+	fn as_ref(&self) -> &T {
+		self.0
 	}
 }
-impl<Args: 'static, Output: 'static> serde::ser::Serialize
-	for dyn FnOnce<Args, Output = Output> + Send
+impl<T: ?Sized> AsMut<T> for RefMut<'_, T> {
+	fn as_mut(&mut self) -> &mut T {
+		self.0
+	}
+}
+impl<T: ?Sized> Borrow<T> for RefMut<'_, T> {
+	fn borrow(&self) -> &T {
+		self.0
+	}
+}
+impl<T: ?Sized> BorrowMut<T> for RefMut<'_, T> {
+	fn borrow_mut(&mut self) -> &mut T {
+		self.0
+	}
+}
+impl<'a, T: ?Sized> From<&'a mut T> for RefMut<'a, T> {
+	fn from(t: &'a mut T) -> Self {
+		Self(t)
+	}
+}
+impl<T: fmt::Debug + ?Sized> fmt::Debug for RefMut<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		self.0.fmt(f)
+	}
+}
+impl<T: fmt::Display + ?Sized> fmt::Display for RefMut<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		self.0.fmt(f)
+	}
+}
+impl<T: Serialize + ?Sized + 'static> serde::ser::Serialize for RefMut<'_, T> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize_ref(&*self.0, serializer)
+	}
+}
+
+/// Serializes a borrowed `std::boxed::Box<T>` as though it were a [`Box<T>`].
+struct SerializeBox<'a, T: ?Sized>(&'a boxed::Box<T>);
+impl<T: Serialize + ?Sized + 'static> serde::ser::Serialize for SerializeBox<'_, T> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self.0, serializer)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::any::Any`].
+///
+/// It can be made into a trait object which is then (de)serializable.
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::any::Any;
+///
+/// let erased: s::Box<dyn s::Any> = s::Box::new(String::from("hi there"));
+///
+/// let serialized = serde_json::to_string(&erased).unwrap();
+/// let deserialized: s::Box<dyn s::Any> = serde_json::from_str(&serialized).unwrap();
+///
+/// let downcast: Box<String> = Box::<dyn Any>::downcast(deserialized.into_any()).unwrap();
+///
+/// println!("{}!", downcast);
+/// # assert_eq!(format!("{}!", downcast), "hi there!");
+/// // hi there!
+/// ```
+pub trait Any: any::Any + Serialize + Deserialize {
+	/// Convert to a `&std::any::Any`.
+	fn as_any(&self) -> &dyn any::Any;
+	/// Convert to a `&mut std::any::Any`.
+	fn as_any_mut(&mut self) -> &mut dyn any::Any;
+	/// Convert to a `std::boxed::Box<dyn std::any::Any>`.
+	fn into_any(self: boxed::Box<Self>) -> boxed::Box<dyn any::Any>;
+	/// The name of the concrete type, as given by [`std::any::type_name`]. Like it, this is intended for diagnostics and isn't guaranteed to be unique or stable.
+	fn type_name(&self) -> &'static str;
+	/// The [`TypeId`](std::any::TypeId) of the concrete type.
+	///
+	/// Unlike calling [`type_id`](std::any::Any::type_id) on a wrapper, which can silently return the id of the wrapper itself, this always reaches the value behind the trait object.
+	fn concrete_type_id(&self) -> any::TypeId;
+}
+impl<T> Any for T
+where
+	T: any::Any + Serialize + Deserialize,
 {
+	fn as_any(&self) -> &dyn any::Any {
+		self
+	}
+	fn as_any_mut(&mut self) -> &mut dyn any::Any {
+		self
+	}
+	fn into_any(self: boxed::Box<Self>) -> boxed::Box<dyn any::Any> {
+		self
+	}
+	fn type_name(&self) -> &'static str {
+		any::type_name::<T>()
+	}
+	fn concrete_type_id(&self) -> any::TypeId {
+		any::TypeId::of::<T>()
+	}
+}
+
+impl fmt::Debug for dyn Any {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("Any { .. }")
+	}
+}
+impl fmt::Debug for dyn Any + Send {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("Any { .. }")
+	}
+}
+impl fmt::Debug for dyn Any + Sync {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("Any { .. }")
+	}
+}
+impl fmt::Debug for dyn Any + Send + Sync {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str("Any { .. }")
+	}
+}
+impl AsRef<Self> for dyn Any {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Any + Send {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Any + Sync {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Any + Send + Sync {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl serde::ser::Serialize for dyn Any {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
@@ -728,36 +1568,141 @@ impl<Args: 'static, Output: 'static> serde::ser::Serialize
 		serialize(self, serializer)
 	}
 }
-impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
-	for boxed::Box<dyn FnOnce<Args, Output = Output> + Send + 'static>
-{
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Any + 'static> {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where
 		D: serde::Deserializer<'de>,
 	{
-		<Box<dyn FnOnce<Args, Output = Output> + Send + 'static>>::deserialize(deserializer)
-			.map(|x| x.0)
+		<Box<dyn Any + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Any + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Any + Send + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Any + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Any + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Any + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Any + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Any + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Any + Send + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Any + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
 	}
 }
 
-/// A convenience trait implemented on all (de)serializable implementors of [`std::ops::FnMut`].
+/// A convenience trait implemented on all (de)serializable implementors of [`std::error::Error`].
 ///
 /// It can be made into a trait object which is then (de)serializable.
-pub trait FnMut<Args: Tuple>: ops::FnMut<Args> + Serialize + Deserialize {}
-impl<T: ?Sized, Args: Tuple> FnMut<Args> for T where T: ops::FnMut<Args> + Serialize + Deserialize {}
+///
+/// # Example
+/// ```
+/// # extern crate serde;
+/// #[macro_use] extern crate serde_derive;
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::fmt;
+///
+/// #[derive(Serialize,Deserialize,Debug)]
+/// struct MyError(String);
+/// impl fmt::Display for MyError {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         write!(f, "{}", self.0)
+///     }
+/// }
+/// impl std::error::Error for MyError {}
+///
+/// fn fallible() -> Result<(),s::Box<dyn s::Error>> {
+///     Err(Box::new(MyError(String::from("boxed error"))) as Box<dyn s::Error>)?
+/// }
+///
+/// let serialized = serde_json::to_string(&fallible()).unwrap();
+/// let deserialized: Result<(),s::Box<dyn s::Error>> = serde_json::from_str(&serialized).unwrap();
+///
+/// println!("{:?}", deserialized);
+/// # assert_eq!(format!("{:?}", deserialized), "Err(MyError(\"boxed error\"))");
+/// // Err(MyError("boxed error"))
+///
+/// // `s::Box<dyn s::Error>` is itself a `std::error::Error`, so can be propagated with `?`
+/// fn forward(result: Result<(),s::Box<dyn s::Error>>) -> Result<(), Box<dyn std::error::Error>> {
+///     Ok(result?)
+/// }
+///
+/// assert_eq!(forward(deserialized).unwrap_err().to_string(), "boxed error");
+/// ```
+pub trait Error: error::Error + Serialize + Deserialize {}
+impl<T: ?Sized> Error for T where T: error::Error + Serialize + Deserialize {}
 
-impl<'a, Args, Output> AsRef<Self> for dyn FnMut<Args, Output = Output> + 'a {
+impl AsRef<Self> for dyn Error + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Error + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Error + Sync + '_ {
 	fn as_ref(&self) -> &Self {
 		self
 	}
 }
-impl<'a, Args, Output> AsRef<Self> for dyn FnMut<Args, Output = Output> + Send + 'a {
+impl AsRef<Self> for dyn Error + Send + Sync + '_ {
 	fn as_ref(&self) -> &Self {
 		self
 	}
 }
 
-impl<Args: 'static, Output: 'static> serde::ser::Serialize for dyn FnMut<Args, Output = Output> {
+impl<'a, E: error::Error + Serialize + Deserialize + 'a> From<E> for boxed::Box<dyn Error + 'a> {
+	fn from(err: E) -> Self {
+		boxed::Box::new(err)
+	}
+}
+impl<'a, E: error::Error + Serialize + Deserialize + Send + Sync + 'a> From<E>
+	for boxed::Box<dyn Error + Send + Sync + 'a>
+{
+	fn from(err: E) -> Self {
+		boxed::Box::new(err)
+	}
+}
+
+impl serde::ser::Serialize for dyn Error {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
@@ -765,19 +1710,15 @@ impl<Args: 'static, Output: 'static> serde::ser::Serialize for dyn FnMut<Args, O
 		serialize(self, serializer)
 	}
 }
-impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
-	for boxed::Box<dyn FnMut<Args, Output = Output> + 'static>
-{
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Error + 'static> {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where
 		D: serde::Deserializer<'de>,
 	{
-		<Box<dyn FnMut<Args, Output = Output> + 'static>>::deserialize(deserializer).map(|x| x.0)
+		<Box<dyn Error + 'static>>::deserialize(deserializer).map(|x| x.0)
 	}
 }
-impl<Args: 'static, Output: 'static> serde::ser::Serialize
-	for dyn FnMut<Args, Output = Output> + Send
-{
+impl serde::ser::Serialize for dyn Error + Send {
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
@@ -785,72 +1726,3252 @@ impl<Args: 'static, Output: 'static> serde::ser::Serialize
 		serialize(self, serializer)
 	}
 }
-impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
-	for boxed::Box<dyn FnMut<Args, Output = Output> + Send + 'static>
-{
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Error + Send + 'static> {
 	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where
 		D: serde::Deserializer<'de>,
 	{
-		<Box<dyn FnMut<Args, Output = Output> + Send + 'static>>::deserialize(deserializer)
-			.map(|x| x.0)
+		<Box<dyn Error + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Error + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Error + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Error + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Error + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Error + Send + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Error + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::fmt::Display`].
+///
+/// It can be made into a trait object which is then (de)serializable.
+///
+/// # Example
+/// ```
+/// # extern crate serde;
+/// #[macro_use] extern crate serde_derive;
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// fn message() -> s::Box<dyn s::Display> {
+///     s::Box::new(String::from("boxed displayable"))
+/// }
+///
+/// let serialized = serde_json::to_string(&message()).unwrap();
+/// let deserialized: s::Box<dyn s::Display> = serde_json::from_str(&serialized).unwrap();
+///
+/// println!("{}", deserialized);
+/// # assert_eq!(format!("{}", deserialized), "boxed displayable");
+/// // boxed displayable
+/// ```
+pub trait Display: fmt::Display + Serialize + Deserialize {}
+impl<T: ?Sized> Display for T where T: fmt::Display + Serialize + Deserialize {}
+
+impl AsRef<Self> for dyn Display + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Display + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Display + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Display + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl serde::ser::Serialize for dyn Display {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Display + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Display + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Display + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Display + Send + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Display + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Display + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Display + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Display + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Display + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Display + Send + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Display + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::fmt::Debug`].
+///
+/// It can be made into a trait object which is then (de)serializable.
+///
+/// # Example
+/// ```
+/// # extern crate serde;
+/// #[macro_use] extern crate serde_derive;
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// fn debug() -> s::Box<dyn s::Debug> {
+///     s::Box::new(String::from("boxed debuggable"))
+/// }
+///
+/// let serialized = serde_json::to_string(&debug()).unwrap();
+/// let deserialized: s::Box<dyn s::Debug> = serde_json::from_str(&serialized).unwrap();
+///
+/// println!("{:?}", deserialized);
+/// # assert_eq!(format!("{:?}", deserialized), "\"boxed debuggable\"");
+/// // "boxed debuggable"
+/// ```
+pub trait Debug: fmt::Debug + Serialize + Deserialize {}
+impl<T: ?Sized> Debug for T where T: fmt::Debug + Serialize + Deserialize {}
+
+impl AsRef<Self> for dyn Debug + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Debug + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Debug + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Debug + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl serde::ser::Serialize for dyn Debug {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Debug + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Debug + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Debug + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Debug + Send + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Debug + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Debug + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Debug + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Debug + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Debug + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Debug + Send + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Debug + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::ops::FnOnce`].
+///
+/// It can be made into a trait object which is then (de)serializable.
+pub trait FnOnce<Args: Tuple>: ops::FnOnce<Args> + Serialize + Deserialize {}
+impl<T: ?Sized, Args: Tuple> FnOnce<Args> for T where T: ops::FnOnce<Args> + Serialize + Deserialize {}
+
+impl<Args, Output> AsRef<Self> for dyn FnOnce<Args, Output = Output> + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Args, Output> AsRef<Self> for dyn FnOnce<Args, Output = Output> + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Args, Output> AsRef<Self> for dyn FnOnce<Args, Output = Output> + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Args, Output> AsRef<Self> for dyn FnOnce<Args, Output = Output> + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl<Args: 'static, Output: 'static> serde::ser::Serialize for dyn FnOnce<Args, Output = Output> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn FnOnce<Args, Output = Output> + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn FnOnce<Args, Output = Output> + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl<Args: 'static, Output: 'static> serde::ser::Serialize
+	for dyn FnOnce<Args, Output = Output> + Send
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn FnOnce<Args, Output = Output> + Send + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn FnOnce<Args, Output = Output> + Send + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+impl<Args: 'static, Output: 'static> serde::ser::Serialize
+	for dyn FnOnce<Args, Output = Output> + Sync
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn FnOnce<Args, Output = Output> + Sync + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn FnOnce<Args, Output = Output> + Sync + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+impl<Args: 'static, Output: 'static> serde::ser::Serialize
+	for dyn FnOnce<Args, Output = Output> + Send + Sync
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn FnOnce<Args, Output = Output> + Send + Sync + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn FnOnce<Args, Output = Output> + Send + Sync + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::ops::FnMut`].
+///
+/// It can be made into a trait object which is then (de)serializable.
+pub trait FnMut<Args: Tuple>: ops::FnMut<Args> + Serialize + Deserialize {}
+impl<T: ?Sized, Args: Tuple> FnMut<Args> for T where T: ops::FnMut<Args> + Serialize + Deserialize {}
+
+impl<Args, Output> AsRef<Self> for dyn FnMut<Args, Output = Output> + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Args, Output> AsRef<Self> for dyn FnMut<Args, Output = Output> + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Args, Output> AsRef<Self> for dyn FnMut<Args, Output = Output> + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Args, Output> AsRef<Self> for dyn FnMut<Args, Output = Output> + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl<Args: 'static, Output: 'static> serde::ser::Serialize for dyn FnMut<Args, Output = Output> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn FnMut<Args, Output = Output> + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn FnMut<Args, Output = Output> + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl<Args: 'static, Output: 'static> serde::ser::Serialize
+	for dyn FnMut<Args, Output = Output> + Send
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn FnMut<Args, Output = Output> + Send + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn FnMut<Args, Output = Output> + Send + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+impl<Args: 'static, Output: 'static> serde::ser::Serialize
+	for dyn FnMut<Args, Output = Output> + Sync
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn FnMut<Args, Output = Output> + Sync + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn FnMut<Args, Output = Output> + Sync + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+impl<Args: 'static, Output: 'static> serde::ser::Serialize
+	for dyn FnMut<Args, Output = Output> + Send + Sync
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn FnMut<Args, Output = Output> + Send + Sync + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn FnMut<Args, Output = Output> + Send + Sync + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::ops::Fn`].
+///
+/// It can be made into a trait object which is then (de)serializable.
+pub trait Fn<Args: Tuple>: ops::Fn<Args> + Serialize + Deserialize {}
+impl<T: ?Sized, Args: Tuple> Fn<Args> for T where T: ops::Fn<Args> + Serialize + Deserialize {}
+
+impl<Args, Output> AsRef<Self> for dyn Fn<Args, Output = Output> + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Args, Output> AsRef<Self> for dyn Fn<Args, Output = Output> + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Args, Output> AsRef<Self> for dyn Fn<Args, Output = Output> + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Args, Output> AsRef<Self> for dyn Fn<Args, Output = Output> + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl<Args: 'static, Output: 'static> serde::ser::Serialize for dyn Fn<Args, Output = Output> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn Fn<Args, Output = Output> + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Fn<Args, Output = Output> + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl<Args: 'static, Output: 'static> serde::ser::Serialize
+	for dyn Fn<Args, Output = Output> + Send
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn Fn<Args, Output = Output> + Send + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Fn<Args, Output = Output> + Send + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+impl<Args: 'static, Output: 'static> serde::ser::Serialize
+	for dyn Fn<Args, Output = Output> + Sync
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn Fn<Args, Output = Output> + Sync + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Fn<Args, Output = Output> + Sync + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+impl<Args: 'static, Output: 'static> serde::ser::Serialize
+	for dyn Fn<Args, Output = Output> + Send + Sync
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn Fn<Args, Output = Output> + Send + Sync + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Fn<Args, Output = Output> + Send + Sync + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::iter::Iterator`].
+///
+/// It can be made into a trait object which is then (de)serializable.
+///
+/// # Example
+/// ```
+/// # extern crate serde;
+/// #[macro_use] extern crate serde_derive;
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// let iter: s::Box<dyn s::Iterator<Item = u32>> = s::Box::new(0..3);
+///
+/// let serialized = serde_json::to_string(&iter).unwrap();
+/// let deserialized: s::Box<dyn s::Iterator<Item = u32>> = serde_json::from_str(&serialized).unwrap();
+///
+/// assert_eq!(deserialized.collect::<Vec<_>>(), [0, 1, 2]);
+/// ```
+pub trait Iterator: iter::Iterator + Serialize + Deserialize {}
+impl<T: ?Sized> Iterator for T where T: iter::Iterator + Serialize + Deserialize {}
+
+impl<Item> AsRef<Self> for dyn Iterator<Item = Item> + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Item> AsRef<Self> for dyn Iterator<Item = Item> + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Item> AsRef<Self> for dyn Iterator<Item = Item> + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Item> AsRef<Self> for dyn Iterator<Item = Item> + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl<Item: 'static> serde::ser::Serialize for dyn Iterator<Item = Item> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Item: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn Iterator<Item = Item> + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Iterator<Item = Item> + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl<Item: 'static> serde::ser::Serialize for dyn Iterator<Item = Item> + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Item: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn Iterator<Item = Item> + Send + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Iterator<Item = Item> + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl<Item: 'static> serde::ser::Serialize for dyn Iterator<Item = Item> + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Item: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn Iterator<Item = Item> + Sync + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Iterator<Item = Item> + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl<Item: 'static> serde::ser::Serialize for dyn Iterator<Item = Item> + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Item: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn Iterator<Item = Item> + Send + Sync + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Iterator<Item = Item> + Send + Sync + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::iter::DoubleEndedIterator`].
+///
+/// It can be made into a trait object which is then (de)serializable.
+///
+/// # Example
+/// ```
+/// # extern crate serde;
+/// #[macro_use] extern crate serde_derive;
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// let iter: s::Box<dyn s::DoubleEndedIterator<Item = u32>> = s::Box::new(0..3);
+///
+/// let serialized = serde_json::to_string(&iter).unwrap();
+/// let deserialized: s::Box<dyn s::DoubleEndedIterator<Item = u32>> =
+///     serde_json::from_str(&serialized).unwrap();
+///
+/// assert_eq!(deserialized.rev().collect::<Vec<_>>(), [2, 1, 0]);
+/// ```
+pub trait DoubleEndedIterator: iter::DoubleEndedIterator + Serialize + Deserialize {}
+impl<T: ?Sized> DoubleEndedIterator for T where
+	T: iter::DoubleEndedIterator + Serialize + Deserialize
+{
+}
+
+impl<Item> AsRef<Self> for dyn DoubleEndedIterator<Item = Item> + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Item> AsRef<Self> for dyn DoubleEndedIterator<Item = Item> + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Item> AsRef<Self> for dyn DoubleEndedIterator<Item = Item> + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Item> AsRef<Self> for dyn DoubleEndedIterator<Item = Item> + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl<Item: 'static> serde::ser::Serialize for dyn DoubleEndedIterator<Item = Item> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Item: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn DoubleEndedIterator<Item = Item> + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn DoubleEndedIterator<Item = Item> + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+impl<Item: 'static> serde::ser::Serialize for dyn DoubleEndedIterator<Item = Item> + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Item: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn DoubleEndedIterator<Item = Item> + Send + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn DoubleEndedIterator<Item = Item> + Send + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+impl<Item: 'static> serde::ser::Serialize for dyn DoubleEndedIterator<Item = Item> + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Item: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn DoubleEndedIterator<Item = Item> + Sync + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn DoubleEndedIterator<Item = Item> + Sync + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+impl<Item: 'static> serde::ser::Serialize for dyn DoubleEndedIterator<Item = Item> + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Item: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn DoubleEndedIterator<Item = Item> + Send + Sync + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn DoubleEndedIterator<Item = Item> + Send + Sync + 'static>>::deserialize(
+			deserializer,
+		)
+		.map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::iter::ExactSizeIterator`].
+///
+/// It can be made into a trait object which is then (de)serializable.
+///
+/// # Example
+/// ```
+/// # extern crate serde;
+/// #[macro_use] extern crate serde_derive;
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// let iter: s::Box<dyn s::ExactSizeIterator<Item = u32>> = s::Box::new(0..3);
+///
+/// let serialized = serde_json::to_string(&iter).unwrap();
+/// let deserialized: s::Box<dyn s::ExactSizeIterator<Item = u32>> =
+///     serde_json::from_str(&serialized).unwrap();
+///
+/// assert_eq!(deserialized.len(), 3);
+/// ```
+pub trait ExactSizeIterator: iter::ExactSizeIterator + Serialize + Deserialize {}
+impl<T: ?Sized> ExactSizeIterator for T where T: iter::ExactSizeIterator + Serialize + Deserialize {}
+
+impl<Item> AsRef<Self> for dyn ExactSizeIterator<Item = Item> + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Item> AsRef<Self> for dyn ExactSizeIterator<Item = Item> + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Item> AsRef<Self> for dyn ExactSizeIterator<Item = Item> + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Item> AsRef<Self> for dyn ExactSizeIterator<Item = Item> + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl<Item: 'static> serde::ser::Serialize for dyn ExactSizeIterator<Item = Item> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Item: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn ExactSizeIterator<Item = Item> + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn ExactSizeIterator<Item = Item> + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl<Item: 'static> serde::ser::Serialize for dyn ExactSizeIterator<Item = Item> + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Item: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn ExactSizeIterator<Item = Item> + Send + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn ExactSizeIterator<Item = Item> + Send + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+impl<Item: 'static> serde::ser::Serialize for dyn ExactSizeIterator<Item = Item> + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Item: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn ExactSizeIterator<Item = Item> + Sync + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn ExactSizeIterator<Item = Item> + Sync + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+impl<Item: 'static> serde::ser::Serialize for dyn ExactSizeIterator<Item = Item> + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Item: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn ExactSizeIterator<Item = Item> + Send + Sync + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn ExactSizeIterator<Item = Item> + Send + Sync + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::future::Future`].
+///
+/// It can be made into a trait object which is then (de)serializable. A [`Box`] of it can be `.await`ed, or converted to a [`BoxFuture`] with [`Box::into_pin`].
+///
+/// # Example
+/// ```
+/// # extern crate serde;
+/// #[macro_use] extern crate serde_derive;
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::{future::{Future, IntoFuture}, pin::Pin, task::{Context, Poll, Waker}};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Countdown(u32);
+/// impl Future for Countdown {
+///     type Output = &'static str;
+///     fn poll(mut self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+///         if self.0 == 0 {
+///             return Poll::Ready("liftoff");
+///         }
+///         self.0 -= 1;
+///         cx.waker().wake_by_ref();
+///         Poll::Pending
+///     }
+/// }
+///
+/// let pending: s::Box<dyn s::Future<Output = &'static str> + Send> = s::Box::new(Countdown(3));
+///
+/// let serialized = serde_json::to_string(&pending).unwrap();
+/// let deserialized: s::Box<dyn s::Future<Output = &'static str> + Send> =
+///     serde_json::from_str(&serialized).unwrap();
+///
+/// let mut future: s::BoxFuture<'static, &'static str> = deserialized.into_future();
+/// let mut cx = Context::from_waker(Waker::noop());
+/// let output = loop {
+///     if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+///         break output;
+///     }
+/// };
+/// assert_eq!(output, "liftoff");
+/// ```
+pub trait Future: future::Future + Serialize + Deserialize {}
+impl<T: ?Sized> Future for T where T: future::Future + Serialize + Deserialize {}
+
+impl<Output> AsRef<Self> for dyn Future<Output = Output> + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Output> AsRef<Self> for dyn Future<Output = Output> + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Output> AsRef<Self> for dyn Future<Output = Output> + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl<Output> AsRef<Self> for dyn Future<Output = Output> + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl<Output: 'static> serde::ser::Serialize for dyn Future<Output = Output> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Output: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn Future<Output = Output> + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Future<Output = Output> + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl<Output: 'static> serde::ser::Serialize for dyn Future<Output = Output> + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Output: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn Future<Output = Output> + Send + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Future<Output = Output> + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl<Output: 'static> serde::ser::Serialize for dyn Future<Output = Output> + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Output: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn Future<Output = Output> + Sync + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Future<Output = Output> + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl<Output: 'static> serde::ser::Serialize for dyn Future<Output = Output> + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, Output: 'static> serde::de::Deserialize<'de>
+	for boxed::Box<dyn Future<Output = Output> + Send + Sync + 'static>
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Future<Output = Output> + Send + Sync + 'static>>::deserialize(deserializer)
+			.map(|x| x.0)
+	}
+}
+
+/// An owned, pinned and dynamically typed [`Future`], like `futures::future::BoxFuture`.
+///
+/// This is what a deserialized `Box<dyn Future<Output = T> + Send>` becomes once it's ready to be polled.
+pub type BoxFuture<'a, T> = Pin<boxed::Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::io::Read`].
+///
+/// It can be made into a trait object which is then (de)serializable.
+///
+/// # Example
+/// ```
+/// # extern crate serde;
+/// #[macro_use] extern crate serde_derive;
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::io::{self, Read};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Memory {
+///     data: Vec<u8>,
+///     pos: usize,
+/// }
+/// impl Read for Memory {
+///     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+///         let len = (&self.data[self.pos..]).read(buf)?;
+///         self.pos += len;
+///         Ok(len)
+///     }
+/// }
+///
+/// let reader: s::Box<dyn s::Read> = s::Box::new(Memory { data: b"erased".to_vec(), pos: 0 });
+///
+/// let serialized = serde_json::to_string(&reader).unwrap();
+/// let mut deserialized: s::Box<dyn s::Read> = serde_json::from_str(&serialized).unwrap();
+///
+/// let mut contents = String::new();
+/// deserialized.read_to_string(&mut contents).unwrap();
+/// assert_eq!(contents, "erased");
+/// ```
+pub trait Read: io::Read + Serialize + Deserialize {}
+impl<T: ?Sized> Read for T where T: io::Read + Serialize + Deserialize {}
+
+impl AsRef<Self> for dyn Read + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Read + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Read + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Read + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl serde::ser::Serialize for dyn Read {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Read + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Read + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Read + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Read + Send + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Read + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Read + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Read + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Read + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Read + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Read + Send + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Read + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::io::Write`].
+///
+/// It can be made into a trait object which is then (de)serializable.
+///
+/// # Example
+/// ```
+/// # extern crate serde;
+/// #[macro_use] extern crate serde_derive;
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::io::Write;
+///
+/// let writer: s::Box<dyn s::Write> = s::Box::new(Vec::<u8>::new());
+///
+/// let serialized = serde_json::to_string(&writer).unwrap();
+/// let mut deserialized: s::Box<dyn s::Write> = serde_json::from_str(&serialized).unwrap();
+///
+/// deserialized.write_all(b"erased").unwrap();
+/// ```
+pub trait Write: io::Write + Serialize + Deserialize {}
+impl<T: ?Sized> Write for T where T: io::Write + Serialize + Deserialize {}
+
+impl AsRef<Self> for dyn Write + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Write + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Write + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Write + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl serde::ser::Serialize for dyn Write {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Write + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Write + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Write + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Write + Send + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Write + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Write + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Write + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Write + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Write + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Write + Send + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Write + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::io::Seek`].
+///
+/// It can be made into a trait object which is then (de)serializable.
+pub trait Seek: io::Seek + Serialize + Deserialize {}
+impl<T: ?Sized> Seek for T where T: io::Seek + Serialize + Deserialize {}
+
+impl AsRef<Self> for dyn Seek + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Seek + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Seek + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Seek + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl serde::ser::Serialize for dyn Seek {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Seek + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Seek + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Seek + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Seek + Send + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Seek + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Seek + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Seek + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Seek + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Seek + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Seek + Send + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Seek + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::io::BufRead`].
+///
+/// It can be made into a trait object which is then (de)serializable.
+///
+/// # Example
+/// ```
+/// # extern crate serde;
+/// #[macro_use] extern crate serde_derive;
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::io::{self, BufRead, Read};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Memory {
+///     data: Vec<u8>,
+///     pos: usize,
+/// }
+/// impl Read for Memory {
+///     fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+///         let len = self.fill_buf()?.read(buf)?;
+///         self.consume(len);
+///         Ok(len)
+///     }
+/// }
+/// impl BufRead for Memory {
+///     fn fill_buf(&mut self) -> io::Result<&[u8]> {
+///         Ok(&self.data[self.pos..])
+///     }
+///     fn consume(&mut self, amt: usize) {
+///         self.pos += amt;
+///     }
+/// }
+///
+/// let reader: s::Box<dyn s::BufRead + Send> = s::Box::new(Memory { data: b"one\ntwo\n".to_vec(), pos: 0 });
+///
+/// let serialized = serde_json::to_string(&reader).unwrap();
+/// let deserialized: s::Box<dyn s::BufRead + Send> = serde_json::from_str(&serialized).unwrap();
+///
+/// let lines = deserialized.lines().collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(lines, ["one", "two"]);
+/// ```
+pub trait BufRead: io::BufRead + Serialize + Deserialize {}
+impl<T: ?Sized> BufRead for T where T: io::BufRead + Serialize + Deserialize {}
+
+impl AsRef<Self> for dyn BufRead + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn BufRead + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn BufRead + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn BufRead + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl serde::ser::Serialize for dyn BufRead {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn BufRead + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn BufRead + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn BufRead + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn BufRead + Send + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn BufRead + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn BufRead + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn BufRead + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn BufRead + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn BufRead + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn BufRead + Send + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn BufRead + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::hash::Hash`].
+///
+/// It can be made into a trait object which is then (de)serializable and hashable. To make your own trait objects hashable, add this as a supertrait and forward to [`Hash::dyn_hash`]:
+///
+/// ```
+/// # use serde_traitobject as s;
+/// trait Key: s::Hash {}
+///
+/// impl std::hash::Hash for dyn Key {
+///     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+///         self.dyn_hash(state)
+///     }
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::{collections::hash_map::DefaultHasher, hash::{Hash, Hasher}};
+///
+/// fn hash<T: Hash + ?Sized>(t: &T) -> u64 {
+///     let mut hasher = DefaultHasher::new();
+///     t.hash(&mut hasher);
+///     hasher.finish()
+/// }
+///
+/// let key: s::Box<dyn s::Hash> = s::Box::new(String::from("key"));
+///
+/// let serialized = serde_json::to_string(&key).unwrap();
+/// let deserialized: s::Box<dyn s::Hash> = serde_json::from_str(&serialized).unwrap();
+///
+/// assert_eq!(hash(&deserialized), hash("key"));
+/// ```
+pub trait Hash: Serialize + Deserialize {
+	/// Feed this value into the given `std::hash::Hasher`.
+	fn dyn_hash(&self, state: &mut dyn hash::Hasher);
+}
+impl<T: ?Sized> Hash for T
+where
+	T: hash::Hash + Serialize + Deserialize,
+{
+	fn dyn_hash(&self, mut state: &mut dyn hash::Hasher) {
+		self.hash(&mut state);
+	}
+}
+
+impl hash::Hash for dyn Hash {
+	fn hash<H: hash::Hasher>(&self, state: &mut H) {
+		self.dyn_hash(state);
+	}
+}
+impl hash::Hash for dyn Hash + Send {
+	fn hash<H: hash::Hasher>(&self, state: &mut H) {
+		self.dyn_hash(state);
+	}
+}
+impl hash::Hash for dyn Hash + Sync {
+	fn hash<H: hash::Hasher>(&self, state: &mut H) {
+		self.dyn_hash(state);
+	}
+}
+impl hash::Hash for dyn Hash + Send + Sync {
+	fn hash<H: hash::Hasher>(&self, state: &mut H) {
+		self.dyn_hash(state);
+	}
+}
+
+impl AsRef<Self> for dyn Hash + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Hash + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Hash + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Hash + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl serde::ser::Serialize for dyn Hash {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Hash + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Hash + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Hash + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Hash + Send + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Hash + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Hash + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Hash + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Hash + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Hash + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Hash + Send + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Hash + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::hash::Hasher`].
+///
+/// It can be made into a trait object which is then (de)serializable.
+///
+/// # Example
+/// ```
+/// # extern crate serde;
+/// #[macro_use] extern crate serde_derive;
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::hash::{Hash, Hasher};
+///
+/// #[derive(Serialize, Deserialize)]
+/// struct Fnv(u64);
+/// impl Hasher for Fnv {
+///     fn finish(&self) -> u64 {
+///         self.0
+///     }
+///     fn write(&mut self, bytes: &[u8]) {
+///         for byte in bytes {
+///             self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(0x100000001b3);
+///         }
+///     }
+/// }
+///
+/// let hasher: s::Box<dyn s::Hasher> = s::Box::new(Fnv(0xcbf29ce484222325));
+///
+/// let serialized = serde_json::to_string(&hasher).unwrap();
+/// let mut deserialized: s::Box<dyn s::Hasher> = serde_json::from_str(&serialized).unwrap();
+///
+/// "shard key".hash(&mut deserialized);
+/// let mut expected = Fnv(0xcbf29ce484222325);
+/// "shard key".hash(&mut expected);
+/// assert_eq!(deserialized.finish(), expected.finish());
+/// ```
+pub trait Hasher: hash::Hasher + Serialize + Deserialize {}
+impl<T: ?Sized> Hasher for T where T: hash::Hasher + Serialize + Deserialize {}
+
+impl AsRef<Self> for dyn Hasher + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Hasher + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Hasher + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Hasher + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl serde::ser::Serialize for dyn Hasher {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Hasher + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Hasher + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Hasher + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Hasher + Send + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Hasher + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Hasher + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Hasher + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Hasher + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Hasher + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Hasher + Send + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Hasher + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::cmp::PartialEq`].
+///
+/// It can be made into a trait object which is then (de)serializable and comparable. Values of different concrete types compare as unequal. To make your own trait objects comparable, add this (or [`Eq`]) as a supertrait and forward to [`PartialEq::dyn_eq`]:
+///
+/// ```
+/// # use serde_traitobject as s;
+/// trait Message: s::PartialEq {}
+///
+/// impl PartialEq for dyn Message {
+///     fn eq(&self, other: &Self) -> bool {
+///         self.dyn_eq(other.as_any())
+///     }
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// let message: s::Box<dyn s::PartialEq> = s::Box::new(String::from("message"));
+///
+/// let serialized = serde_json::to_string(&message).unwrap();
+/// let deserialized: s::Box<dyn s::PartialEq> = serde_json::from_str(&serialized).unwrap();
+///
+/// assert!(deserialized == message);
+/// assert!(deserialized != s::Box::new(123u8) as s::Box<dyn s::PartialEq>);
+/// ```
+pub trait PartialEq: Any {
+	/// Whether this value equals `other`, which is `false` if `other` is of a different concrete type.
+	fn dyn_eq(&self, other: &dyn any::Any) -> bool;
+}
+impl<T> PartialEq for T
+where
+	T: cmp::PartialEq + Any,
+{
+	fn dyn_eq(&self, other: &dyn any::Any) -> bool {
+		other
+			.downcast_ref::<Self>()
+			.is_some_and(|other| self == other)
+	}
+}
+
+impl cmp::PartialEq for dyn PartialEq {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other.as_any())
+	}
+}
+impl cmp::PartialEq for dyn PartialEq + Send {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other.as_any())
+	}
+}
+impl cmp::PartialEq for dyn PartialEq + Sync {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other.as_any())
+	}
+}
+impl cmp::PartialEq for dyn PartialEq + Send + Sync {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other.as_any())
+	}
+}
+impl AsRef<Self> for dyn PartialEq + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn PartialEq + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn PartialEq + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn PartialEq + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl serde::ser::Serialize for dyn PartialEq {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn PartialEq + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn PartialEq + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn PartialEq + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn PartialEq + Send + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn PartialEq + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn PartialEq + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn PartialEq + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn PartialEq + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn PartialEq + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn PartialEq + Send + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn PartialEq + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::cmp::Eq`].
+///
+/// It can be made into a trait object which is then (de)serializable and comparable, for example as the key of a `HashMap` along with [`Hash`]. See [`PartialEq`] for how to make your own trait objects comparable.
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::{collections::HashMap, hash::{Hash, Hasher}};
+///
+/// trait Key: s::Eq + s::Hash {}
+/// impl<T: s::Eq + s::Hash> Key for T {}
+///
+/// impl PartialEq for dyn Key {
+///     fn eq(&self, other: &Self) -> bool {
+///         self.dyn_eq(other.as_any())
+///     }
+/// }
+/// impl Eq for dyn Key {}
+/// impl Hash for dyn Key {
+///     fn hash<H: Hasher>(&self, state: &mut H) {
+///         self.dyn_hash(state)
+///     }
+/// }
+///
+/// let mut map: HashMap<Box<dyn Key>, &str> = HashMap::new();
+/// let _ = map.insert(Box::new(String::from("a")), "string");
+/// let _ = map.insert(Box::new(1u8), "integer");
+///
+/// assert_eq!(map[&(Box::new(1u8) as Box<dyn Key>)], "integer");
+/// assert_eq!(map[&(Box::new(String::from("a")) as Box<dyn Key>)], "string");
+/// ```
+pub trait Eq: PartialEq {}
+impl<T> Eq for T where T: cmp::Eq + PartialEq {}
+
+impl cmp::PartialEq for dyn Eq {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other.as_any())
+	}
+}
+impl cmp::Eq for dyn Eq {}
+impl cmp::PartialEq for dyn Eq + Send {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other.as_any())
+	}
+}
+impl cmp::Eq for dyn Eq + Send {}
+impl cmp::PartialEq for dyn Eq + Sync {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other.as_any())
+	}
+}
+impl cmp::Eq for dyn Eq + Sync {}
+impl cmp::PartialEq for dyn Eq + Send + Sync {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other.as_any())
+	}
+}
+impl cmp::Eq for dyn Eq + Send + Sync {}
+impl AsRef<Self> for dyn Eq + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Eq + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Eq + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Eq + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl serde::ser::Serialize for dyn Eq {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Eq + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Eq + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Eq + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Eq + Send + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Eq + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Eq + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Eq + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Eq + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Eq + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Eq + Send + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Eq + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::cmp::PartialOrd`].
+///
+/// It can be made into a trait object which is then (de)serializable and comparable. Values of different concrete types are ordered by their [`TypeId`](std::any::TypeId), which is consistent within a binary. As with [`PartialEq`], to make your own trait objects comparable, add this (or [`Ord`]) as a supertrait and forward to [`PartialOrd::dyn_partial_cmp`].
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// let a: s::Box<dyn s::PartialOrd> = s::Box::new(1.5f64);
+/// let b: s::Box<dyn s::PartialOrd> = s::Box::new(2.5f64);
+///
+/// let serialized = serde_json::to_string(&a).unwrap();
+/// let deserialized: s::Box<dyn s::PartialOrd> = serde_json::from_str(&serialized).unwrap();
+///
+/// assert!(deserialized < b);
+/// ```
+pub trait PartialOrd: PartialEq {
+	/// Compare this value with `other`, falling back to comparing type ids if `other` is of a different concrete type.
+	fn dyn_partial_cmp(&self, other: &dyn any::Any) -> Option<cmp::Ordering>;
+}
+impl<T> PartialOrd for T
+where
+	T: cmp::PartialOrd + PartialEq,
+{
+	fn dyn_partial_cmp(&self, other: &dyn any::Any) -> Option<cmp::Ordering> {
+		match other.downcast_ref::<Self>() {
+			Some(other) => self.partial_cmp(other),
+			None => Some(any::TypeId::of::<Self>().cmp(&other.type_id())),
+		}
+	}
+}
+
+impl cmp::PartialEq for dyn PartialOrd {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other.as_any())
+	}
+}
+impl cmp::PartialOrd for dyn PartialOrd {
+	fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+		self.dyn_partial_cmp(other.as_any())
+	}
+}
+impl cmp::PartialEq for dyn PartialOrd + Send {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other.as_any())
+	}
+}
+impl cmp::PartialOrd for dyn PartialOrd + Send {
+	fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+		self.dyn_partial_cmp(other.as_any())
+	}
+}
+impl cmp::PartialEq for dyn PartialOrd + Sync {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other.as_any())
+	}
+}
+impl cmp::PartialOrd for dyn PartialOrd + Sync {
+	fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+		self.dyn_partial_cmp(other.as_any())
+	}
+}
+impl cmp::PartialEq for dyn PartialOrd + Send + Sync {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other.as_any())
+	}
+}
+impl cmp::PartialOrd for dyn PartialOrd + Send + Sync {
+	fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+		self.dyn_partial_cmp(other.as_any())
+	}
+}
+impl AsRef<Self> for dyn PartialOrd + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn PartialOrd + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn PartialOrd + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn PartialOrd + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl serde::ser::Serialize for dyn PartialOrd {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn PartialOrd + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn PartialOrd + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn PartialOrd + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn PartialOrd + Send + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn PartialOrd + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn PartialOrd + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn PartialOrd + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn PartialOrd + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn PartialOrd + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn PartialOrd + Send + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn PartialOrd + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::cmp::Ord`].
+///
+/// It can be made into a trait object which is then (de)serializable and totally ordered, for example to be held in a `BinaryHeap`. Values of different concrete types are ordered by their [`TypeId`](std::any::TypeId), which is consistent within a binary.
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::collections::BinaryHeap;
+///
+/// let mut queue: BinaryHeap<s::Box<dyn s::Ord>> = BinaryHeap::new();
+/// queue.push(s::Box::new(2u32));
+/// queue.push(s::Box::new(7u32));
+/// queue.push(s::Box::new(4u32));
+///
+/// let serialized = serde_json::to_string(&queue.into_sorted_vec()).unwrap();
+/// let deserialized: Vec<s::Box<dyn s::Ord>> = serde_json::from_str(&serialized).unwrap();
+///
+/// let mut queue: BinaryHeap<s::Box<dyn s::Ord>> = deserialized.into_iter().collect();
+/// assert!(queue.pop().unwrap() == s::Box::new(7u32) as s::Box<dyn s::Ord>);
+/// ```
+pub trait Ord: Eq + PartialOrd {
+	/// Compare this value with `other`, falling back to comparing type ids if `other` is of a different concrete type.
+	fn dyn_cmp(&self, other: &dyn any::Any) -> cmp::Ordering;
+}
+impl<T> Ord for T
+where
+	T: cmp::Ord + Eq + PartialOrd,
+{
+	fn dyn_cmp(&self, other: &dyn any::Any) -> cmp::Ordering {
+		match other.downcast_ref::<Self>() {
+			Some(other) => self.cmp(other),
+			None => any::TypeId::of::<Self>().cmp(&other.type_id()),
+		}
+	}
+}
+
+impl cmp::PartialEq for dyn Ord {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other.as_any())
+	}
+}
+impl cmp::Eq for dyn Ord {}
+impl cmp::PartialOrd for dyn Ord {
+	fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+impl cmp::Ord for dyn Ord {
+	fn cmp(&self, other: &Self) -> cmp::Ordering {
+		self.dyn_cmp(other.as_any())
+	}
+}
+impl cmp::PartialEq for dyn Ord + Send {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other.as_any())
+	}
+}
+impl cmp::Eq for dyn Ord + Send {}
+impl cmp::PartialOrd for dyn Ord + Send {
+	fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+impl cmp::Ord for dyn Ord + Send {
+	fn cmp(&self, other: &Self) -> cmp::Ordering {
+		self.dyn_cmp(other.as_any())
+	}
+}
+impl cmp::PartialEq for dyn Ord + Sync {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other.as_any())
+	}
+}
+impl cmp::Eq for dyn Ord + Sync {}
+impl cmp::PartialOrd for dyn Ord + Sync {
+	fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+impl cmp::Ord for dyn Ord + Sync {
+	fn cmp(&self, other: &Self) -> cmp::Ordering {
+		self.dyn_cmp(other.as_any())
+	}
+}
+impl cmp::PartialEq for dyn Ord + Send + Sync {
+	fn eq(&self, other: &Self) -> bool {
+		self.dyn_eq(other.as_any())
+	}
+}
+impl cmp::Eq for dyn Ord + Send + Sync {}
+impl cmp::PartialOrd for dyn Ord + Send + Sync {
+	fn partial_cmp(&self, other: &Self) -> Option<cmp::Ordering> {
+		Some(self.cmp(other))
+	}
+}
+impl cmp::Ord for dyn Ord + Send + Sync {
+	fn cmp(&self, other: &Self) -> cmp::Ordering {
+		self.dyn_cmp(other.as_any())
+	}
+}
+impl AsRef<Self> for dyn Ord + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Ord + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Ord + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Ord + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl serde::ser::Serialize for dyn Ord {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Ord + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Ord + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Ord + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Ord + Send + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Ord + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Ord + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Ord + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Ord + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Ord + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Ord + Send + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Ord + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+
+/// A convenience trait implemented on all (de)serializable implementors of [`std::clone::Clone`].
+///
+/// It can be made into a trait object which is then (de)serializable and cloneable. `Box<dyn Clone>` and `Box<dyn Clone + Send>` implement `std::clone::Clone`.
+///
+/// Traits with `Clone` as a supertrait can use [`clone_box`] to implement `std::clone::Clone` on a `std::boxed::Box` of their trait object:
+/// ```
+/// extern crate serde_traitobject as s;
+///
+/// trait MyTrait: s::Clone {}
+///
+/// impl Clone for Box<dyn MyTrait> {
+///     fn clone(&self) -> Self {
+///         s::clone_box(&**self)
+///     }
+/// }
+/// ```
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// let value: s::Box<dyn s::Clone> = s::Box::new(String::from("cloneable"));
+///
+/// let serialized = serde_json::to_string(&value).unwrap();
+/// let deserialized: s::Box<dyn s::Clone> = serde_json::from_str(&serialized).unwrap();
+///
+/// let cloned = deserialized.clone();
+/// assert_eq!(serde_json::to_string(&cloned).unwrap(), serialized);
+/// ```
+pub trait Clone: Serialize + Deserialize + cloneable::Sealed {
+	/// Clone this value into a new `std::boxed::Box`.
+	fn clone_box(&self) -> boxed::Box<dyn Clone>;
+}
+impl<T> Clone for T
+where
+	T: clone::Clone + Serialize + Deserialize + 'static,
+{
+	fn clone_box(&self) -> boxed::Box<dyn Clone> {
+		boxed::Box::new(self.clone())
+	}
+}
+
+mod cloneable {
+	/// Sealed, such that [`clone_raw`](Sealed::clone_raw) is only ever the implementation below, which [`clone_box`](super::clone_box) relies on for soundness.
+	pub trait Sealed {
+		/// Clone this value into a new allocation of the same concrete type, returning a thin pointer to it.
+		fn clone_raw(&self) -> *mut ();
+	}
+	impl<T: Clone + 'static> Sealed for T {
+		fn clone_raw(&self) -> *mut () {
+			Box::into_raw(Box::new(self.clone())).cast()
+		}
+	}
+}
+
+/// Clone a trait object, whose trait has [`Clone`] as a supertrait, into a new `std::boxed::Box`.
+pub fn clone_box<T: Clone + ?Sized>(t: &T) -> boxed::Box<T> {
+	let thin = t.clone_raw();
+	// Safety: `clone_raw` is sealed, and returns an owned allocation of the same concrete type as `t`, so `t`'s metadata applies to it.
+	unsafe { boxed::Box::from_raw(metatype::Type::fatten(thin, metatype::Type::meta(t))) }
+}
+
+impl clone::Clone for boxed::Box<dyn Clone> {
+	fn clone(&self) -> Self {
+		clone_box(&**self)
+	}
+}
+impl clone::Clone for boxed::Box<dyn Clone + Send> {
+	fn clone(&self) -> Self {
+		clone_box(&**self)
+	}
+}
+impl clone::Clone for boxed::Box<dyn Clone + Sync> {
+	fn clone(&self) -> Self {
+		clone_box(&**self)
+	}
+}
+impl clone::Clone for boxed::Box<dyn Clone + Send + Sync> {
+	fn clone(&self) -> Self {
+		clone_box(&**self)
+	}
+}
+impl clone::Clone for Box<dyn Clone> {
+	fn clone(&self) -> Self {
+		Self(clone_box(&*self.0))
+	}
+}
+impl clone::Clone for Box<dyn Clone + Send> {
+	fn clone(&self) -> Self {
+		Self(clone_box(&*self.0))
+	}
+}
+impl clone::Clone for Box<dyn Clone + Sync> {
+	fn clone(&self) -> Self {
+		Self(clone_box(&*self.0))
+	}
+}
+impl clone::Clone for Box<dyn Clone + Send + Sync> {
+	fn clone(&self) -> Self {
+		Self(clone_box(&*self.0))
+	}
+}
+impl AsRef<Self> for dyn Clone + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Clone + Send + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Clone + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+impl AsRef<Self> for dyn Clone + Send + Sync + '_ {
+	fn as_ref(&self) -> &Self {
+		self
+	}
+}
+
+impl serde::ser::Serialize for dyn Clone {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Clone + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Clone + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Clone + Send {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Clone + Send + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Clone + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Clone + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Clone + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Clone + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+impl serde::ser::Serialize for dyn Clone + Send + Sync {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn Clone + Send + Sync + 'static> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<Box<dyn Clone + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+	}
+}
+
+#[cfg(feature = "futures")]
+pub use self::stream::*;
+
+/// Convenience traits for the [`futures`] crate, enabled by the `futures` feature.
+#[cfg(feature = "futures")]
+mod stream {
+	use super::{serialize, Box, Deserialize, Serialize};
+	use std::{alloc::Allocator, boxed, pin::Pin, task};
+
+	/// A convenience trait implemented on all (de)serializable implementors of [`futures::Stream`].
+	///
+	/// It can be made into a trait object which is then (de)serializable. A deserialized [`Box`] of it can be converted to a [`BoxStream`] with [`Box::into_pin`] to be polled.
+	///
+	/// # Example
+	/// ```
+	/// # extern crate serde;
+	/// #[macro_use] extern crate serde_derive;
+	/// extern crate futures;
+	/// extern crate serde_json;
+	/// extern crate serde_traitobject as s;
+	///
+	/// use futures::{executor::block_on, stream::{Stream, StreamExt}};
+	/// use std::{pin::Pin, task::{Context, Poll}};
+	///
+	/// #[derive(Serialize, Deserialize)]
+	/// struct Pages {
+	///     next: u32,
+	///     last: u32,
+	/// }
+	/// impl Stream for Pages {
+	///     type Item = u32;
+	///     fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context) -> Poll<Option<u32>> {
+	///         if self.next > self.last {
+	///             return Poll::Ready(None);
+	///         }
+	///         self.next += 1;
+	///         Poll::Ready(Some(self.next - 1))
+	///     }
+	/// }
+	///
+	/// let pages: s::Box<dyn s::Stream<Item = u32> + Send> = s::Box::new(Pages { next: 1, last: 3 });
+	///
+	/// let serialized = serde_json::to_string(&pages).unwrap();
+	/// let deserialized: s::Box<dyn s::Stream<Item = u32> + Send> =
+	///     serde_json::from_str(&serialized).unwrap();
+	///
+	/// assert_eq!(block_on(deserialized.into_pin().collect::<Vec<_>>()), [1, 2, 3]);
+	/// ```
+	pub trait Stream: futures::Stream + Serialize + Deserialize {}
+	impl<T: ?Sized> Stream for T where T: futures::Stream + Serialize + Deserialize {}
+
+	impl<Item> AsRef<Self> for dyn Stream<Item = Item> + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl<Item> AsRef<Self> for dyn Stream<Item = Item> + Send + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl<Item> AsRef<Self> for dyn Stream<Item = Item> + Sync + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl<Item> AsRef<Self> for dyn Stream<Item = Item> + Send + Sync + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+
+	impl<Item: 'static> serde::ser::Serialize for dyn Stream<Item = Item> {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de, Item: 'static> serde::de::Deserialize<'de>
+		for boxed::Box<dyn Stream<Item = Item> + 'static>
+	{
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn Stream<Item = Item> + 'static>>::deserialize(deserializer).map(|x| x.0)
+		}
+	}
+	impl<Item: 'static> serde::ser::Serialize for dyn Stream<Item = Item> + Send {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de, Item: 'static> serde::de::Deserialize<'de>
+		for boxed::Box<dyn Stream<Item = Item> + Send + 'static>
+	{
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn Stream<Item = Item> + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+		}
+	}
+	impl<Item: 'static> serde::ser::Serialize for dyn Stream<Item = Item> + Sync {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de, Item: 'static> serde::de::Deserialize<'de>
+		for boxed::Box<dyn Stream<Item = Item> + Sync + 'static>
+	{
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn Stream<Item = Item> + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+		}
+	}
+	impl<Item: 'static> serde::ser::Serialize for dyn Stream<Item = Item> + Send + Sync {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de, Item: 'static> serde::de::Deserialize<'de>
+		for boxed::Box<dyn Stream<Item = Item> + Send + Sync + 'static>
+	{
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn Stream<Item = Item> + Send + Sync + 'static>>::deserialize(deserializer)
+				.map(|x| x.0)
+		}
+	}
+
+	/// An owned, pinned and dynamically typed [`Stream`], like `futures::stream::BoxStream`.
+	pub type BoxStream<'a, T> = Pin<boxed::Box<dyn Stream<Item = T> + Send + 'a>>;
+
+	impl<St: futures::Stream + Unpin + ?Sized, A: Allocator> futures::Stream for Box<St, A> {
+		type Item = St::Item;
+		fn poll_next(
+			mut self: Pin<&mut Self>, cx: &mut task::Context<'_>,
+		) -> task::Poll<Option<Self::Item>> {
+			Pin::new(&mut *self.0).poll_next(cx)
+		}
+		fn size_hint(&self) -> (usize, Option<usize>) {
+			self.0.size_hint()
+		}
+	}
+}
+
+#[cfg(feature = "tokio")]
+pub use self::async_io::*;
+
+/// Convenience traits for [`tokio`]'s I/O traits, enabled by the `tokio` feature.
+#[cfg(feature = "tokio")]
+mod async_io {
+	use super::{serialize, Box, Deserialize, Serialize};
+	use std::{alloc::Allocator, boxed, io, pin::Pin, task};
+
+	/// A convenience trait implemented on all (de)serializable implementors of [`tokio::io::AsyncRead`].
+	///
+	/// It can be made into a trait object which is then (de)serializable. Implementors must be [`Unpin`], so that a [`Box`] of the trait object can itself be read from or written to.
+	///
+	/// # Example
+	/// ```
+	/// # extern crate serde;
+	/// #[macro_use] extern crate serde_derive;
+	/// extern crate serde_json;
+	/// extern crate serde_traitobject as s;
+	/// extern crate tokio;
+	///
+	/// use std::{io, pin::Pin, task::{Context, Poll}};
+	/// use tokio::io::{AsyncRead, AsyncReadExt, ReadBuf};
+	///
+	/// #[derive(Serialize, Deserialize)]
+	/// struct Memory {
+	///     data: Vec<u8>,
+	///     pos: usize,
+	/// }
+	/// impl AsyncRead for Memory {
+	///     fn poll_read(
+	///         mut self: Pin<&mut Self>, _cx: &mut Context, buf: &mut ReadBuf,
+	///     ) -> Poll<io::Result<()>> {
+	///         let len = buf.remaining().min(self.data.len() - self.pos);
+	///         buf.put_slice(&self.data[self.pos..self.pos + len]);
+	///         self.pos += len;
+	///         Poll::Ready(Ok(()))
+	///     }
+	/// }
+	///
+	/// let reader: s::Box<dyn s::AsyncRead + Send> = s::Box::new(Memory { data: b"remote".to_vec(), pos: 0 });
+	///
+	/// let serialized = serde_json::to_string(&reader).unwrap();
+	/// let mut deserialized: s::Box<dyn s::AsyncRead + Send> = serde_json::from_str(&serialized).unwrap();
+	///
+	/// let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+	/// let mut contents = String::new();
+	/// runtime.block_on(deserialized.read_to_string(&mut contents)).unwrap();
+	/// assert_eq!(contents, "remote");
+	/// ```
+	pub trait AsyncRead: tokio::io::AsyncRead + Unpin + Serialize + Deserialize {}
+	impl<T: ?Sized> AsyncRead for T where T: tokio::io::AsyncRead + Unpin + Serialize + Deserialize {}
+
+	impl AsRef<Self> for dyn AsyncRead + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl AsRef<Self> for dyn AsyncRead + Send + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl AsRef<Self> for dyn AsyncRead + Sync + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl AsRef<Self> for dyn AsyncRead + Send + Sync + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+
+	impl serde::ser::Serialize for dyn AsyncRead {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn AsyncRead + 'static> {
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn AsyncRead + 'static>>::deserialize(deserializer).map(|x| x.0)
+		}
+	}
+	impl serde::ser::Serialize for dyn AsyncRead + Send {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn AsyncRead + Send + 'static> {
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn AsyncRead + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+		}
+	}
+	impl serde::ser::Serialize for dyn AsyncRead + Sync {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn AsyncRead + Sync + 'static> {
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn AsyncRead + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+		}
+	}
+	impl serde::ser::Serialize for dyn AsyncRead + Send + Sync {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn AsyncRead + Send + Sync + 'static> {
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn AsyncRead + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+		}
+	}
+
+	/// A convenience trait implemented on all (de)serializable implementors of [`tokio::io::AsyncWrite`].
+	///
+	/// It can be made into a trait object which is then (de)serializable. Implementors must be [`Unpin`], so that a [`Box`] of the trait object can itself be read from or written to.
+	pub trait AsyncWrite: tokio::io::AsyncWrite + Unpin + Serialize + Deserialize {}
+	impl<T: ?Sized> AsyncWrite for T where T: tokio::io::AsyncWrite + Unpin + Serialize + Deserialize {}
+
+	impl AsRef<Self> for dyn AsyncWrite + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl AsRef<Self> for dyn AsyncWrite + Send + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl AsRef<Self> for dyn AsyncWrite + Sync + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl AsRef<Self> for dyn AsyncWrite + Send + Sync + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+
+	impl serde::ser::Serialize for dyn AsyncWrite {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn AsyncWrite + 'static> {
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn AsyncWrite + 'static>>::deserialize(deserializer).map(|x| x.0)
+		}
+	}
+	impl serde::ser::Serialize for dyn AsyncWrite + Send {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn AsyncWrite + Send + 'static> {
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn AsyncWrite + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+		}
+	}
+	impl serde::ser::Serialize for dyn AsyncWrite + Sync {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn AsyncWrite + Sync + 'static> {
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn AsyncWrite + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+		}
+	}
+	impl serde::ser::Serialize for dyn AsyncWrite + Send + Sync {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn AsyncWrite + Send + Sync + 'static> {
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn AsyncWrite + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+		}
+	}
+
+	impl<R: tokio::io::AsyncRead + Unpin + ?Sized, A: Allocator> tokio::io::AsyncRead for Box<R, A> {
+		fn poll_read(
+			mut self: Pin<&mut Self>, cx: &mut task::Context<'_>, buf: &mut tokio::io::ReadBuf<'_>,
+		) -> task::Poll<io::Result<()>> {
+			Pin::new(&mut *self.0).poll_read(cx, buf)
+		}
+	}
+	impl<W: tokio::io::AsyncWrite + Unpin + ?Sized, A: Allocator> tokio::io::AsyncWrite for Box<W, A> {
+		fn poll_write(
+			mut self: Pin<&mut Self>, cx: &mut task::Context<'_>, buf: &[u8],
+		) -> task::Poll<io::Result<usize>> {
+			Pin::new(&mut *self.0).poll_write(cx, buf)
+		}
+		fn poll_write_vectored(
+			mut self: Pin<&mut Self>, cx: &mut task::Context<'_>, bufs: &[io::IoSlice<'_>],
+		) -> task::Poll<io::Result<usize>> {
+			Pin::new(&mut *self.0).poll_write_vectored(cx, bufs)
+		}
+		fn is_write_vectored(&self) -> bool {
+			self.0.is_write_vectored()
+		}
+		fn poll_flush(
+			mut self: Pin<&mut Self>, cx: &mut task::Context<'_>,
+		) -> task::Poll<io::Result<()>> {
+			Pin::new(&mut *self.0).poll_flush(cx)
+		}
+		fn poll_shutdown(
+			mut self: Pin<&mut Self>, cx: &mut task::Context<'_>,
+		) -> task::Poll<io::Result<()>> {
+			Pin::new(&mut *self.0).poll_shutdown(cx)
+		}
 	}
 }
 
-/// A convenience trait implemented on all (de)serializable implementors of [`std::ops::Fn`].
-///
-/// It can be made into a trait object which is then (de)serializable.
-pub trait Fn<Args: Tuple>: ops::Fn<Args> + Serialize + Deserialize {}
-impl<T: ?Sized, Args: Tuple> Fn<Args> for T where T: ops::Fn<Args> + Serialize + Deserialize {}
+#[cfg(feature = "rand")]
+pub use self::rng::*;
 
-impl<'a, Args, Output> AsRef<Self> for dyn Fn<Args, Output = Output> + 'a {
-	fn as_ref(&self) -> &Self {
-		self
+/// Convenience traits for [`rand`], enabled by the `rand` feature.
+#[cfg(feature = "rand")]
+mod rng {
+	use super::{serialize, Box, Deserialize, Serialize};
+	use std::{alloc::Allocator, boxed};
+
+	/// A convenience trait implemented on all (de)serializable implementors of [`rand::RngCore`].
+	///
+	/// It can be made into a trait object which is then (de)serializable, including the generator's internal state.
+	///
+	/// # Example
+	/// ```
+	/// # extern crate serde;
+	/// #[macro_use] extern crate serde_derive;
+	/// extern crate rand;
+	/// extern crate serde_json;
+	/// extern crate serde_traitobject as s;
+	///
+	/// use rand::{Rng, RngCore};
+	///
+	/// #[derive(Serialize, Deserialize)]
+	/// struct XorShift(u64);
+	/// impl RngCore for XorShift {
+	///     fn next_u32(&mut self) -> u32 {
+	///         self.next_u64() as u32
+	///     }
+	///     fn next_u64(&mut self) -> u64 {
+	///         self.0 ^= self.0 << 13;
+	///         self.0 ^= self.0 >> 7;
+	///         self.0 ^= self.0 << 17;
+	///         self.0
+	///     }
+	///     fn fill_bytes(&mut self, dest: &mut [u8]) {
+	///         for chunk in dest.chunks_mut(8) {
+	///             let bytes = self.next_u64().to_le_bytes();
+	///             chunk.copy_from_slice(&bytes[..chunk.len()]);
+	///         }
+	///     }
+	///     fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+	///         self.fill_bytes(dest);
+	///         Ok(())
+	///     }
+	/// }
+	///
+	/// let mut rng: s::Box<dyn s::RngCore + Send> = s::Box::new(XorShift(0x2545f4914f6cdd1d));
+	/// let _: u64 = rng.gen();
+	///
+	/// let serialized = serde_json::to_string(&rng).unwrap();
+	/// let mut deserialized: s::Box<dyn s::RngCore + Send> = serde_json::from_str(&serialized).unwrap();
+	///
+	/// assert_eq!(rng.gen::<u64>(), deserialized.gen::<u64>());
+	/// ```
+	pub trait RngCore: rand::RngCore + Serialize + Deserialize {}
+	impl<T: ?Sized> RngCore for T where T: rand::RngCore + Serialize + Deserialize {}
+
+	impl AsRef<Self> for dyn RngCore + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
 	}
-}
-impl<'a, Args, Output> AsRef<Self> for dyn Fn<Args, Output = Output> + Send + 'a {
-	fn as_ref(&self) -> &Self {
-		self
+	impl AsRef<Self> for dyn RngCore + Send + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl AsRef<Self> for dyn RngCore + Sync + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl AsRef<Self> for dyn RngCore + Send + Sync + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
 	}
-}
 
-impl<Args: 'static, Output: 'static> serde::ser::Serialize for dyn Fn<Args, Output = Output> {
-	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer,
-	{
-		serialize(self, serializer)
+	impl serde::ser::Serialize for dyn RngCore {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
 	}
-}
-impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
-	for boxed::Box<dyn Fn<Args, Output = Output> + 'static>
-{
-	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-	where
-		D: serde::Deserializer<'de>,
-	{
-		<Box<dyn Fn<Args, Output = Output> + 'static>>::deserialize(deserializer).map(|x| x.0)
+	impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn RngCore + 'static> {
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn RngCore + 'static>>::deserialize(deserializer).map(|x| x.0)
+		}
 	}
-}
-impl<Args: 'static, Output: 'static> serde::ser::Serialize
-	for dyn Fn<Args, Output = Output> + Send
-{
-	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
-	where
-		S: serde::Serializer,
-	{
-		serialize(self, serializer)
+	impl serde::ser::Serialize for dyn RngCore + Send {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
 	}
-}
-impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
-	for boxed::Box<dyn Fn<Args, Output = Output> + Send + 'static>
-{
-	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
-	where
-		D: serde::Deserializer<'de>,
-	{
-		<Box<dyn Fn<Args, Output = Output> + Send + 'static>>::deserialize(deserializer)
-			.map(|x| x.0)
+	impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn RngCore + Send + 'static> {
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn RngCore + Send + 'static>>::deserialize(deserializer).map(|x| x.0)
+		}
+	}
+	impl serde::ser::Serialize for dyn RngCore + Sync {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn RngCore + Sync + 'static> {
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn RngCore + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+		}
+	}
+	impl serde::ser::Serialize for dyn RngCore + Send + Sync {
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de> serde::de::Deserialize<'de> for boxed::Box<dyn RngCore + Send + Sync + 'static> {
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn RngCore + Send + Sync + 'static>>::deserialize(deserializer).map(|x| x.0)
+		}
+	}
+
+	impl<R: rand::RngCore + ?Sized, A: Allocator> rand::RngCore for Box<R, A> {
+		fn next_u32(&mut self) -> u32 {
+			self.0.next_u32()
+		}
+		fn next_u64(&mut self) -> u64 {
+			self.0.next_u64()
+		}
+		fn fill_bytes(&mut self, dest: &mut [u8]) {
+			self.0.fill_bytes(dest);
+		}
+		fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+			self.0.try_fill_bytes(dest)
+		}
 	}
 }
 
@@ -867,12 +4988,22 @@ pub mod sc {
 	pub trait FnOnce<Args>: sc::FnOnceBox<Args> + Serialize + Deserialize {}
 	impl<T: ?Sized, Args> FnOnce<Args> for T where T: sc::FnOnceBox<Args> + Serialize + Deserialize {}
 
-	impl<'a, Args, Output> AsRef<Self> for dyn FnOnce<Args, Output = Output> + 'a {
+	impl<Args, Output> AsRef<Self> for dyn FnOnce<Args, Output = Output> + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl<Args, Output> AsRef<Self> for dyn FnOnce<Args, Output = Output> + Send + '_ {
 		fn as_ref(&self) -> &Self {
 			self
 		}
 	}
-	impl<'a, Args, Output> AsRef<Self> for dyn FnOnce<Args, Output = Output> + Send + 'a {
+	impl<Args, Output> AsRef<Self> for dyn FnOnce<Args, Output = Output> + Sync + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl<Args, Output> AsRef<Self> for dyn FnOnce<Args, Output = Output> + Send + Sync + '_ {
 		fn as_ref(&self) -> &Self {
 			self
 		}
@@ -918,6 +5049,50 @@ pub mod sc {
 				.map(|x| x.0)
 		}
 	}
+	impl<Args: 'static, Output: 'static> serde::ser::Serialize
+		for dyn FnOnce<Args, Output = Output> + Sync
+	{
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+		for boxed::Box<dyn FnOnce<Args, Output = Output> + Sync + 'static>
+	{
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn FnOnce<Args, Output = Output> + Sync + 'static>>::deserialize(deserializer)
+				.map(|x| x.0)
+		}
+	}
+	impl<Args: 'static, Output: 'static> serde::ser::Serialize
+		for dyn FnOnce<Args, Output = Output> + Send + Sync
+	{
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+		for boxed::Box<dyn FnOnce<Args, Output = Output> + Send + Sync + 'static>
+	{
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn FnOnce<Args, Output = Output> + Send + Sync + 'static>>::deserialize(
+				deserializer,
+			)
+			.map(|x| x.0)
+		}
+	}
 
 	/// A convenience trait implemented on all (de)serializable implementors of [`serde_closure::traits::FnMut`].
 	///
@@ -925,12 +5100,22 @@ pub mod sc {
 	pub trait FnMut<Args>: sc::FnMut<Args> + Serialize + Deserialize {}
 	impl<T: ?Sized, Args> FnMut<Args> for T where T: sc::FnMut<Args> + Serialize + Deserialize {}
 
-	impl<'a, Args, Output> AsRef<Self> for dyn FnMut<Args, Output = Output> + 'a {
+	impl<Args, Output> AsRef<Self> for dyn FnMut<Args, Output = Output> + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl<Args, Output> AsRef<Self> for dyn FnMut<Args, Output = Output> + Send + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl<Args, Output> AsRef<Self> for dyn FnMut<Args, Output = Output> + Sync + '_ {
 		fn as_ref(&self) -> &Self {
 			self
 		}
 	}
-	impl<'a, Args, Output> AsRef<Self> for dyn FnMut<Args, Output = Output> + Send + 'a {
+	impl<Args, Output> AsRef<Self> for dyn FnMut<Args, Output = Output> + Send + Sync + '_ {
 		fn as_ref(&self) -> &Self {
 			self
 		}
@@ -976,6 +5161,50 @@ pub mod sc {
 				.map(|x| x.0)
 		}
 	}
+	impl<Args: 'static, Output: 'static> serde::ser::Serialize
+		for dyn FnMut<Args, Output = Output> + Sync
+	{
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+		for boxed::Box<dyn FnMut<Args, Output = Output> + Sync + 'static>
+	{
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn FnMut<Args, Output = Output> + Sync + 'static>>::deserialize(deserializer)
+				.map(|x| x.0)
+		}
+	}
+	impl<Args: 'static, Output: 'static> serde::ser::Serialize
+		for dyn FnMut<Args, Output = Output> + Send + Sync
+	{
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+		for boxed::Box<dyn FnMut<Args, Output = Output> + Send + Sync + 'static>
+	{
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn FnMut<Args, Output = Output> + Send + Sync + 'static>>::deserialize(
+				deserializer,
+			)
+			.map(|x| x.0)
+		}
+	}
 
 	/// A convenience trait implemented on all (de)serializable implementors of [`serde_closure::traits::Fn`].
 	///
@@ -983,12 +5212,22 @@ pub mod sc {
 	pub trait Fn<Args>: sc::Fn<Args> + Serialize + Deserialize {}
 	impl<T: ?Sized, Args> Fn<Args> for T where T: sc::Fn<Args> + Serialize + Deserialize {}
 
-	impl<'a, Args, Output> AsRef<Self> for dyn Fn<Args, Output = Output> + 'a {
+	impl<Args, Output> AsRef<Self> for dyn Fn<Args, Output = Output> + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl<Args, Output> AsRef<Self> for dyn Fn<Args, Output = Output> + Send + '_ {
 		fn as_ref(&self) -> &Self {
 			self
 		}
 	}
-	impl<'a, Args, Output> AsRef<Self> for dyn Fn<Args, Output = Output> + Send + 'a {
+	impl<Args, Output> AsRef<Self> for dyn Fn<Args, Output = Output> + Sync + '_ {
+		fn as_ref(&self) -> &Self {
+			self
+		}
+	}
+	impl<Args, Output> AsRef<Self> for dyn Fn<Args, Output = Output> + Send + Sync + '_ {
 		fn as_ref(&self) -> &Self {
 			self
 		}
@@ -1033,4 +5272,46 @@ pub mod sc {
 				.map(|x| x.0)
 		}
 	}
+	impl<Args: 'static, Output: 'static> serde::ser::Serialize
+		for dyn Fn<Args, Output = Output> + Sync
+	{
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+		for boxed::Box<dyn Fn<Args, Output = Output> + Sync + 'static>
+	{
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn Fn<Args, Output = Output> + Sync + 'static>>::deserialize(deserializer)
+				.map(|x| x.0)
+		}
+	}
+	impl<Args: 'static, Output: 'static> serde::ser::Serialize
+		for dyn Fn<Args, Output = Output> + Send + Sync
+	{
+		fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+		where
+			S: serde::Serializer,
+		{
+			serialize(self, serializer)
+		}
+	}
+	impl<'de, Args: 'static, Output: 'static> serde::de::Deserialize<'de>
+		for boxed::Box<dyn Fn<Args, Output = Output> + Send + Sync + 'static>
+	{
+		fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			<Box<dyn Fn<Args, Output = Output> + Send + Sync + 'static>>::deserialize(deserializer)
+				.map(|x| x.0)
+		}
+	}
 }
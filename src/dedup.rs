@@ -0,0 +1,143 @@
+//! Deduplication of the validation data of trait objects of the same concrete type.
+//!
+//! By default, every trait object is serialized with its full validation data, even if many within the same document are of the same concrete type. Within a [`Dedup`], only the first occurrence of each is serialized in full, with subsequent occurrences serialized as a small back-reference. Deserialization must likewise be of a [`Dedup`].
+//!
+//! This applies to the fixed-width header that formats that aren't human-readable, like `bincode`, use by default, and that the [`bytes`](crate::bytes) module uses for all formats.
+//!
+//! The session the back-references refer within starts afresh each time a [`Dedup`] is serialized, so formats that traverse the value more than once, like `bincode::serialize`, which measures the value before writing it, produce the same output on each pass.
+//!
+//! # Example
+//! ```
+//! extern crate bincode;
+//! extern crate serde_traitobject as s;
+//!
+//! use s::dedup::Dedup;
+//!
+//! let message: Vec<s::Box<dyn s::Debug>> = (0..100_u32).map(|i| s::Box::new(i) as _).collect();
+//!
+//! let serialized = bincode::serialize(&Dedup(&message)).unwrap();
+//! assert!(serialized.len() < bincode::serialize(&message).unwrap().len() / 2);
+//!
+//! let Dedup(deserialized): Dedup<Vec<s::Box<dyn s::Debug>>> =
+//!     bincode::deserialize(&serialized).unwrap();
+//! assert_eq!(format!("{:?}", deserialized[99]), "99");
+//! assert!(bincode::deserialize::<Vec<s::Box<dyn s::Debug>>>(&serialized).is_err());
+//! ```
+
+use std::{cell::RefCell, collections::HashMap, convert::TryFrom, mem};
+
+use super::Header;
+
+#[derive(Clone, Default)]
+struct Session {
+	/// The index assigned to each header serialized so far.
+	serialized: HashMap<[u8; Header::LEN], u32>,
+	/// Each header deserialized so far, by index.
+	deserialized: Vec<[u8; Header::LEN]>,
+}
+
+thread_local! {
+	static SESSION: RefCell<Option<Session>> = const { RefCell::new(None) };
+}
+
+/// A value that's (de)serialized within its own session, such that the trait objects within it only have their validation data written once per concrete type.
+///
+/// A [`Dedup`] nested within another is (de)serialized within the outermost's session.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Dedup<T>(pub T);
+impl<T: serde::ser::Serialize> serde::ser::Serialize for Dedup<T> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		session(|| self.0.serialize(serializer))
+	}
+}
+impl<'de, T: serde::de::Deserialize<'de>> serde::de::Deserialize<'de> for Dedup<T> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		session(|| T::deserialize(deserializer).map(Dedup))
+	}
+}
+
+/// Run `f` within a session, unless it's already within one, in which case that's used.
+fn session<F, R>(f: F) -> R
+where
+	F: FnOnce() -> R,
+{
+	struct Guard(bool);
+	impl Drop for Guard {
+		fn drop(&mut self) {
+			if self.0 {
+				SESSION.with(|session| *session.borrow_mut() = None);
+			}
+		}
+	}
+	let _guard = Guard(SESSION.with(|session| {
+		let mut session = session.borrow_mut();
+		session.is_none() && {
+			*session = Some(Session::default());
+			true
+		}
+	}));
+	f()
+}
+
+/// Run `f` within a copy of the current session, if any, such that the headers it serializes aren't recorded in the session itself.
+pub(crate) fn scratch<F, R>(f: F) -> R
+where
+	F: FnOnce() -> R,
+{
+	struct Guard(Option<Session>);
+	impl Drop for Guard {
+		fn drop(&mut self) {
+			SESSION.with(|session| *session.borrow_mut() = self.0.take());
+		}
+	}
+	let _guard = Guard(SESSION.with(|session| {
+		let mut session = session.borrow_mut();
+		let copy = session.clone();
+		mem::replace(&mut *session, copy)
+	}));
+	f()
+}
+
+/// The index of an identical header serialized earlier in the session, if any. Otherwise the header is recorded so that later occurrences can refer back to it.
+pub(crate) fn intern(header: &[u8; Header::LEN]) -> Option<u32> {
+	SESSION.with(|session| {
+		let mut session = session.borrow_mut();
+		let session = session.as_mut()?;
+		if let Some(&index) = session.serialized.get(header) {
+			return Some(index);
+		}
+		if let Ok(index) = u32::try_from(session.serialized.len()) {
+			let _ = session.serialized.insert(*header, index);
+		}
+		None
+	})
+}
+
+/// Record a header deserialized in full, mirroring [`intern`].
+pub(crate) fn record(header: &[u8; Header::LEN]) {
+	SESSION.with(|session| {
+		if let Some(session) = session.borrow_mut().as_mut() {
+			if u32::try_from(session.deserialized.len()).is_ok() {
+				session.deserialized.push(*header);
+			}
+		}
+	});
+}
+
+/// The header deserialized earlier in the session with the given index.
+pub(crate) fn resolve(index: u32) -> Option<[u8; Header::LEN]> {
+	SESSION.with(|session| {
+		session
+			.borrow()
+			.as_ref()?
+			.deserialized
+			.get(usize::try_from(index).ok()?)
+			.copied()
+	})
+}
@@ -0,0 +1,71 @@
+//! A limit on how deeply trait objects may be nested within one another when deserialized.
+//!
+//! A trait object's value can itself contain trait objects, like a `Box<dyn Any>` holding a `Box<dyn Any>`, and each level of nesting recurses during deserialization. So that a malicious or buggy peer can't overflow the stack with deeply nested trait objects, deserialization fails once they're nested more than [`max_depth`] deep, [`DEFAULT_MAX_DEPTH`] unless changed with [`set_max_depth`].
+//!
+//! # Example
+//! ```
+//! extern crate bincode;
+//! extern crate serde_traitobject as s;
+//!
+//! let innermost: s::Box<dyn s::Any> = s::Box::new(1_u8);
+//! let inner: s::Box<dyn s::Any> = s::Box::new(innermost);
+//! let nested: s::Box<dyn s::Any> = s::Box::new(inner);
+//! let serialized = bincode::serialize(&nested).unwrap();
+//!
+//! s::depth::set_max_depth(2);
+//! let result: Result<s::Box<dyn s::Any>, _> = bincode::deserialize(&serialized);
+//! assert!(result.unwrap_err().to_string().contains("nested more than 2 deep"));
+//!
+//! s::depth::set_max_depth(3);
+//! let result: Result<s::Box<dyn s::Any>, _> = bincode::deserialize(&serialized);
+//! assert!(result.is_ok());
+//! ```
+
+use std::{
+	cell::Cell, sync::atomic::{AtomicUsize, Ordering}
+};
+
+use super::error::DeserializeError;
+
+/// The limit on how deeply trait objects may be nested, unless changed with [`set_max_depth`].
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+static MAX_DEPTH: AtomicUsize = AtomicUsize::new(DEFAULT_MAX_DEPTH);
+
+thread_local! {
+	static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Set how deeply trait objects may be nested when deserialized, for all threads.
+pub fn set_max_depth(max_depth: usize) {
+	MAX_DEPTH.store(max_depth, Ordering::Relaxed);
+}
+
+/// How deeply trait objects may be nested when deserialized.
+pub fn max_depth() -> usize {
+	MAX_DEPTH.load(Ordering::Relaxed)
+}
+
+/// Run `f`, which deserializes a `T` trait object, one level of nesting deeper, erroring instead if that's deeper than [`max_depth`].
+pub(crate) fn nested<T: ?Sized, E: serde::de::Error, F, R>(f: F) -> Result<R, E>
+where
+	F: FnOnce() -> Result<R, E>,
+{
+	struct Guard;
+	impl Drop for Guard {
+		fn drop(&mut self) {
+			DEPTH.with(|depth| depth.set(depth.get() - 1));
+		}
+	}
+	let max_depth = max_depth();
+	if DEPTH.with(Cell::get) >= max_depth {
+		return Err(DeserializeError::TooDeep {
+			trait_object: std::any::type_name::<T>(),
+			max_depth,
+		}
+		.raise());
+	}
+	DEPTH.with(|depth| depth.set(depth.get() + 1));
+	let _guard = Guard;
+	f()
+}
@@ -0,0 +1,116 @@
+//! Trait objects encrypted with a secret key, for payloads that traverse untrusted intermediaries.
+//!
+//! With the `encryption` feature enabled and a key installed, either globally with [`set_key`] or for the duration of a closure with [`with_key`], [`to_encrypted_bytes`] serializes a trait object like [`to_bytes`](crate::to_bytes), and encrypts the whole envelope, header and payload alike, with XChaCha20-Poly1305 under a random nonce. [`from_encrypted_bytes`] decrypts and authenticates it before deserializing, such that the vtable offset and type information are neither visible nor tamperable in transit.
+//!
+//! # Example
+//! ```
+//! extern crate serde_traitobject as s;
+//! # s::policy::set_policy(s::policy::Policy::Error);
+//!
+//! let key = [7; 32];
+//! let bytes = s::encrypted::with_key(&key, || {
+//!     s::encrypted::to_encrypted_bytes::<dyn s::Debug>(&String::from("secret"))
+//! })
+//! .unwrap();
+//! assert!(!bytes.windows(6).any(|window| window == b"secret"));
+//!
+//! let value: s::Box<dyn s::Debug> =
+//!     s::encrypted::with_key(&key, || s::encrypted::from_encrypted_bytes(&bytes)).unwrap();
+//! assert_eq!(format!("{:?}", value), "\"secret\"");
+//!
+//! let result: Result<s::Box<dyn s::Debug>, _> =
+//!     s::encrypted::with_key(&[8; 32], || s::encrypted::from_encrypted_bytes(&bytes));
+//! assert!(result.is_err());
+//! ```
+
+use chacha20poly1305::{
+	aead::{Aead, AeadCore, KeyInit, OsRng}, XChaCha20Poly1305, XNonce
+};
+use std::{
+	cell::RefCell, sync::{PoisonError, RwLock}
+};
+
+use super::{error::DeserializeError, from_bytes, to_bytes, Box, Deserialize, Serialize};
+
+/// The length of a key.
+pub const KEY_LEN: usize = 32;
+
+/// The length of the nonce that precedes the ciphertext.
+const NONCE_LEN: usize = 24;
+
+static KEY: RwLock<Option<[u8; KEY_LEN]>> = RwLock::new(None);
+
+thread_local! {
+	static SCOPED_KEY: RefCell<Option<[u8; KEY_LEN]>> = const { RefCell::new(None) };
+}
+
+/// Install `key` for all threads, or remove it if `None`, such that trait objects are encrypted with it.
+///
+/// A key installed with [`with_key`] takes precedence.
+pub fn set_key(key: Option<&[u8; KEY_LEN]>) {
+	*KEY.write().unwrap_or_else(PoisonError::into_inner) = key.copied();
+}
+
+/// Run `f` with `key` installed on this thread, such that the trait objects encrypted and decrypted by it use it.
+///
+/// This takes precedence over a key installed with [`set_key`], and any key installed by an enclosing `with_key`.
+pub fn with_key<F, R>(key: &[u8; KEY_LEN], f: F) -> R
+where
+	F: FnOnce() -> R,
+{
+	struct Guard(Option<[u8; KEY_LEN]>);
+	impl Drop for Guard {
+		fn drop(&mut self) {
+			SCOPED_KEY.with(|scoped| *scoped.borrow_mut() = self.0.take());
+		}
+	}
+	let _guard = Guard(SCOPED_KEY.with(|scoped| scoped.borrow_mut().replace(*key)));
+	f()
+}
+
+/// The cipher for the key trait objects are currently encrypted with, if any.
+fn cipher() -> Option<XChaCha20Poly1305> {
+	SCOPED_KEY
+		.with(|scoped| *scoped.borrow())
+		.or_else(|| *KEY.read().unwrap_or_else(PoisonError::into_inner))
+		.map(|key| XChaCha20Poly1305::new(&key.into()))
+}
+
+/// Serialize a trait object to bytes, using `bincode`, and encrypt them with the installed key.
+///
+/// The bytes can be deserialized with [`from_encrypted_bytes`] by the same binary with the same key installed.
+///
+/// # Errors
+///
+/// If no key is installed.
+pub fn to_encrypted_bytes<T: Serialize + ?Sized + 'static>(
+	t: &T,
+) -> Result<Vec<u8>, bincode::Error> {
+	let Some(cipher) = cipher() else {
+		return Err(serde::ser::Error::custom(
+			"no key is installed to encrypt the trait object with",
+		));
+	};
+	let nonce = XChaCha20Poly1305::generate_nonce(&mut OsRng);
+	let ciphertext = cipher.encrypt(&nonce, &*to_bytes(t)?).map_err(|_| {
+		<bincode::Error as serde::ser::Error>::custom("the trait object couldn't be encrypted")
+	})?;
+	Ok([&nonce[..], &ciphertext].concat())
+}
+
+/// Decrypt bytes written by [`to_encrypted_bytes`] with the installed key, and deserialize the trait object from them.
+pub fn from_encrypted_bytes<T: Deserialize + ?Sized + 'static>(
+	bytes: &[u8],
+) -> Result<Box<T>, bincode::Error> {
+	let plaintext = cipher()
+		.zip(bytes.get(..NONCE_LEN))
+		.and_then(|(cipher, nonce)| {
+			cipher
+				.decrypt(XNonce::from_slice(nonce), &bytes[NONCE_LEN..])
+				.ok()
+		});
+	let Some(plaintext) = plaintext else {
+		return Err(DeserializeError::DecryptionFailed.raise());
+	};
+	from_bytes(&plaintext)
+}
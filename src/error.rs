@@ -0,0 +1,1266 @@
+//! Helpers for sending errors between processes.
+//!
+//! Errors whose concrete types are serializable can be sent as a [`Box<dyn Error>`](crate::Error) directly. [`Message`] is a fallback for those that aren't, preserving the `Display` output of the error and of its [`source`](std::error::Error::source) chain.
+//!
+//! With the `anyhow` feature enabled, `anyhow::Error` can be converted into a `Box<dyn Error + Send + Sync>`. The first link of its chain that is itself a `Box<dyn Error + Send + Sync>` is preserved, with a [`Context`] for each link of context above it; if there's none, it falls back to a [`Message`]. The reverse conversion is provided by `anyhow`'s `From<E: std::error::Error>` impl.
+//!
+//! [`Context`] holds context added on top of an error that's itself serializable, such that the error beneath it is preserved.
+//!
+//! [`Panic`] is a serializable representation of the payload of a panic, as caught by [`catch_unwind`](std::panic::catch_unwind).
+//!
+//! [`TracedError`] wraps an error together with the frames of the backtrace captured when it was created.
+//!
+//! [`Chain`] opts in to (de)serializing the [`source`](std::error::Error::source) chain of an error alongside it, preserving each link as a trait object where possible and as a [`Message`] otherwise.
+//!
+//! [`IoError`] is a serializable adapter for [`std::io::Error`], which isn't itself serializable.
+//!
+//! Likewise with the `eyre` feature enabled for `eyre::Report`. The sections added by its handler, like the location, are also captured, separately from its chain, as rendered by eyre's default handler. They're held by the [`Message`] when falling back to one, and otherwise by a `WithSections` wrapping the error preserved, and in either case the `Debug` output is rendered like the report's.
+//!
+//! [`DeserializeError`] distinguishes the ways deserializing a trait object can fail, so that callers can handle them programmatically. [`WithCause`] carries it alongside the format's error.
+
+use std::{
+	any, backtrace::{Backtrace, BacktraceStatus}, cell::RefCell, error, fmt, io, panic, process
+};
+
+use super::{
+	policy::{self, Policy}, validate::ValidationError, Any, Deserialize, Serialize, SerializeRef, FORMAT_VERSION, FORMAT_VERSION_DIAGNOSTICS
+};
+
+/// A serializable error holding the `Display` output of an error and of its `source` chain.
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::error::Error;
+///
+/// let err = std::io::Error::new(std::io::ErrorKind::Other, "not serializable");
+/// let err: s::Box<dyn s::Error> = s::Box::new(s::error::Message::from_error(&err));
+///
+/// let serialized = serde_json::to_string(&err).unwrap();
+/// let deserialized: s::Box<dyn s::Error> = serde_json::from_str(&serialized).unwrap();
+///
+/// assert_eq!(deserialized.to_string(), "not serializable");
+/// ```
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Message {
+	display: String,
+	source: Option<Box<Message>>,
+	sections: Vec<String>,
+}
+impl Message {
+	/// Create a new `Message` with no source.
+	pub fn new(message: impl Into<String>) -> Self {
+		Self {
+			display: message.into(),
+			source: None,
+			sections: Vec::new(),
+		}
+	}
+	/// Capture the `Display` output of `err` and of each error in its `source` chain.
+	pub fn from_error(err: &(dyn error::Error + '_)) -> Self {
+		Self {
+			display: err.to_string(),
+			source: err
+				.source()
+				.map(|source| Box::new(Self::from_error(source))),
+			sections: Vec::new(),
+		}
+	}
+	/// The captured `Display` output.
+	pub fn message(&self) -> &str {
+		&self.display
+	}
+	/// The sections added by the handler of the report this was converted from, like its location, as rendered in its `Debug` output.
+	pub fn sections(&self) -> &[String] {
+		&self.sections
+	}
+}
+impl fmt::Debug for Message {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		// Render like the report this was converted from, if it has any sections.
+		if !self.sections.is_empty() {
+			return debug_report(self, &self.sections, f);
+		}
+		f.debug_struct("Message")
+			.field("display", &self.display)
+			.field("source", &self.source)
+			.finish()
+	}
+}
+impl fmt::Display for Message {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(&self.display)
+	}
+}
+impl error::Error for Message {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		self.source
+			.as_deref()
+			.map(|source| -> &(dyn error::Error + 'static) { source })
+	}
+}
+impl serde::ser::Error for Message {
+	fn custom<T: fmt::Display>(msg: T) -> Self {
+		Self::new(msg.to_string())
+	}
+}
+impl serde::ser::Serialize for Message {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serde::ser::Serialize::serialize(&(&self.display, &self.source, &self.sections), serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for Message {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<(String, Option<Box<Self>>, Vec<String>) as serde::de::Deserialize>::deserialize(
+			deserializer,
+		)
+		.map(|(display, source, sections)| Self {
+			display,
+			source,
+			sections,
+		})
+	}
+}
+
+/// A serializable error holding the `Display` output of context added on top of an error that's itself serializable, as produced by the `anyhow` and `eyre` conversions.
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::error::Error;
+///
+/// let source: s::Box<dyn s::Error + Send + Sync> = s::Box::new(s::error::Message::new("connection reset"));
+/// let err: s::Box<dyn s::Error> = s::Box::new(s::error::Context::new("request failed", source));
+///
+/// let serialized = serde_json::to_string(&err).unwrap();
+/// let deserialized: s::Box<dyn s::Error> = serde_json::from_str(&serialized).unwrap();
+///
+/// assert_eq!(deserialized.to_string(), "request failed");
+/// assert_eq!(deserialized.source().unwrap().to_string(), "connection reset");
+/// ```
+pub struct Context {
+	display: String,
+	source: super::Box<dyn super::Error + Send + Sync>,
+}
+impl Context {
+	/// Create a new `Context` with `source` beneath it.
+	pub fn new(
+		message: impl Into<String>, source: super::Box<dyn super::Error + Send + Sync>,
+	) -> Self {
+		Self {
+			display: message.into(),
+			source,
+		}
+	}
+	/// The captured `Display` output.
+	pub fn message(&self) -> &str {
+		&self.display
+	}
+	/// Unwrap into the error beneath it.
+	pub fn into_source(self) -> super::Box<dyn super::Error + Send + Sync> {
+		self.source
+	}
+}
+impl fmt::Debug for Context {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Context")
+			.field("display", &self.display)
+			.field("source", &self.source)
+			.finish()
+	}
+}
+impl fmt::Display for Context {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(&self.display)
+	}
+}
+impl error::Error for Context {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		Some(&self.source)
+	}
+}
+impl serde::ser::Serialize for Context {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serde::ser::Serialize::serialize(&(&self.display, &self.source), serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for Context {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		type Source = super::Box<dyn super::Error + Send + Sync>;
+		<(String, Source) as serde::de::Deserialize>::deserialize(deserializer)
+			.map(|(display, source)| Self { display, source })
+	}
+}
+
+/// A serializable error adding the sections of the handler of an `eyre::Report` to the error beneath it, as produced by the `eyre` conversion when the report's error is itself serializable.
+///
+/// Its `Display` output and [`source`](std::error::Error::source) are those of the error beneath it, and its `Debug` output is rendered like the report's.
+///
+/// # Example
+/// ```
+/// extern crate eyre;
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// let source: s::Box<dyn s::Error + Send + Sync> = s::Box::new(s::error::Message::new("connection reset"));
+/// let report = eyre::Report::new(source).wrap_err("request failed");
+/// let err: s::Box<dyn s::Error + Send + Sync> = report.into();
+///
+/// let serialized = serde_json::to_string(&err).unwrap();
+/// let deserialized: s::Box<dyn s::Error + Send + Sync> = serde_json::from_str(&serialized).unwrap();
+///
+/// assert_eq!(deserialized.to_string(), "request failed");
+/// assert!(format!("{:?}", deserialized)
+///     .starts_with("request failed\n\nCaused by:\n    0: connection reset\n\nLocation:\n"));
+/// ```
+#[cfg(feature = "eyre")]
+pub struct WithSections {
+	error: super::Box<dyn super::Error + Send + Sync>,
+	sections: Vec<String>,
+}
+#[cfg(feature = "eyre")]
+impl WithSections {
+	/// The error beneath the sections.
+	pub fn error(&self) -> &(dyn super::Error + Send + Sync + 'static) {
+		&**self.error
+	}
+	/// The sections added by the handler of the report this was converted from, like its location, as rendered in its `Debug` output.
+	pub fn sections(&self) -> &[String] {
+		&self.sections
+	}
+	/// Unwrap into the error beneath the sections.
+	pub fn into_inner(self) -> super::Box<dyn super::Error + Send + Sync> {
+		self.error
+	}
+}
+#[cfg(feature = "eyre")]
+impl fmt::Debug for WithSections {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		debug_report(self, &self.sections, f)
+	}
+}
+#[cfg(feature = "eyre")]
+impl fmt::Display for WithSections {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(&**self.error, f)
+	}
+}
+#[cfg(feature = "eyre")]
+impl error::Error for WithSections {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		self.error.source()
+	}
+}
+#[cfg(feature = "eyre")]
+impl serde::ser::Serialize for WithSections {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serde::ser::Serialize::serialize(&(&self.error, &self.sections), serializer)
+	}
+}
+#[cfg(feature = "eyre")]
+impl<'de> serde::de::Deserialize<'de> for WithSections {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		type Error = super::Box<dyn super::Error + Send + Sync>;
+		<(Error, Vec<String>) as serde::de::Deserialize>::deserialize(deserializer)
+			.map(|(error, sections)| Self { error, sections })
+	}
+}
+
+/// Render `error` and its `source` chain followed by `sections`, like the `Debug` output of an `eyre::Report`.
+fn debug_report(
+	error: &dyn error::Error, sections: &[String], f: &mut fmt::Formatter,
+) -> fmt::Result {
+	write!(f, "{error}")?;
+	if error.source().is_some() {
+		f.write_str("\n\nCaused by:")?;
+	}
+	let chain = std::iter::successors(error.source(), |link| link.source());
+	for (i, link) in chain.enumerate() {
+		write!(f, "\n{i:>5}: {link}")?;
+	}
+	for section in sections {
+		write!(f, "\n\n{section}")?;
+	}
+	Ok(())
+}
+
+/// A serializable panic payload, for forwarding panics caught with [`catch_unwind`](std::panic::catch_unwind) to another process.
+///
+/// `String` and `&'static str` payloads, which are what `panic!` produces, are preserved as a `String`. Payloads of type `s::Box<dyn s::Any + Send>`, as can be raised with [`panic_any`](std::panic::panic_any), are preserved as trait objects. Other payloads are unknown and are represented only by their absence.
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::panic::{self, AssertUnwindSafe};
+///
+/// let payload = panic::catch_unwind(|| panic!("worker {} failed", 3)).unwrap_err();
+/// let payload = s::error::Panic::from_payload(payload);
+///
+/// let serialized = serde_json::to_string(&payload).unwrap();
+/// let deserialized: s::error::Panic = serde_json::from_str(&serialized).unwrap();
+///
+/// assert_eq!(deserialized.message(), Some("worker 3 failed"));
+/// let resumed = panic::catch_unwind(AssertUnwindSafe(|| deserialized.resume_unwind())).unwrap_err();
+/// assert_eq!(resumed.downcast_ref::<String>().unwrap(), "worker 3 failed");
+/// ```
+pub struct Panic {
+	message: Option<String>,
+	payload: Option<super::Box<dyn Any + Send>>,
+}
+impl Panic {
+	/// Convert the payload of a panic, as returned by [`catch_unwind`](std::panic::catch_unwind).
+	pub fn from_payload(payload: Box<dyn any::Any + Send>) -> Self {
+		let payload = match payload.downcast::<String>() {
+			Ok(message) => return Self::from_message(*message),
+			Err(payload) => payload,
+		};
+		let payload = match payload.downcast::<&'static str>() {
+			Ok(message) => return Self::from_message(String::from(*message)),
+			Err(payload) => payload,
+		};
+		Self {
+			message: None,
+			payload: payload
+				.downcast::<super::Box<dyn Any + Send>>()
+				.ok()
+				.map(|payload| *payload),
+		}
+	}
+	fn from_message(message: String) -> Self {
+		Self {
+			message: Some(message),
+			payload: None,
+		}
+	}
+	/// The panic message, if the payload was a `String` or `&'static str`.
+	pub fn message(&self) -> Option<&str> {
+		self.message.as_deref()
+	}
+	/// The payload, if it was a `s::Box<dyn s::Any + Send>`.
+	pub fn payload(&self) -> Option<&super::Box<dyn Any + Send>> {
+		self.payload.as_ref()
+	}
+	/// Convert back into a panic payload, suitable for [`resume_unwind`](std::panic::resume_unwind).
+	///
+	/// Messages become a `String`, and trait object payloads a `s::Box<dyn s::Any + Send>`. Unknown payloads become the `&'static str` `"Box<dyn Any>"`, mirroring how std reports them.
+	pub fn into_payload(self) -> Box<dyn any::Any + Send> {
+		match (self.message, self.payload) {
+			(Some(message), _) => Box::new(message),
+			(None, Some(payload)) => Box::new(payload),
+			(None, None) => Box::new("Box<dyn Any>"),
+		}
+	}
+	/// Resume unwinding with the converted payload, as if by [`resume_unwind`](std::panic::resume_unwind).
+	pub fn resume_unwind(self) -> ! {
+		panic::resume_unwind(self.into_payload())
+	}
+}
+impl fmt::Debug for Panic {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Panic")
+			.field("message", &self.message)
+			.field("has_payload", &self.payload.is_some())
+			.finish()
+	}
+}
+impl fmt::Display for Panic {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(self.message.as_deref().unwrap_or("Box<dyn Any>"))
+	}
+}
+impl error::Error for Panic {}
+impl serde::ser::Serialize for Panic {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serde::ser::Serialize::serialize(&(&self.message, &self.payload), serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for Panic {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<(Option<String>, Option<super::Box<dyn Any + Send>>) as serde::de::Deserialize>::deserialize(
+			deserializer,
+		)
+		.map(|(message, payload)| Self { message, payload })
+	}
+}
+
+/// A serializable error carrying the backtrace captured at its construction.
+///
+/// The backtrace is captured with [`Backtrace::capture`], so is only captured if enabled with the `RUST_BACKTRACE` or `RUST_LIB_BACKTRACE` environment variables. Its frames are (de)serialized as strings alongside the inner error, so that on the deserializing side they describe where the error was created rather than where it was deserialized.
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// std::env::set_var("RUST_LIB_BACKTRACE", "1");
+///
+/// let err = s::error::TracedError::from_box(
+///     s::Box::new(s::error::Message::new("disk full")) as s::Box<dyn s::Error>,
+/// );
+///
+/// let serialized = serde_json::to_string(&err).unwrap();
+/// let deserialized: s::error::TracedError = serde_json::from_str(&serialized).unwrap();
+///
+/// assert_eq!(deserialized.to_string(), "disk full");
+/// assert_eq!(deserialized.frames(), err.frames());
+/// # assert!(!err.frames().is_empty());
+/// ```
+pub struct TracedError<E: ?Sized = dyn super::Error> {
+	frames: Vec<String>,
+	error: super::Box<E>,
+}
+impl<E> TracedError<E> {
+	/// Wrap `error`, capturing a backtrace.
+	pub fn new(error: E) -> Self {
+		Self::from_box(super::Box::new(error))
+	}
+}
+impl<E: ?Sized> TracedError<E> {
+	/// Wrap a boxed `error`, capturing a backtrace.
+	pub fn from_box(error: super::Box<E>) -> Self {
+		Self {
+			frames: frames(&Backtrace::capture()),
+			error,
+		}
+	}
+	/// The frames of the backtrace, each rendered as a string. This is empty if the backtrace wasn't captured.
+	pub fn frames(&self) -> &[String] {
+		&self.frames
+	}
+	/// The wrapped error.
+	pub fn error(&self) -> &E {
+		&self.error
+	}
+	/// Unwrap into the wrapped error, discarding the backtrace.
+	pub fn into_inner(self) -> super::Box<E> {
+		self.error
+	}
+}
+impl<E: fmt::Debug + ?Sized> fmt::Debug for TracedError<E> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Debug::fmt(&*self.error, f)?;
+		if !self.frames.is_empty() {
+			f.write_str("\n\nStack backtrace:")?;
+			for (i, frame) in self.frames.iter().enumerate() {
+				write!(f, "\n{:>4}: {}", i, frame.replace('\n', "\n             "))?;
+			}
+		}
+		Ok(())
+	}
+}
+impl<E: fmt::Display + ?Sized> fmt::Display for TracedError<E> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(&*self.error, f)
+	}
+}
+impl<E: error::Error + ?Sized> error::Error for TracedError<E> {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		self.error.source()
+	}
+}
+impl<E: Serialize + ?Sized + 'static> serde::ser::Serialize for TracedError<E> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serde::ser::Serialize::serialize(&(&self.frames, &self.error), serializer)
+	}
+}
+impl<'de, E: Deserialize + ?Sized + 'static> serde::de::Deserialize<'de> for TracedError<E> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<(Vec<String>, super::Box<E>) as serde::de::Deserialize>::deserialize(deserializer)
+			.map(|(frames, error)| Self { frames, error })
+	}
+}
+
+/// Split the `Display` output of a captured backtrace into its frames, stripping their indices.
+fn frames(backtrace: &Backtrace) -> Vec<String> {
+	if backtrace.status() != BacktraceStatus::Captured {
+		return Vec::new();
+	}
+	let mut frames: Vec<String> = Vec::new();
+	for line in backtrace.to_string().lines() {
+		let line = line.trim_start();
+		match line.split_once(": ") {
+			Some((index, frame))
+				if !index.is_empty() && index.bytes().all(|b| b.is_ascii_digit()) =>
+			{
+				frames.push(frame.to_owned());
+			}
+			_ => match frames.last_mut() {
+				Some(frame) => {
+					frame.push('\n');
+					frame.push_str(line);
+				}
+				None => frames.push(line.to_owned()),
+			},
+		}
+	}
+	frames
+}
+
+/// A serializable adapter for [`std::io::Error`].
+///
+/// It preserves the [`ErrorKind`](std::io::ErrorKind), the raw OS error code, and the message and `source` chain of any custom inner error, and converts back into an equivalent `io::Error`.
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::io;
+///
+/// let err = io::Error::new(io::ErrorKind::TimedOut, "peer didn't respond");
+///
+/// let serialized = serde_json::to_string(&s::error::IoError::from(err)).unwrap();
+/// let deserialized: s::error::IoError = serde_json::from_str(&serialized).unwrap();
+///
+/// let err = io::Error::from(deserialized);
+/// assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+/// assert_eq!(err.to_string(), "peer didn't respond");
+///
+/// let err = io::Error::from_raw_os_error(2);
+/// let serialized = serde_json::to_string(&s::error::IoError::from(err)).unwrap();
+/// let deserialized: s::error::IoError = serde_json::from_str(&serialized).unwrap();
+/// assert_eq!(io::Error::from(deserialized).raw_os_error(), Some(2));
+/// ```
+pub struct IoError {
+	kind: io::ErrorKind,
+	raw_os_error: Option<i32>,
+	custom: Option<Message>,
+}
+impl IoError {
+	/// The [`ErrorKind`](std::io::ErrorKind) of the error. Kinds unknown to the deserializing side become [`ErrorKind::Other`](std::io::ErrorKind::Other).
+	pub fn kind(&self) -> io::ErrorKind {
+		self.kind
+	}
+	/// The OS error code, if the error was created from one.
+	pub fn raw_os_error(&self) -> Option<i32> {
+		self.raw_os_error
+	}
+	/// Convert into an equivalent `io::Error`.
+	pub fn into_io_error(self) -> io::Error {
+		match (self.raw_os_error, self.custom) {
+			(Some(code), _) => io::Error::from_raw_os_error(code),
+			(None, Some(custom)) => io::Error::new(self.kind, custom),
+			(None, None) => io::Error::from(self.kind),
+		}
+	}
+}
+impl From<io::Error> for IoError {
+	fn from(err: io::Error) -> Self {
+		Self {
+			kind: err.kind(),
+			raw_os_error: err.raw_os_error(),
+			custom: err.get_ref().map(|custom| Message::from_error(custom)),
+		}
+	}
+}
+impl From<IoError> for io::Error {
+	fn from(err: IoError) -> Self {
+		err.into_io_error()
+	}
+}
+impl fmt::Debug for IoError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("IoError")
+			.field("kind", &self.kind)
+			.field("raw_os_error", &self.raw_os_error)
+			.field("custom", &self.custom)
+			.finish()
+	}
+}
+impl fmt::Display for IoError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match (self.raw_os_error, &self.custom) {
+			(Some(code), _) => fmt::Display::fmt(&io::Error::from_raw_os_error(code), f),
+			(None, Some(custom)) => fmt::Display::fmt(custom, f),
+			(None, None) => fmt::Display::fmt(&io::Error::from(self.kind), f),
+		}
+	}
+}
+impl error::Error for IoError {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		self.custom.as_ref().and_then(error::Error::source)
+	}
+}
+impl serde::ser::Serialize for IoError {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		let kind = ERROR_KINDS
+			.iter()
+			.find(|&&(kind, _)| kind == self.kind)
+			.map_or("Other", |&(_, name)| name);
+		serde::ser::Serialize::serialize(&(kind, self.raw_os_error, &self.custom), serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for IoError {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<(String, Option<i32>, Option<Message>) as serde::de::Deserialize>::deserialize(
+			deserializer,
+		)
+		.map(|(kind, raw_os_error, custom)| Self {
+			kind: ERROR_KINDS
+				.iter()
+				.find(|&&(_, name)| name == kind)
+				.map_or(io::ErrorKind::Other, |&(kind, _)| kind),
+			raw_os_error,
+			custom,
+		})
+	}
+}
+
+/// The [`ErrorKind`](std::io::ErrorKind)s preserved by [`IoError`], by name so that the encoding doesn't depend on their discriminants.
+const ERROR_KINDS: &[(io::ErrorKind, &str)] = &[
+	(io::ErrorKind::NotFound, "NotFound"),
+	(io::ErrorKind::PermissionDenied, "PermissionDenied"),
+	(io::ErrorKind::ConnectionRefused, "ConnectionRefused"),
+	(io::ErrorKind::ConnectionReset, "ConnectionReset"),
+	(io::ErrorKind::HostUnreachable, "HostUnreachable"),
+	(io::ErrorKind::NetworkUnreachable, "NetworkUnreachable"),
+	(io::ErrorKind::ConnectionAborted, "ConnectionAborted"),
+	(io::ErrorKind::NotConnected, "NotConnected"),
+	(io::ErrorKind::AddrInUse, "AddrInUse"),
+	(io::ErrorKind::AddrNotAvailable, "AddrNotAvailable"),
+	(io::ErrorKind::NetworkDown, "NetworkDown"),
+	(io::ErrorKind::BrokenPipe, "BrokenPipe"),
+	(io::ErrorKind::AlreadyExists, "AlreadyExists"),
+	(io::ErrorKind::WouldBlock, "WouldBlock"),
+	(io::ErrorKind::NotADirectory, "NotADirectory"),
+	(io::ErrorKind::IsADirectory, "IsADirectory"),
+	(io::ErrorKind::DirectoryNotEmpty, "DirectoryNotEmpty"),
+	(io::ErrorKind::ReadOnlyFilesystem, "ReadOnlyFilesystem"),
+	(
+		io::ErrorKind::StaleNetworkFileHandle,
+		"StaleNetworkFileHandle",
+	),
+	(io::ErrorKind::InvalidInput, "InvalidInput"),
+	(io::ErrorKind::InvalidData, "InvalidData"),
+	(io::ErrorKind::TimedOut, "TimedOut"),
+	(io::ErrorKind::WriteZero, "WriteZero"),
+	(io::ErrorKind::StorageFull, "StorageFull"),
+	(io::ErrorKind::NotSeekable, "NotSeekable"),
+	(io::ErrorKind::QuotaExceeded, "QuotaExceeded"),
+	(io::ErrorKind::FileTooLarge, "FileTooLarge"),
+	(io::ErrorKind::ResourceBusy, "ResourceBusy"),
+	(io::ErrorKind::ExecutableFileBusy, "ExecutableFileBusy"),
+	(io::ErrorKind::Deadlock, "Deadlock"),
+	(io::ErrorKind::CrossesDevices, "CrossesDevices"),
+	(io::ErrorKind::TooManyLinks, "TooManyLinks"),
+	(io::ErrorKind::InvalidFilename, "InvalidFilename"),
+	(io::ErrorKind::ArgumentListTooLong, "ArgumentListTooLong"),
+	(io::ErrorKind::Interrupted, "Interrupted"),
+	(io::ErrorKind::Unsupported, "Unsupported"),
+	(io::ErrorKind::UnexpectedEof, "UnexpectedEof"),
+	(io::ErrorKind::OutOfMemory, "OutOfMemory"),
+	(io::ErrorKind::Other, "Other"),
+];
+
+/// An error with its `source` chain (de)serialized alongside it.
+///
+/// Ordinarily only the error itself is (de)serialized, so its `source` chain is lost unless its concrete type serializes its source itself. `Chain` instead walks the `source` chain on serialization. Links that are themselves `s::Box<dyn s::Error>` (or `+ Send` or `+ Send + Sync`) are serialized as trait objects; others are serialized as a [`Message`] holding their `Display` output. On deserialization the chain is reconstructed such that [`source`](std::error::Error::source) yields each link in turn.
+///
+/// # Example
+/// ```
+/// # extern crate serde;
+/// #[macro_use] extern crate serde_derive;
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::{error::Error, fmt};
+///
+/// #[derive(Serialize, Deserialize, Debug)]
+/// struct RequestFailed {
+///     #[serde(skip)]
+///     cause: Option<s::Box<dyn s::Error>>,
+/// }
+/// impl fmt::Display for RequestFailed {
+///     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+///         f.write_str("request failed")
+///     }
+/// }
+/// impl Error for RequestFailed {
+///     fn source(&self) -> Option<&(dyn Error + 'static)> {
+///         self.cause.as_ref().map(|cause| cause as _)
+///     }
+/// }
+///
+/// let cause: s::Box<dyn s::Error> = s::Box::new(s::error::Message::new("connection reset"));
+/// let err = s::error::Chain::new(s::Box::new(RequestFailed { cause: Some(cause) }));
+///
+/// let serialized = serde_json::to_string(&err).unwrap();
+/// let deserialized: s::error::Chain = serde_json::from_str(&serialized).unwrap();
+///
+/// assert_eq!(deserialized.to_string(), "request failed");
+/// assert_eq!(deserialized.source().unwrap().to_string(), "connection reset");
+/// ```
+pub struct Chain {
+	error: super::Box<dyn super::Error>,
+	source: Option<Box<Chain>>,
+}
+impl Chain {
+	/// Wrap `error` such that its `source` chain is (de)serialized alongside it.
+	pub fn new(error: super::Box<dyn super::Error>) -> Self {
+		Self {
+			error,
+			source: None,
+		}
+	}
+	/// The wrapped error. After deserialization, its own `source` may differ from that of the `Chain`.
+	pub fn error(&self) -> &(dyn super::Error + 'static) {
+		&**self.error
+	}
+	/// Unwrap into the wrapped error.
+	pub fn into_inner(self) -> super::Box<dyn super::Error> {
+		self.error
+	}
+}
+impl From<super::Box<dyn super::Error>> for Chain {
+	fn from(error: super::Box<dyn super::Error>) -> Self {
+		Self::new(error)
+	}
+}
+impl fmt::Debug for Chain {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Chain")
+			.field("error", &self.error)
+			.field("source", &error::Error::source(self))
+			.finish()
+	}
+}
+impl fmt::Display for Chain {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(&**self.error, f)
+	}
+}
+impl error::Error for Chain {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		match &self.source {
+			Some(source) => Some(&**source),
+			None => self.error.source(),
+		}
+	}
+}
+impl serde::ser::Serialize for Chain {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		let mut sources = Vec::new();
+		let mut source = error::Error::source(self);
+		while let Some(err) = source {
+			let link: Option<&dyn super::Error> = if let Some(chain) = err.downcast_ref::<Self>() {
+				Some(&**chain.error)
+			} else if let Some(err) = err.downcast_ref::<super::Box<dyn super::Error>>() {
+				Some(&***err)
+			} else if let Some(err) = err.downcast_ref::<super::Box<dyn super::Error + Send>>() {
+				Some(&***err)
+			} else if let Some(err) =
+				err.downcast_ref::<super::Box<dyn super::Error + Send + Sync>>()
+			{
+				Some(&***err)
+			} else {
+				None
+			};
+			sources.push(match link {
+				Some(link) => (Some(SerializeRef(link)), None),
+				None => (None, Some(Message::new(err.to_string()))),
+			});
+			source = err.source();
+		}
+		serde::ser::Serialize::serialize(&(SerializeRef(&**self.error), sources), serializer)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for Chain {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		type Link = (Option<super::Box<dyn super::Error>>, Option<Message>);
+		let (error, sources) =
+			<(super::Box<dyn super::Error>, Vec<Link>) as serde::de::Deserialize>::deserialize(
+				deserializer,
+			)?;
+		let mut source = None;
+		for link in sources.into_iter().rev() {
+			let error = match link {
+				(Some(error), _) => error,
+				(None, Some(message)) => super::Box::new(message),
+				(None, None) => return Err(serde::de::Error::custom("empty error chain link")),
+			};
+			source = Some(Box::new(Self { error, source }));
+		}
+		Ok(Self { error, source })
+	}
+}
+
+/// The ways deserializing a trait object can fail, beyond the format or the value itself being malformed.
+///
+/// Formats only preserve the message of errors raised while deserializing, if that, so deserializing within [`DeserializeError::capture`] returns the format's error wrapped in a [`WithCause`] that carries the error it was raised with. [`DeserializeError::of`] finds it in the [`source`](std::error::Error::source) chain of an error, for example once it's been converted into a `Box<dyn std::error::Error>`. Where a trait object nested within another fails, it's the nested one's error that's carried, as the cause.
+///
+/// The error is also recorded on the thread deserializing, which [`DeserializeError::last`] retrieves. The record is cleared as each trait object starts deserializing, and when it succeeds, and errors raised on other threads aren't visible.
+///
+/// It's named `DeserializeError` rather than `Error`, as [`Error`](crate::Error) at the crate root is already the (de)serializable `std::error::Error` trait object.
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+/// # s::policy::set_policy(s::policy::Policy::Error);
+///
+/// let value: s::Box<dyn s::Debug> = s::Box::new(1_u8);
+/// let serialized = serde_json::to_string(&value).unwrap();
+///
+/// let result = s::error::DeserializeError::capture(|| {
+///     serde_json::from_str::<s::Box<dyn s::Any>>(&serialized)
+/// });
+/// let error = result.err().unwrap();
+/// assert!(matches!(
+///     error.cause(),
+///     Some(s::error::DeserializeError::TraitTypeIdMismatch { .. })
+/// ));
+///
+/// let error: Box<dyn std::error::Error> = Box::new(error);
+/// assert!(matches!(
+///     s::error::DeserializeError::of(&*error),
+///     Some(s::error::DeserializeError::TraitTypeIdMismatch { .. })
+/// ));
+/// ```
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum DeserializeError {
+	/// The format version isn't one this version of the crate can read.
+	UnsupportedVersion {
+		/// The format version read.
+		version: u8,
+	},
+	/// The trait object came from a target with a different architecture or byte order.
+	ArchitectureMismatch {
+		/// The name of the trait object type.
+		trait_object: &'static str,
+		/// The architecture or byte order of the target the trait object came from.
+		theirs: &'static str,
+		/// The architecture or byte order of this target.
+		ours: &'static str,
+	},
+	/// The trait object came from a target with a different pointer width.
+	PointerWidthMismatch {
+		/// The name of the trait object type.
+		trait_object: &'static str,
+		/// The pointer width in bits of the target the trait object came from.
+		theirs: u8,
+		/// The pointer width in bits of this target.
+		ours: u8,
+	},
+	/// The trait object came from a different binary.
+	BuildIdMismatch {
+		/// The name of the trait object type.
+		trait_object: &'static str,
+	},
+	/// The trait object was serialized as a different trait object type.
+	TraitTypeIdMismatch {
+		/// The name of the trait object type.
+		trait_object: &'static str,
+	},
+	/// The concrete type of the trait object doesn't match the one it was serialized as.
+	ConcreteTypeIdMismatch {
+		/// The name of the trait object type.
+		trait_object: &'static str,
+	},
+	/// The header of the trait object failed its integrity check.
+	Corrupt {
+		/// The name of the trait object type.
+		trait_object: &'static str,
+	},
+	/// The vtable offset lies outside this binary.
+	InvalidOffset {
+		/// The name of the trait object type.
+		trait_object: &'static str,
+		/// The vtable offset read.
+		offset: u64,
+	},
+	/// The vtable doesn't match the fingerprint it was serialized with.
+	VtableMismatch {
+		/// The name of the trait object type.
+		trait_object: &'static str,
+	},
+	/// The trait object came from a build with the same [schema version](crate::compat), but its concrete type hasn't been [registered](crate::tagged::register) for the trait object type.
+	Unregistered {
+		/// The name of the trait object type.
+		trait_object: &'static str,
+		/// The type id of the concrete type.
+		type_id: u64,
+	},
+	/// The trait object is nested within others more deeply than the [limit](crate::depth::max_depth).
+	TooDeep {
+		/// The name of the trait object type.
+		trait_object: &'static str,
+		/// The limit.
+		max_depth: usize,
+	},
+	/// A key is installed, but the trait object isn't authenticated.
+	Unauthenticated,
+	/// The trait object failed authentication with the installed key.
+	AuthenticationFailed,
+	/// The Ed25519 signature of the trait object is missing or doesn't match the verifying key.
+	BadSignature,
+	/// No key is installed to decrypt the trait object with, or it failed to decrypt with the installed key.
+	DecryptionFailed,
+	/// A back-reference to a header that hasn't been deserialized in this [`Dedup`](crate::dedup::Dedup).
+	MissingBackReference {
+		/// The index referred to.
+		index: u32,
+	},
+	/// The concrete type of the trait object isn't one [allowed](crate::validate::allow) for the trait object type.
+	Disallowed {
+		/// The name of the trait object type.
+		trait_object: &'static str,
+		/// The type id of the concrete type.
+		type_id: u64,
+	},
+	/// The installed [`Validator`](crate::validate::Validator) rejected the trait object.
+	Rejected {
+		/// The name of the trait object type.
+		trait_object: &'static str,
+		/// The error the validator rejected it with.
+		error: ValidationError,
+	},
+	/// Deserializing the value of the trait object failed.
+	PayloadError {
+		/// The error message.
+		message: String,
+	},
+	/// The trait object couldn't be read from the format, for example as it was truncated.
+	Malformed {
+		/// The error message.
+		message: String,
+	},
+}
+thread_local! {
+	static LAST: RefCell<Option<DeserializeError>> = const { RefCell::new(None) };
+}
+impl DeserializeError {
+	/// The error that deserializing the most recent trait object on this thread failed with, if it failed in one of these ways.
+	pub fn last() -> Option<Self> {
+		LAST.with(|last| last.borrow().clone())
+	}
+	/// Run `f`, deserializing with a format, and if it fails, wrap the format's error in a [`WithCause`] carrying the error the most recent trait object deserialized within `f` failed with, if any.
+	///
+	/// Unlike matching on the format's error, this works for formats that drop the messages of custom errors.
+	pub fn capture<T, E>(f: impl FnOnce() -> Result<T, E>) -> Result<T, WithCause<E>> {
+		Self::clear();
+		f().map_err(|error| WithCause {
+			error,
+			cause: Self::last(),
+		})
+	}
+	/// The error carried by the first [`WithCause`] in the [`source`](std::error::Error::source) chain of `error`, including `error` itself.
+	pub fn of<'a>(error: &'a (dyn error::Error + 'static)) -> Option<&'a Self> {
+		let mut link = Some(error);
+		while let Some(error) = link {
+			if let Some(error) = error.downcast_ref::<Self>() {
+				return Some(error);
+			}
+			link = error.source();
+		}
+		None
+	}
+	/// Whether this is the trait object failing validation, such that the [`policy`] applies to it.
+	///
+	/// That's all but [`UnsupportedVersion`](Self::UnsupportedVersion), [`TooDeep`](Self::TooDeep), [`MissingBackReference`](Self::MissingBackReference), [`PayloadError`](Self::PayloadError) and [`Malformed`](Self::Malformed), which are the input being unreadable or exceeding a limit rather than the trait object being invalid.
+	pub fn is_validation_failure(&self) -> bool {
+		!matches!(
+			self,
+			Self::UnsupportedVersion { .. }
+				| Self::TooDeep { .. }
+				| Self::MissingBackReference { .. }
+				| Self::PayloadError { .. }
+				| Self::Malformed { .. }
+		)
+	}
+	/// Record this error, and convert it into a format's error, or, if it's a [validation failure](Self::is_validation_failure), panic or abort instead as directed by the [`policy`](crate::policy).
+	pub(crate) fn raise<E: serde::de::Error>(self) -> E {
+		if self.is_validation_failure() {
+			match policy::policy() {
+				Policy::Error => (),
+				Policy::Panic => panic!("{}", self),
+				Policy::Abort => {
+					eprintln!("{self}");
+					process::abort()
+				}
+			}
+		}
+		let error = E::custom(&self);
+		LAST.with(|last| *last.borrow_mut() = Some(self));
+		error
+	}
+	/// Forget any error recorded, as a new trait object is being deserialized.
+	pub(crate) fn clear() {
+		LAST.with(|last| *last.borrow_mut() = None);
+	}
+	/// Record an error deserializing the value, unless it was caused by a nested trait object failing in one of these ways.
+	pub(crate) fn payload<E: fmt::Display>(error: E) -> E {
+		LAST.with(|last| {
+			let _ = last.borrow_mut().get_or_insert_with(|| Self::PayloadError {
+				message: error.to_string(),
+			});
+		});
+		error
+	}
+	/// The error recorded for the trait object that just failed to deserialize with `error`, recording it as [`Malformed`](Self::Malformed) if it failed in none of the other ways.
+	pub(crate) fn failed<E: fmt::Display>(error: &E) -> Self {
+		LAST.with(|last| {
+			last.borrow_mut()
+				.get_or_insert_with(|| Self::Malformed {
+					message: error.to_string(),
+				})
+				.clone()
+		})
+	}
+}
+impl fmt::Display for DeserializeError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::UnsupportedVersion { version } => write!(
+				f,
+				"unsupported format version {version}, expected {FORMAT_VERSION} or {FORMAT_VERSION_DIAGNOSTICS}"
+			),
+			Self::ArchitectureMismatch { trait_object, theirs, ours } => write!(
+				f,
+				"architecture mismatch: {theirs} vs {ours}; the \"{trait_object}\" trait object came from a different target"
+			),
+			Self::PointerWidthMismatch { trait_object, theirs, ours } => write!(
+				f,
+				"architecture mismatch: the \"{trait_object}\" trait object came from a {theirs}-bit target, but this is a {ours}-bit target"
+			),
+			Self::BuildIdMismatch { trait_object } => write!(
+				f,
+				"the \"{trait_object}\" trait object came from a different binary, so its vtable can't be trusted"
+			),
+			Self::TraitTypeIdMismatch { trait_object } => {
+				write!(f, "the trait object isn't a \"{trait_object}\"")
+			}
+			Self::ConcreteTypeIdMismatch { trait_object } => write!(
+				f,
+				"the concrete type of the \"{trait_object}\" trait object doesn't match the one it was serialized as"
+			),
+			Self::Corrupt { trait_object } => {
+				write!(f, "the header of the \"{trait_object}\" trait object is corrupt")
+			}
+			Self::InvalidOffset { trait_object, offset } => write!(
+				f,
+				"the vtable offset {offset:#x} of the \"{trait_object}\" trait object lies outside this binary"
+			),
+			Self::VtableMismatch { trait_object } => write!(
+				f,
+				"the vtable of the \"{trait_object}\" trait object doesn't match the one it was serialized with"
+			),
+			Self::Unregistered { trait_object, type_id } => write!(
+				f,
+				"the concrete type {type_id:016x} of the \"{trait_object}\" trait object hasn't been registered with `tagged::register`"
+			),
+			Self::TooDeep { trait_object, max_depth } => write!(
+				f,
+				"the \"{trait_object}\" trait object is nested more than {max_depth} deep"
+			),
+			Self::Unauthenticated => {
+				f.write_str("the trait object isn't authenticated, but a key is installed")
+			}
+			Self::AuthenticationFailed => f.write_str("the trait object failed authentication"),
+			Self::DecryptionFailed => {
+				f.write_str("the trait object couldn't be decrypted with the installed key")
+			}
+			Self::BadSignature => {
+				f.write_str("the signature of the trait object is missing or doesn't match the verifying key")
+			}
+			Self::MissingBackReference { index } => write!(
+				f,
+				"back-reference to trait object header {index} that hasn't been deserialized in this session"
+			),
+			Self::Disallowed { trait_object, type_id } => write!(
+				f,
+				"the concrete type {type_id:016x} isn't allowed for the \"{trait_object}\" trait object"
+			),
+			Self::Rejected { trait_object, error } => {
+				write!(f, "the \"{trait_object}\" trait object was rejected: {error}")
+			}
+			Self::PayloadError { message } | Self::Malformed { message } => f.write_str(message),
+		}
+	}
+}
+impl error::Error for DeserializeError {}
+
+/// A format's error, along with the [`DeserializeError`] that caused it, if any, as returned by [`DeserializeError::capture`].
+///
+/// Its [`source`](std::error::Error::source) is the cause, if any, otherwise that of the format's error.
+pub struct WithCause<E> {
+	error: E,
+	cause: Option<DeserializeError>,
+}
+impl<E> WithCause<E> {
+	/// The format's error.
+	pub fn error(&self) -> &E {
+		&self.error
+	}
+	/// The error deserializing a trait object failed with that caused the format's error, if any.
+	pub fn cause(&self) -> Option<&DeserializeError> {
+		self.cause.as_ref()
+	}
+	/// Unwrap into the format's error.
+	pub fn into_inner(self) -> E {
+		self.error
+	}
+}
+impl<E: fmt::Debug> fmt::Debug for WithCause<E> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("WithCause")
+			.field("error", &self.error)
+			.field("cause", &self.cause)
+			.finish()
+	}
+}
+impl<E: fmt::Display> fmt::Display for WithCause<E> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		fmt::Display::fmt(&self.error, f)
+	}
+}
+impl<E: error::Error> error::Error for WithCause<E> {
+	fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+		match &self.cause {
+			Some(cause) => Some(cause),
+			None => self.error.source(),
+		}
+	}
+}
+
+/// The `Display` output of the links of `chain` before the first that's an `E`, or `None` if none is.
+#[cfg(any(feature = "anyhow", feature = "eyre"))]
+fn context<'a, E: error::Error + 'static>(
+	chain: impl Iterator<Item = &'a (dyn error::Error + 'static)>,
+) -> Option<Vec<String>> {
+	let mut context = Vec::new();
+	for link in chain {
+		if link.is::<E>() {
+			return Some(context);
+		}
+		context.push(link.to_string());
+	}
+	None
+}
+
+/// Wrap `source` in a [`Context`] for each link of `context`, outermost first.
+#[cfg(any(feature = "anyhow", feature = "eyre"))]
+fn with_context(
+	context: Vec<String>, source: super::Box<dyn super::Error + Send + Sync>,
+) -> super::Box<dyn super::Error + Send + Sync> {
+	context.into_iter().rev().fold(source, |source, display| {
+		super::Box::new(Context::new(display, source))
+	})
+}
+
+#[cfg(feature = "anyhow")]
+impl From<anyhow::Error> for super::Box<dyn super::Error + Send + Sync> {
+	fn from(err: anyhow::Error) -> Self {
+		// The first link that's itself a `Box<dyn Error + Send + Sync>` is preserved, along with its own sources, beneath the context added on top of it. `downcast` reaches it through any number of layers of context, though not through the `source` of another error.
+		let Some(context) = context::<Self>(err.chain()) else {
+			return super::Box::new(Message::from_error(&*err));
+		};
+		match err.downcast::<Self>() {
+			Ok(source) => with_context(context, source),
+			Err(err) => super::Box::new(Message::from_error(&*err)),
+		}
+	}
+}
+
+/// The sections the handler of `report` added to its `Debug` output.
+///
+/// The output is read as eyre's default handler renders it: the report's message, followed by blocks that each start after a blank line with an unindented header line, like `Location:`, and continue with indented lines. The `Caused by:` block, which is the report's chain and is captured link by link, and the `Stack backtrace:` block, which describes this process, are skipped. Output in any other format, such as `color-eyre`'s, doesn't start with the message, and no sections are captured from it.
+#[cfg(feature = "eyre")]
+fn sections(report: &eyre::Report) -> Vec<String> {
+	let debug = format!("{report:?}");
+	let Some(rest) = debug.strip_prefix(&report.to_string()) else {
+		return Vec::new();
+	};
+	let mut blocks: Vec<String> = Vec::new();
+	let mut blank = false;
+	for line in rest.split('\n') {
+		if blank && !line.is_empty() && !line.starts_with(char::is_whitespace) {
+			blocks.push(line.to_owned());
+		} else if let Some(block) = blocks.last_mut() {
+			block.push('\n');
+			block.push_str(line);
+		}
+		blank = line.is_empty();
+	}
+	blocks
+		.into_iter()
+		.map(|block| block.trim_end_matches('\n').to_owned())
+		.filter(|block| {
+			let header = block.lines().next().unwrap_or_default();
+			header != "Caused by:" && header != "Stack backtrace:"
+		})
+		.collect()
+}
+
+#[cfg(feature = "eyre")]
+impl From<eyre::Report> for super::Box<dyn super::Error + Send + Sync> {
+	fn from(report: eyre::Report) -> Self {
+		let sections = sections(&report);
+		let fallback = |sections: Vec<String>, report: eyre::Report| -> Self {
+			super::Box::new(Message {
+				sections,
+				..Message::from_error(&*report)
+			})
+		};
+		// As for `anyhow::Error`, with the sections kept alongside the error preserved.
+		let Some(context) = context::<Self>(report.chain()) else {
+			return fallback(sections, report);
+		};
+		match report.downcast::<Self>() {
+			Ok(source) if sections.is_empty() => with_context(context, source),
+			Ok(source) => super::Box::new(WithSections {
+				error: with_context(context, source),
+				sections,
+			}),
+			Err(report) => fallback(sections, report),
+		}
+	}
+}
@@ -0,0 +1,70 @@
+//! Hooks observing the trait objects (de)serialized.
+//!
+//! A [`Hook`], installed with [`set_hook`], is invoked on every trait object serialized, and on every trait object deserialized whether or not that succeeds, giving a place to plug in logging, metrics or anomaly detection. Unlike a [`Validator`](crate::validate::Validator) it can't reject a trait object.
+//!
+//! # Example
+//! ```
+//! extern crate bincode;
+//! extern crate serde_traitobject as s;
+//! # s::policy::set_policy(s::policy::Policy::Error);
+//!
+//! use s::{error::DeserializeError, validate::HeaderInfo};
+//! use std::sync::atomic::{AtomicUsize, Ordering};
+//!
+//! static FAILED: AtomicUsize = AtomicUsize::new(0);
+//!
+//! struct CountFailures;
+//! impl s::hook::Hook for CountFailures {
+//!     fn on_deserialize(&self, _: &'static str, result: Result<&HeaderInfo, &DeserializeError>) {
+//!         if result.is_err() {
+//!             let _ = FAILED.fetch_add(1, Ordering::Relaxed);
+//!         }
+//!     }
+//! }
+//! s::hook::set_hook(CountFailures);
+//!
+//! let message: s::Box<dyn s::Debug> = s::Box::new(String::from("hooked"));
+//! let serialized = bincode::serialize(&message).unwrap();
+//! let _: s::Box<dyn s::Debug> = bincode::deserialize(&serialized).unwrap();
+//! assert_eq!(FAILED.load(Ordering::Relaxed), 0);
+//! let result: Result<s::Box<dyn s::Any>, _> = bincode::deserialize(&serialized);
+//! assert!(result.is_err());
+//! assert_eq!(FAILED.load(Ordering::Relaxed), 1);
+//! ```
+
+use std::sync::{Arc, PoisonError, RwLock};
+
+use super::{error::DeserializeError, validate::HeaderInfo};
+
+/// An observer of the trait objects (de)serialized.
+///
+/// Both methods do nothing by default, so only those of interest need implementing.
+pub trait Hook: Send + Sync {
+	/// Called with the trait object about to be serialized.
+	fn on_serialize(&self, info: &HeaderInfo<'_>) {
+		let _ = info;
+	}
+	/// Called after a `trait_object` trait object is deserialized, with either its [`HeaderInfo`] or the error it failed with.
+	fn on_deserialize(
+		&self, trait_object: &'static str, result: Result<&HeaderInfo<'_>, &DeserializeError>,
+	) {
+		let _ = (trait_object, result);
+	}
+}
+
+static HOOK: RwLock<Option<Arc<dyn Hook>>> = RwLock::new(None);
+
+/// Install `hook` for all threads, replacing any installed before.
+pub fn set_hook<H: Hook + 'static>(hook: H) {
+	*HOOK.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(hook));
+}
+
+/// Remove the hook installed with [`set_hook`], if any.
+pub fn remove_hook() {
+	*HOOK.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// The installed hook, if any.
+pub(crate) fn hook() -> Option<Arc<dyn Hook>> {
+	HOOK.read().unwrap_or_else(PoisonError::into_inner).clone()
+}
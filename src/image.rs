@@ -0,0 +1,138 @@
+//! The bounds of the loaded binary, that a vtable recreated from its offset is checked to lie within before it's used.
+//!
+//! On Linux and Android the bounds are the read-only segments of the ELF object, as enumerated by `dl_iterate_phdr`, such that a vtable can't be recreated in writable memory. On macOS and iOS, where the dynamic linker can place the Mach-O images, and on Windows, where ASLR can place the PE modules, anywhere, a range is instead taken to lie within them if both its ends lie within the same image or module, as given by `dladdr` or `GetModuleHandleExW`. Nothing lies within an object that can't be found. On platforms where the bounds can't be determined, every range is taken to lie within them.
+
+/// Whether the `len` bytes at `address` lie within one of the read-only segments of the object containing `base`.
+///
+/// The read-only segments are the loaded segments that aren't writable, such as text and rodata, along with the relocation read-only (RELRO) segment, which holds the vtables of position-independent objects and is made read-only once they're relocated.
+#[cfg(any(target_os = "linux", target_os = "android"))]
+pub(crate) fn contains(base: usize, address: usize, len: usize) -> bool {
+	use std::{
+		collections::BTreeMap, convert::TryFrom, ops::Range, sync::{PoisonError, RwLock}
+	};
+
+	/// The writable segment flag, which `libc` doesn't define on Android.
+	const PF_W: u32 = 0x2;
+
+	unsafe extern "C" fn callback(
+		info: *mut libc::dl_phdr_info, _size: libc::size_t, data: *mut libc::c_void,
+	) -> libc::c_int {
+		let (base, segments) = unsafe { &mut *data.cast::<(usize, Vec<Range<usize>>)>() };
+		let info = unsafe { &*info };
+		if info.dlpi_phdr.is_null() {
+			return 0;
+		}
+		let phdrs = unsafe { std::slice::from_raw_parts(info.dlpi_phdr, info.dlpi_phnum.into()) };
+		let headers = phdrs
+			.iter()
+			.filter_map(|phdr| {
+				let start = usize::try_from(info.dlpi_addr.wrapping_add(phdr.p_vaddr)).ok()?;
+				let end = start.checked_add(usize::try_from(phdr.p_memsz).ok()?)?;
+				Some((phdr.p_type, phdr.p_flags, start..end))
+			})
+			.collect::<Vec<_>>();
+		if !headers
+			.iter()
+			.any(|(kind, _, segment)| *kind == libc::PT_LOAD && segment.contains(base))
+		{
+			return 0;
+		}
+		*segments = headers
+			.into_iter()
+			.filter(|&(kind, flags, _)| {
+				(kind == libc::PT_LOAD && flags & PF_W == 0) || kind == libc::PT_GNU_RELRO
+			})
+			.map(|(_, _, segment)| segment)
+			.collect();
+		1
+	}
+
+	/// The read-only segments of the objects containing each `base` looked up so far.
+	static SEGMENTS: RwLock<BTreeMap<usize, Vec<Range<usize>>>> = RwLock::new(BTreeMap::new());
+	let Some(end) = address.checked_add(len) else {
+		return false;
+	};
+	// An object that couldn't be found has no segments, so nothing lies within it.
+	let contains = |segments: &Vec<Range<usize>>| {
+		segments
+			.iter()
+			.any(|segment| segment.start <= address && end <= segment.end)
+	};
+	if let Some(segments) = SEGMENTS
+		.read()
+		.unwrap_or_else(PoisonError::into_inner)
+		.get(&base)
+	{
+		return contains(segments);
+	}
+	let mut data = (base, Vec::new());
+	let _ = unsafe { libc::dl_iterate_phdr(Some(callback), (&raw mut data).cast()) };
+	contains(
+		SEGMENTS
+			.write()
+			.unwrap_or_else(PoisonError::into_inner)
+			.entry(base)
+			.or_insert(data.1),
+	)
+}
+
+/// Whether the `len` bytes at `address` lie within the same Mach-O image as `base`.
+#[cfg(any(target_os = "macos", target_os = "ios"))]
+pub(crate) fn contains(base: usize, address: usize, len: usize) -> bool {
+	/// The address the image containing `address` is loaded at.
+	fn image(address: usize) -> Option<usize> {
+		let mut info = std::mem::MaybeUninit::<libc::Dl_info>::uninit();
+		let found = unsafe { libc::dladdr(address as *const libc::c_void, info.as_mut_ptr()) };
+		(found != 0).then(|| unsafe { info.assume_init() }.dli_fbase as usize)
+	}
+	let Some(end) = address.checked_add(len.saturating_sub(1)) else {
+		return false;
+	};
+	image(base).is_some_and(|image_base| {
+		image(address) == Some(image_base) && image(end) == Some(image_base)
+	})
+}
+
+/// Whether the `len` bytes at `address` lie within the same PE module as `base`.
+#[cfg(windows)]
+pub(crate) fn contains(base: usize, address: usize, len: usize) -> bool {
+	const GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT: u32 = 0x2;
+	const GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS: u32 = 0x4;
+	#[link(name = "kernel32")]
+	extern "system" {
+		fn GetModuleHandleExW(
+			flags: u32, module_name: *const u16, module: *mut *mut std::ffi::c_void,
+		) -> i32;
+	}
+	/// The address the module containing `address` is loaded at.
+	fn module(address: usize) -> Option<usize> {
+		let mut module = std::ptr::null_mut();
+		let found = unsafe {
+			GetModuleHandleExW(
+				GET_MODULE_HANDLE_EX_FLAG_FROM_ADDRESS
+					| GET_MODULE_HANDLE_EX_FLAG_UNCHANGED_REFCOUNT,
+				address as *const u16,
+				&raw mut module,
+			)
+		};
+		(found != 0).then(|| module as usize)
+	}
+	let Some(end) = address.checked_add(len.saturating_sub(1)) else {
+		return false;
+	};
+	module(base).is_some_and(|module_base| {
+		module(address) == Some(module_base) && module(end) == Some(module_base)
+	})
+}
+
+/// Whether the `len` bytes at `address` lie within the loaded binary, which can't be determined on this platform.
+#[cfg(not(any(
+	target_os = "linux",
+	target_os = "android",
+	target_os = "macos",
+	target_os = "ios",
+	windows
+)))]
+pub(crate) fn contains(_base: usize, _address: usize, _len: usize) -> bool {
+	true
+}
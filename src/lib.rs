@@ -25,13 +25,44 @@
 //!
 //! And that's it! The two traits are automatically implemented for all `T: serde::Serialize` and all `T: serde::de::DeserializeOwned`, so as long as all implementors of your trait are themselves serializable then you're good to go.
 //!
-//! There are two ways to (de)serialize your trait object:
+//! There are three ways to (de)serialize your trait object:
 //!  * Apply the `#[serde(with = "serde_traitobject")]` [field attribute](https://serde.rs/attributes.html), which instructs serde to use this crate's [serialize](serialize()) and [deserialize](deserialize()) functions;
-//!  * The [Box], [Rc] and [Arc] structs, which are simple wrappers around their stdlib counterparts that automatically handle (de)serialization without needing the above annotation;
+//!  * The [Box], [Rc], [Arc] and [Vec] structs, which are simple wrappers around their stdlib counterparts that automatically handle (de)serialization without needing the above annotation. As they implement `serde::Serialize` and `serde::Deserialize` themselves, they can be nested within other containers, e.g. `Vec<Option<s::Box<dyn MyTrait>>>`;
+//!  * The [option], [vec], [map], [result], [rc] and [arc] modules for use with `#[serde(with = "...")]` on fields holding stdlib pointers within common containers. With the `serde_with` feature enabled, the `As` adapter handles arbitrarily nested containers via `#[serde_as(as = "Vec<Option<serde_traitobject::As>>")]`.
+//!
+//! Trait objects serialized this way can only be deserialized by the same binary. Beyond that, the modules below each document, with an example, how to persist trait objects beyond a single binary ([tagged], [compat], [builds]), write them more compactly ([batch], [dedup]), check them as they're deserialized ([validate], [depth], [policy], [hook]), and (de)serialize concrete types that need more than `serde` ([state], [repr], [resources], [`Seed`], [`PreSerialize`] and [`PostDeserialize`]). [`serialized_size`] counts the bytes a trait object serializes to with `bincode`.
+//!
+//! Optional formats, integrations and hardening are enabled with features:
+//!
+//! | Feature | Enables |
+//! |---|---|
+//! | `bincode` | `to_bytes` and `from_bytes` |
+//! | `json` | `to_value` and `from_value`, for `serde_json::Value` |
+//! | `cbor` | the `cbor` module, writing trait objects under a CBOR tag |
+//! | `msgpack` | the `msgpack` module, writing the header as an msgpack ext type |
+//! | `rkyv` | the `rkyv` module, archiving trait objects with `rkyv` |
+//! | `borsh` | `BorshSerialize` and `BorshDeserialize` for the wrappers, with the `borsh` module |
+//! | `schemars` | `JsonSchema` for the wrappers, with the `schema` module |
+//! | `serde_with` | the `As` adapter, for arbitrarily nested containers |
+//! | `anyhow`, `eyre` | conversion of their errors into a serializable [`Error`], with the [error] module |
+//! | `hmac` | the `auth` module, authenticating headers with a secret key |
+//! | `ed25519` | the `signed` module, signing trait objects |
+//! | `encryption` | the `encrypted` module, encrypting trait objects |
+//! | `trusted` | the `trusted` module, skipping validation of trait objects known to come from this process |
+//! | `audit` | the `audit` module, recording the vtables reconstructed |
+//! | `symbols` | the `symbols` module, resolving vtables via the loaded object containing them |
+//! | `diagnostics`, `paranoid`, `wide_type_id`, `security` | more descriptive or more thorough [validation](#validation) |
+//! | `panic_on_invalid`, `abort_on_invalid` | a different default [policy] |
+//! | `futures`, `tokio`, `rand` | the convenience traits below that extend theirs |
 //!
 //! Additionally, there are several convenience traits implemented that extend their stdlib counterparts:
 //!
-//!  * [Any], [Debug], [Display], [Error], [Fn], [FnMut], [FnOnce]
+//!  * [`Any`], [`Debug`], [`Display`], [`Error`], [`Fn`], [`FnMut`], [`FnOnce`]
+//!  * [`Iterator`], [`DoubleEndedIterator`], [`ExactSizeIterator`]
+//!  * [`Future`], and with the `futures` feature enabled, `Stream`
+//!  * [`Clone`], [`Hash`], [`Hasher`], [`PartialEq`], [`Eq`], [`PartialOrd`], [`Ord`]
+//!  * [`Read`], [`BufRead`], [`Write`], [`Seek`], and with the `tokio` feature enabled, `AsyncRead` and `AsyncWrite`
+//!  * with the `rand` feature enabled, `RngCore`
 //!
 //! These are automatically implemented on all implementors of their stdlib counterparts that also implement `serde::Serialize` and `serde::de::DeserializeOwned`.
 //!
@@ -81,17 +112,25 @@
 //!
 //! At some point in Rust's future, I think it would be great if the latter could be used to safely look up and create a trait object. As it is, that functionality doesn't exist yet, so what this crate does instead is serialize the vtable pointer (relative to a static base), and do as much validity checking as it reasonably can before it can be used and potentially invoke UB.
 //!
-//! The first two are [checked for validity](https://github.com/alecmocatta/relative/blob/dae206663a09b9c0c4b3012c528b0e9c063df742/src/lib.rs#L457-L474) before usage of the vtable pointer. The `build_id` ensures that the vtable pointer came from an invocation of an identically laid out binary<sup>1</sup>. The `type_id` ensures that the trait object being deserialized is the same type as the trait object that was serialized. They ensure that under non-malicious conditions, attempts to deserialize invalid data return an error rather than UB. The `type_id` of the concrete type is used as a [sanity check](https://github.com/alecmocatta/serde_traitobject/blob/b20d74e183063e7d49aff2eabc9dcd5bc26d7c07/src/lib.rs#L469) that panics if it differs from the `type_id` of the concrete type to be deserialized.
+//! The first two are [checked for validity](https://github.com/alecmocatta/relative/blob/dae206663a09b9c0c4b3012c528b0e9c063df742/src/lib.rs#L457-L474) before usage of the vtable pointer. The `build_id` ensures that the vtable pointer came from an invocation of an identically laid out binary<sup>1</sup>. The `type_id` ensures that the trait object being deserialized is the same type as the trait object that was serialized. They ensure that under non-malicious conditions, attempts to deserialize invalid data return an error rather than UB. The `type_id` of the concrete type is used as a [sanity check](https://github.com/alecmocatta/serde_traitobject/blob/b20d74e183063e7d49aff2eabc9dcd5bc26d7c07/src/lib.rs#L469) that returns an error if it differs from the `type_id` of the concrete type to be deserialized.
+//!
+//! The validation data is preceded by a format version, such that payloads written by an incompatible version of this crate are rejected with an error rather than misinterpreted.
+//!
+//! With the `diagnostics` feature enabled, the name of the concrete type is also serialized, such that validation errors can say what the payload was. This is off by default to avoid bloating payloads.
+//!
+//! With the `paranoid` feature enabled, the header of each trait object also carries a fingerprint of the leading entries of its vtable, which is verified on deserialization, catching layout drift that the build id alone might miss.
 //!
-//! Regarding collisions, the 128 bit `build_id` colliding is sufficiently unlikely that it can be relied upon to never occur. The 64 bit `type_id` colliding is possible, see [rust-lang/rust#10389](https://github.com/rust-lang/rust/issues/10389), though exceedingly unlikely to occur in practise.
+//! With the `security` feature enabled, the build ids, type ids and integrity checks in the validation data are compared in constant time, such that the time taken to reject a forged trait object doesn't reveal how much of it was correct.
 //!
-//! The vtable pointer is (de)serialized as a usize relative to the vtable pointer of [this static trait object](https://github.com/alecmocatta/relative/blob/dae206663a09b9c0c4b3012c528b0e9c063df742/src/lib.rs#L90). This enables it to work under typical dynamic linking conditions, where the absolute vtable addresses can differ across invocations of the same binary, but relative addresses remain constant.
+//! Regarding collisions, the 128 bit `build_id` colliding is sufficiently unlikely that it can be relied upon to never occur. The 64 bit `type_id` colliding is possible, see [rust-lang/rust#10389](https://github.com/rust-lang/rust/issues/10389), though exceedingly unlikely to occur in practise. The `wide_type_id` feature rules it out for concrete types.
+//!
+//! The vtable pointer is (de)serialized as a usize relative to the vtable pointer of [this static trait object](https://github.com/alecmocatta/relative/blob/dae206663a09b9c0c4b3012c528b0e9c063df742/src/lib.rs#L90). This enables it to work under typical dynamic linking conditions, where the absolute vtable addresses can differ across invocations of the same binary, but relative addresses remain constant. The offset is always written as 64 bits, alongside the architecture, byte order and pointer width of the target, such that payloads from a different target are rejected with a descriptive error. Before it's used, the vtable pointer is also checked to lie within the loaded binary, on platforms where its bounds can be determined: Linux and Android, macOS and iOS, and Windows. `platform::self_check` can be run at startup to check that vtable pointers round-trip through their offsets on the current platform, failing with a descriptive error rather than when trait objects are later exchanged.
 //!
 //! All together this leaves, as far as I'm aware, three soundness holes:
 //!
-//!  * A malicious user with a copy of the binary could trivially craft a `build_id` and `type_id` that pass validation and gives them control of where to jump to.
-//!  * Data corruption of the serialized vtable pointer but not the `build_id` or `type_id` used for validation, resulting in a jump to an arbitrary address. This could be rectified in a future version of this library by using a cipher to make it vanishingly unlikely for corruptions to affect only the vtable pointer, by mixing the vtable pointer and validation components upon (de)serialization.
-//!  * Dynamic linking conditions where the relative addresses (vtable - static vtable) are different across different invocations of the same binary. I'm sure this is possible, but it's not a scenario I've encountered so I can't speak to its commonness.
+//!  * A malicious user with a copy of the binary could trivially craft a `build_id` and `type_id` that pass validation and gives them control of where to jump to. Where the channel allows, authenticating trait objects with the `hmac` or `ed25519` features closes it.
+//!  * Data corruption of the serialized vtable pointer but not the `build_id` or `type_id` used for validation, resulting in a jump to an arbitrary address. This is rectified for the fixed-width header that formats that aren't human-readable use by default, by using a cipher to mix the vtable pointer and validation components upon (de)serialization, making it vanishingly unlikely for corruptions to affect only the vtable pointer. It remains possible with human-readable formats.
+//!  * Dynamic linking conditions where the relative addresses (vtable - static vtable) are different across different invocations of the same binary. I'm sure this is possible, but it's not a scenario I've encountered so I can't speak to its commonness. Registering an anchor within each shared object that the vtables lie in, with `anchors::register`, addresses it, by (de)serializing the vtable pointer relative to the anchor within the same object instead. On Linux and Android, the `symbols` feature also addresses it, by instead (de)serializing the vtable pointer as a hash of the file name of the loaded object containing it and its offset within that object, such that it's resolved via that object wherever it's loaded.
 //!
 //! <sup>1</sup>I don't think this requirement is strictly necessary, as the `type_id` should include all information that could affect soundness (trait methods, calling conventions, etc), but it's included in case that doesn't hold in practise; to provide a more helpful error message; and to reduce the likelihood of collisions.
 //!
@@ -101,7 +140,9 @@
 
 #![doc(html_root_url = "https://docs.rs/serde_traitobject/0.2.7")]
 #![feature(
+	allocator_api,
 	arbitrary_self_types,
+	arbitrary_self_types_pointers,
 	coerce_unsized,
 	fn_traits,
 	tuple_trait,
@@ -126,14 +167,84 @@
 	incomplete_features
 )]
 
+pub mod anchors;
+pub mod arc;
+#[cfg(feature = "audit")]
+pub mod audit;
+#[cfg(feature = "hmac")]
+pub mod auth;
+pub mod batch;
+#[cfg(feature = "borsh")]
+pub mod borsh;
+pub mod builds;
+pub mod bytes;
+#[cfg(feature = "cbor")]
+pub mod cbor;
+mod cipher;
+#[cfg(any(feature = "bincode", feature = "json"))]
+mod codec;
+pub mod compat;
+mod constant_time;
 mod convenience;
+pub mod dedup;
+pub mod depth;
+#[cfg(feature = "encryption")]
+pub mod encrypted;
+pub mod error;
+pub mod hook;
+mod image;
+pub mod map;
+#[cfg(feature = "msgpack")]
+pub mod msgpack;
+pub mod named;
+pub mod option;
+pub mod platform;
+mod pointer;
+pub mod policy;
+pub mod rc;
+pub mod repr;
+pub mod resources;
+pub mod result;
+#[cfg(feature = "rkyv")]
+pub mod rkyv;
+#[cfg(feature = "schemars")]
+pub mod schema;
+mod seed;
+#[cfg(feature = "serde_with")]
+mod serde_as;
+pub mod shared;
+#[cfg(feature = "ed25519")]
+pub mod signed;
+mod size;
+mod small_box;
+pub mod state;
+#[cfg(all(feature = "symbols", any(target_os = "linux", target_os = "android")))]
+mod symbols;
+pub mod tagged;
+mod thin;
+#[cfg(feature = "trusted")]
+pub mod trusted;
+pub mod validate;
+pub mod vec;
 
+use error::DeserializeError;
 use metatype::type_coerce;
 use relative::Vtable;
-use serde::ser::SerializeTuple;
-use std::{any::type_name, boxed, fmt, marker};
+use serde::ser::{SerializeStruct, SerializeTuple};
+use std::{
+	alloc, any::{self, type_name}, boxed, convert::{TryFrom, TryInto}, fmt, marker, ptr
+};
 
+#[cfg(any(feature = "bincode", feature = "json"))]
+pub use codec::*;
 pub use convenience::*;
+pub use pointer::*;
+pub use seed::*;
+#[cfg(feature = "serde_with")]
+pub use serde_as::As;
+pub use size::*;
+pub use small_box::*;
+pub use thin::*;
 
 /// Any trait with this as a supertrait can be serialized as a trait object.
 ///
@@ -233,6 +344,73 @@ impl<T: serde::de::DeserializeOwned> Deserialize for T {}
 impl Deserialize for str {}
 impl<T: serde::de::DeserializeOwned> Deserialize for [T] {}
 
+/// A hook invoked on a concrete value immediately before it's serialized, to flush buffers or snapshot volatile state such that what's serialized is consistent.
+///
+/// Implementing it is optional: it's invoked on the concrete values that implement it, whether serialized as a trait object or directly as a `Box`. As serialization only has shared access to the value, any state it updates needs interior mutability.
+///
+/// ```
+/// extern crate bincode;
+/// extern crate serde_traitobject as s;
+/// # use serde_derive::{Serialize, Deserialize};
+/// use std::sync::Mutex;
+///
+/// #[derive(Serialize, Deserialize, Debug)]
+/// struct Log {
+///     flushed: Mutex<Vec<String>>,
+///     #[serde(skip)]
+///     buffered: Mutex<Vec<String>>,
+/// }
+///
+/// impl s::PreSerialize for Log {
+///     fn pre_serialize(&self) {
+///         let buffered = std::mem::take(&mut *self.buffered.lock().unwrap());
+///         self.flushed.lock().unwrap().extend(buffered);
+///     }
+/// }
+///
+/// let log = Log { flushed: Mutex::new(Vec::new()), buffered: Mutex::new(vec![String::from("entry")]) };
+/// let log: s::Box<dyn s::Debug> = s::Box::new(log);
+/// let serialized = bincode::serialize(&log).unwrap();
+/// let deserialized: s::Box<dyn s::Debug> = bincode::deserialize(&serialized).unwrap();
+/// assert!(format!("{:?}", deserialized).contains("[\"entry\"]"));
+/// ```
+pub trait PreSerialize {
+	/// Called on the value immediately before it's serialized.
+	fn pre_serialize(&self);
+}
+
+/// A hook invoked on a concrete value immediately after it's deserialized, to rebuild state that isn't serialized, like caches or connections.
+///
+/// Implementing it is optional: it's invoked on the concrete values that implement it, whether deserialized as a trait object or directly as a `Box`.
+///
+/// ```
+/// extern crate bincode;
+/// extern crate serde_traitobject as s;
+/// # use serde_derive::{Serialize, Deserialize};
+///
+/// #[derive(Serialize, Deserialize, Debug)]
+/// struct Squares {
+///     up_to: usize,
+///     #[serde(skip)]
+///     cache: Vec<usize>,
+/// }
+///
+/// impl s::PostDeserialize for Squares {
+///     fn post_deserialize(&mut self) {
+///         self.cache = (0..self.up_to).map(|i| i * i).collect();
+///     }
+/// }
+///
+/// let squares: s::Box<dyn s::Debug> = s::Box::new(Squares { up_to: 4, cache: Vec::new() });
+/// let serialized = bincode::serialize(&squares).unwrap();
+/// let deserialized: s::Box<dyn s::Debug> = bincode::deserialize(&serialized).unwrap();
+/// assert_eq!(format!("{:?}", deserialized), "Squares { up_to: 4, cache: [0, 1, 4, 9] }");
+/// ```
+pub trait PostDeserialize {
+	/// Called on the value immediately after it's deserialized.
+	fn post_deserialize(&mut self);
+}
+
 mod serialize {
 	use metatype::type_id;
 
@@ -249,6 +427,31 @@ mod serialize {
 		{
 			type_id::<Self>()
 		}
+
+		#[inline]
+		fn serialize_type_name(&self) -> &'static str {
+			std::any::type_name::<Self>()
+		}
+
+		/// Invoke [`PreSerialize`](super::PreSerialize) on the value about to be serialized, if it implements it.
+		#[inline]
+		fn pre_serialize(&self) {
+			MaybePreSerialize::pre_serialize(self);
+		}
+	}
+
+	trait MaybePreSerialize {
+		fn pre_serialize(&self);
+	}
+	impl<T: ?Sized> MaybePreSerialize for T {
+		#[inline]
+		default fn pre_serialize(&self) {}
+	}
+	impl<T: super::PreSerialize + ?Sized> MaybePreSerialize for T {
+		#[inline]
+		fn pre_serialize(&self) {
+			super::PreSerialize::pre_serialize(self);
+		}
 	}
 
 	impl<T: serde::ser::Serialize + ?Sized> Sealed for T {
@@ -270,6 +473,7 @@ mod serialize {
 			S: serde::Serializer,
 			Self: Sized,
 		{
+			Sealed::pre_serialize(self);
 			serde::ser::Serialize::serialize(self, serializer)
 		}
 	}
@@ -277,13 +481,21 @@ mod serialize {
 
 mod deserialize {
 	use metatype::type_id;
-	use std::ptr::NonNull;
+	use std::{
+		alloc::{self, Layout}, ptr::{self, NonNull}
+	};
+
+	/// Provides the memory a deserialized value is written into. It's passed
+	/// the layout of the concrete value, and returns a pointer valid for
+	/// writes of that layout.
+	pub type Place<'a> = dyn FnMut(Layout) -> NonNull<u8> + 'a;
 
 	pub trait Sealed {
 		fn deserialize_erased(
 			self: *const Self, deserializer: &mut dyn erased_serde::Deserializer,
-		) -> Result<NonNull<()>, erased_serde::Error> {
-			let _ = deserializer;
+			place: &mut Place<'_>,
+		) -> Result<*mut (), erased_serde::Error> {
+			let _ = (deserializer, place);
 			unreachable!()
 		}
 
@@ -296,6 +508,17 @@ mod deserialize {
 			unreachable!()
 		}
 
+		fn deserialize_in<'de, D>(
+			deserializer: D, place: &mut Place<'_>,
+		) -> Result<*mut Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+			Self: Sized,
+		{
+			let _ = (deserializer, place);
+			unreachable!()
+		}
+
 		#[inline]
 		fn type_id(self: *const Self) -> u64
 		where
@@ -303,15 +526,21 @@ mod deserialize {
 		{
 			type_id::<Self>()
 		}
+
+		#[cfg(feature = "wide_type_id")]
+		#[inline]
+		fn type_name(self: *const Self) -> &'static str {
+			std::any::type_name::<Self>()
+		}
 	}
 
 	impl<T: serde::de::DeserializeOwned> Sealed for T {
 		#[inline]
 		fn deserialize_erased(
 			self: *const Self, deserializer: &mut dyn erased_serde::Deserializer,
-		) -> Result<NonNull<()>, erased_serde::Error> {
-			erased_serde::deserialize::<Self>(deserializer)
-				.map(|x| NonNull::new(Box::into_raw(Box::new(x)).cast()).unwrap())
+			place: &mut Place<'_>,
+		) -> Result<*mut (), erased_serde::Error> {
+			Self::deserialize_in(deserializer, place).map(<*mut Self>::cast)
 		}
 
 		#[inline]
@@ -320,7 +549,46 @@ mod deserialize {
 			D: serde::Deserializer<'de>,
 			Self: Sized,
 		{
-			serde::de::Deserialize::deserialize(deserializer).map(Box::new)
+			serde::de::Deserialize::deserialize(deserializer).map(|mut x| {
+				post_deserialize(&mut x);
+				Box::new(x)
+			})
+		}
+
+		#[inline]
+		fn deserialize_in<'de, D>(
+			deserializer: D, place: &mut Place<'_>,
+		) -> Result<*mut Self, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+			Self: Sized,
+		{
+			// Moved from the stack into `place`, with no intermediate allocation.
+			serde::de::Deserialize::deserialize(deserializer).map(|mut x| {
+				post_deserialize(&mut x);
+				let raw = place(Layout::new::<Self>()).cast::<Self>();
+				unsafe { raw.as_ptr().write(x) };
+				raw.as_ptr()
+			})
+		}
+	}
+
+	/// Invoke [`PostDeserialize`](super::PostDeserialize) on a value just deserialized, if it implements it.
+	#[inline]
+	pub fn post_deserialize<T>(t: &mut T) {
+		MaybePostDeserialize::post_deserialize(t);
+	}
+	trait MaybePostDeserialize {
+		fn post_deserialize(&mut self);
+	}
+	impl<T> MaybePostDeserialize for T {
+		#[inline]
+		default fn post_deserialize(&mut self) {}
+	}
+	impl<T: super::PostDeserialize> MaybePostDeserialize for T {
+		#[inline]
+		fn post_deserialize(&mut self) {
+			super::PostDeserialize::post_deserialize(self);
 		}
 	}
 
@@ -329,19 +597,726 @@ mod deserialize {
 
 	/// Rust currently doesn't support returning Self traitobjects from
 	/// traitobject methods. Work around that by returning a thin pointer and
-	/// fattening it.
+	/// fattening it with the metadata of `self_`, which neither copies nor
+	/// reallocates the value.
 	#[allow(clippy::module_name_repetitions)]
 	#[inline]
-	pub fn deserialize_erased<T: ?Sized>(
-		self_: *const T, deserializer: &mut dyn erased_serde::Deserializer,
-	) -> Result<Box<T>, erased_serde::Error>
+	pub fn deserialize_erased<T: Sealed + ?Sized>(
+		self_: *const T, deserializer: &mut dyn erased_serde::Deserializer, place: &mut Place<'_>,
+	) -> Result<*mut T, erased_serde::Error> {
+		self_
+			.deserialize_erased(deserializer, place)
+			.map(|raw| metatype::Type::fatten(raw, metatype::Type::meta(self_)))
+	}
+
+	/// The [`Place`] used for `Box`: allocate with the global allocator.
+	#[inline]
+	pub fn heap(layout: Layout) -> NonNull<u8> {
+		if layout.size() == 0 {
+			return NonNull::new(layout.align() as *mut u8).unwrap();
+		}
+		NonNull::new(unsafe { alloc::alloc(layout) })
+			.unwrap_or_else(|| alloc::handle_alloc_error(layout))
+	}
+
+	/// Move the contents of a `Box<T>` into `place`, freeing the box.
+	#[inline]
+	pub fn unbox<T: ?Sized>(t: Box<T>, place: &mut Place<'_>) -> *mut T {
+		let layout = Layout::for_value::<T>(&t);
+		let raw: *mut T = Box::into_raw(t);
+		let dst = place(layout);
+		unsafe {
+			ptr::copy_nonoverlapping(raw.cast::<u8>(), dst.as_ptr(), layout.size());
+			if layout.size() != 0 {
+				alloc::dealloc(raw.cast(), layout);
+			}
+		}
+		metatype::Type::fatten(dst.as_ptr().cast(), metatype::Type::meta(raw))
+	}
+}
+
+/// How a trait object is laid out by the serializer.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+enum Encoding {
+	/// [`Named`](Encoding::Named) for human-readable formats, to aid debugging, and [`Bytes`](Encoding::Bytes) otherwise. The default.
+	Auto,
+	/// A struct of format version, vtable, type id and value, with the concrete type's name after the version if the `diagnostics` feature is enabled. Unlike a tuple it can be `#[serde(flatten)]`ed.
+	Named,
+	/// A tuple of a fixed-width [`Header`] and value, with the concrete type's name after the header if the `diagnostics` feature is enabled. Compact, and quick to (de)serialize as the header is written and read in one go.
+	Bytes,
+	/// [`Bytes`](Encoding::Bytes), with the header wrapped in an msgpack ext type.
+	#[cfg(feature = "msgpack")]
+	MessagePack,
+	/// A 2-tuple of vtable offset and value, for elements of a [`Batch`](batch::Batch), which validates the build and trait object type once for all of its elements.
+	Batched,
+}
+impl Encoding {
+	#[inline]
+	fn resolve(self, is_human_readable: bool) -> Self {
+		match self {
+			Self::Auto if is_human_readable => Self::Named,
+			Self::Auto => Self::Bytes,
+			encoding => encoding,
+		}
+	}
+}
+
+/// The field names of [`Encoding::Named`], in serialization order.
+const FIELDS: [&str; 5] = ["version", "type_name", "vtable", "type_id", "value"];
+#[derive(Copy, Clone)]
+enum Field {
+	Version,
+	TypeName,
+	Vtable,
+	TypeId,
+	Value,
+}
+impl<'de> serde::de::Deserialize<'de> for Field {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
 	where
-		T: Sealed,
+		D: serde::Deserializer<'de>,
 	{
-		self_.deserialize_erased(deserializer).map(|raw| {
-			let object: *mut T = metatype::Type::fatten(raw.as_ptr(), metatype::Type::meta(self_));
-			unsafe { Box::from_raw(object) }
-		})
+		struct Visitor;
+		impl serde::de::Visitor<'_> for Visitor {
+			type Value = Field;
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				formatter.write_str("a trait object field identifier")
+			}
+			fn visit_u64<E>(self, v: u64) -> Result<Field, E>
+			where
+				E: serde::de::Error,
+			{
+				match v {
+					0 => Ok(Field::Version),
+					1 => Ok(Field::TypeName),
+					2 => Ok(Field::Vtable),
+					3 => Ok(Field::TypeId),
+					4 => Ok(Field::Value),
+					_ => Err(E::invalid_value(serde::de::Unexpected::Unsigned(v), &self)),
+				}
+			}
+			fn visit_str<E>(self, v: &str) -> Result<Field, E>
+			where
+				E: serde::de::Error,
+			{
+				match v {
+					"version" => Ok(Field::Version),
+					"type_name" => Ok(Field::TypeName),
+					"vtable" => Ok(Field::Vtable),
+					"type_id" => Ok(Field::TypeId),
+					"value" => Ok(Field::Value),
+					_ => Err(E::unknown_field(v, &FIELDS)),
+				}
+			}
+		}
+		deserializer.deserialize_identifier(Visitor)
+	}
+}
+
+/// The version of the wire format, which is written first so that the format can evolve without older or newer payloads being misinterpreted.
+const FORMAT_VERSION: u8 = 1;
+/// [`FORMAT_VERSION`] with the concrete type's name following the version, as written with the `diagnostics` feature enabled.
+const FORMAT_VERSION_DIAGNOSTICS: u8 = 2;
+/// The version of a [`Header`] that refers back to an identical one serialized earlier in a [`dedup::Dedup`], by its index as a little-endian `u32`.
+const FORMAT_VERSION_BACKREF: u8 = 3;
+/// Returns whether the version is followed by the concrete type's name.
+fn check_version<E: serde::de::Error>(version: u8) -> Result<bool, E> {
+	match version {
+		FORMAT_VERSION => Ok(false),
+		FORMAT_VERSION_DIAGNOSTICS => Ok(true),
+		_ => Err(DeserializeError::UnsupportedVersion { version }.raise()),
+	}
+}
+/// Add the concrete type's name, if it was serialized, to an error.
+fn diagnose<E: serde::de::Error>(error: E, type_name: Option<&str>) -> E {
+	match type_name {
+		Some(type_name) => E::custom(format_args!("{error}; the payload was a `{type_name}`")),
+		None => error,
+	}
+}
+
+/// The validation data and vtable offset of [`Encoding::Bytes`], serialized with `serialize_bytes`: the format version, the target's [architecture](ARCHES), byte order and pointer width in bits, the build id or the hash of the [schema version](compat), the type id of the trait object, the vtable offset and the type id of the concrete type mixed with the preceding validation data by a [`cipher::Cipher`], and the type id of the concrete type again, followed, with the `paranoid` feature enabled, by a [fingerprint](vtable_fingerprint) of the vtable, and with the `wide_type_id` feature enabled, by a [wide type id](wide_type_id) of the concrete type, the integers as little-endian `u64`s.
+///
+/// Mixing the vtable offset with the validation data means that corruption of any part of the header, rather than jumping to an arbitrary address, fails validation, as the two copies of the concrete type's type id no longer match.
+///
+/// The vtable offset is always 64 bits wide, whatever the pointer width, and the integers are always little-endian, whatever the byte order, such that a header from a different target can still be parsed, and rejected with a descriptive error rather than a generic build id mismatch.
+struct Header([u8; Header::LEN]);
+impl Header {
+	const LEN: usize = 1 + 3 + 16 + 8 + 16 + 8 + Self::FINGERPRINT_LEN + Self::WIDE_TYPE_ID_LEN;
+	/// The length of the [fingerprint](vtable_fingerprint) of the vtable, written with the `paranoid` feature enabled.
+	#[cfg(feature = "paranoid")]
+	const FINGERPRINT_LEN: usize = 8;
+	#[cfg(not(feature = "paranoid"))]
+	const FINGERPRINT_LEN: usize = 0;
+	/// The length of the [wide type id](wide_type_id) of the concrete type, written with the `wide_type_id` feature enabled.
+	#[cfg(feature = "wide_type_id")]
+	const WIDE_TYPE_ID_LEN: usize = 16;
+	#[cfg(not(feature = "wide_type_id"))]
+	const WIDE_TYPE_ID_LEN: usize = 0;
+	/// The length of the header with the tag that follows it when [authenticated](auth).
+	#[cfg(feature = "hmac")]
+	const MAX_LEN: usize = Self::LEN + auth::TAG_LEN;
+	#[cfg(not(feature = "hmac"))]
+	const MAX_LEN: usize = Self::LEN;
+
+	fn new<T: Serialize + ?Sized + 'static>(version: u8, t: &T) -> Self {
+		let trait_object: metatype::TraitObject = type_coerce(metatype::Type::meta(t));
+		let vtable: *const () = trait_object.vtable;
+		let type_id = <T as serialize::Sealed>::type_id(t);
+		let offset = vtable_offset(vtable);
+		let mut header = [0; Self::LEN];
+		header[0] = version;
+		header[1..4].copy_from_slice(&TARGET);
+		header[4..20].copy_from_slice(&compat::origin().to_be_bytes());
+		header[20..28].copy_from_slice(&metatype::type_id::<T>().to_le_bytes());
+		let [offset, mixed_type_id] = cipher::Cipher::new(&header[..28]).encrypt([offset, type_id]);
+		header[28..36].copy_from_slice(&offset.to_le_bytes());
+		header[36..44].copy_from_slice(&mixed_type_id.to_le_bytes());
+		header[44..52].copy_from_slice(&type_id.to_le_bytes());
+		#[cfg(feature = "paranoid")]
+		header[52..60].copy_from_slice(&vtable_fingerprint(vtable, 0).to_le_bytes());
+		#[cfg(feature = "wide_type_id")]
+		header[Self::LEN - Self::WIDE_TYPE_ID_LEN..].copy_from_slice(&wide_type_id(
+			<T as serialize::Sealed>::serialize_type_name(t),
+			type_id,
+			size_of_val(t),
+			align_of_val(t),
+		));
+		Self(header)
+	}
+
+	/// The format version, which is checked separately to [`parse`](Self::parse) so that the concrete type's name can be read first, if present.
+	fn version(&self) -> u8 {
+		self.0[0]
+	}
+
+	/// Validate the target, build id, trait object type id and integrity of the header, returning the vtable and the type id of the concrete type.
+	fn parse<T: ?Sized + 'static, E: serde::de::Error>(&self) -> Result<(Vtable<T>, u64), E> {
+		let u64_at = |i: usize| u64::from_le_bytes(self.0[i..i + 8].try_into().unwrap());
+		let trusted = trusted();
+		if !trusted {
+			check_target::<T, E>(self.0[1..4].try_into().unwrap())?;
+		}
+		let build = u128::from_be_bytes(self.0[4..20].try_into().unwrap());
+		let shift = builds::shift(build);
+		let compatible = shift.is_none() && compat::matches(build);
+		check_origin::<T, E>(shift.is_some() || compatible, u64_at(20))?;
+		let [offset, mixed_type_id] =
+			cipher::Cipher::new(&self.0[..28]).decrypt([u64_at(28), u64_at(36)]);
+		if !trusted && !constant_time::eq_u64(mixed_type_id, u64_at(44)) {
+			return Err(DeserializeError::Corrupt {
+				trait_object: type_name::<T>(),
+			}
+			.raise());
+		}
+		if compatible {
+			return Self::registered(build, u64_at(44)).map(|vtable| (vtable, u64_at(44)));
+		}
+		let shift = shift.unwrap_or(0);
+		let vtable = vtable_at(Some(build), shifted(offset, shift), Some(u64_at(44)))?;
+		#[cfg(feature = "paranoid")]
+		if !trusted && !constant_time::eq_u64(vtable_fingerprint(vtable.to(), shift), u64_at(52)) {
+			return Err(DeserializeError::VtableMismatch {
+				trait_object: type_name::<T>(),
+			}
+			.raise());
+		}
+		Ok((vtable, u64_at(44)))
+	}
+
+	/// Check the [wide type id](wide_type_id) of the concrete type, with the `wide_type_id` feature enabled, against that of `object`, recreated from the vtable returned by [`parse`](Self::parse).
+	#[cfg_attr(
+		not(feature = "wide_type_id"),
+		allow(clippy::unnecessary_wraps, clippy::unused_self)
+	)]
+	fn check_wide_type_id<T: Deserialize + ?Sized + 'static, E: serde::de::Error>(
+		&self, object: *const T,
+	) -> Result<(), E> {
+		#[cfg(feature = "wide_type_id")]
+		if !trusted() {
+			let trait_object: metatype::TraitObject = type_coerce(metatype::Type::meta(object));
+			let [_, size, align] =
+				unsafe { *ptr::from_ref(trait_object.vtable).cast::<[usize; 3]>() };
+			let wide_type_id = wide_type_id(
+				deserialize::Sealed::type_name(object),
+				object.type_id(),
+				size,
+				align,
+			);
+			if !constant_time::eq(&self.0[Self::LEN - Self::WIDE_TYPE_ID_LEN..], &wide_type_id) {
+				return Err(DeserializeError::ConcreteTypeIdMismatch {
+					trait_object: type_name::<T>(),
+				}
+				.raise());
+			}
+		}
+		#[cfg(not(feature = "wide_type_id"))]
+		let _ = object;
+		Ok(())
+	}
+
+	/// Resolve the vtable of a trait object from a build with the same [schema version](compat) from the [`tagged`] registry, by the type id of its concrete type, invoking the installed [`validate::Validator`], if any.
+	#[cfg_attr(not(feature = "audit"), allow(unused_variables))]
+	fn registered<T: ?Sized + 'static, E: serde::de::Error>(
+		build: u128, type_id: u64,
+	) -> Result<Vtable<T>, E> {
+		let result = tagged::vtable::<T>(type_id)
+			.ok_or_else(|| {
+				DeserializeError::Unregistered {
+					trait_object: type_name::<T>(),
+					type_id,
+				}
+				.raise()
+			})
+			.and_then(|vtable| {
+				check_validator::<T, E>(vtable_offset(vtable), type_id)?;
+				Ok(unsafe { Vtable::from(vtable) })
+			});
+		#[cfg(feature = "audit")]
+		audit::record(Some(build), None, Some(type_id), &result);
+		result
+	}
+}
+
+/// A 128-bit hash of the name, type id, size and alignment of a concrete type, the leading bytes of their SHA-256 digest, such that unlike its 64-bit type id, a collision with another type is effectively impossible.
+#[cfg(feature = "wide_type_id")]
+fn wide_type_id(type_name: &str, type_id: u64, size: usize, align: usize) -> [u8; 16] {
+	use sha2::{Digest, Sha256};
+	let digest = Sha256::new()
+		.chain_update(type_name.as_bytes())
+		.chain_update(type_id.to_le_bytes())
+		.chain_update((size as u64).to_le_bytes())
+		.chain_update((align as u64).to_le_bytes())
+		.finalize();
+	digest[..16].try_into().unwrap()
+}
+
+/// The architectures a [`Header`] can name, as given by [`std::env::consts::ARCH`], by their index. Any other architecture is written as [`u8::MAX`].
+const ARCHES: [&str; 24] = [
+	"x86",
+	"x86_64",
+	"arm",
+	"aarch64",
+	"m68k",
+	"csky",
+	"mips",
+	"mips32r6",
+	"mips64",
+	"mips64r6",
+	"powerpc",
+	"powerpc64",
+	"riscv32",
+	"riscv64",
+	"s390x",
+	"sparc",
+	"sparc64",
+	"loongarch64",
+	"hexagon",
+	"wasm32",
+	"wasm64",
+	"bpf",
+	"avr",
+	"msp430",
+];
+/// The width of pointers on this target, in bits.
+#[allow(clippy::cast_possible_truncation)]
+const POINTER_WIDTH: u8 = usize::BITS as u8;
+/// This target's architecture, as an index into [`ARCHES`], whether it's big-endian, and its [`POINTER_WIDTH`], as written in each [`Header`].
+#[allow(clippy::cast_possible_truncation)]
+const TARGET: [u8; 3] = {
+	let (mut arch, mut i) = (u8::MAX, 0);
+	while i < ARCHES.len() {
+		if ARCHES[i].eq_ignore_ascii_case(std::env::consts::ARCH) {
+			arch = i as u8;
+		}
+		i += 1;
+	}
+	[arch, cfg!(target_endian = "big") as u8, POINTER_WIDTH]
+};
+/// Check that a trait object was serialized on a target like this one, naming the difference if not.
+fn check_target<T: ?Sized + 'static, E: serde::de::Error>(target: [u8; 3]) -> Result<(), E> {
+	let arch = |arch: u8| ARCHES.get(usize::from(arch)).copied().unwrap_or("unknown");
+	let endian = |big: u8| {
+		if big == 0 {
+			"little-endian"
+		} else {
+			"big-endian"
+		}
+	};
+	let trait_object = type_name::<T>();
+	let error = match (target, TARGET) {
+		([a, ..], [b, ..]) if a != b => DeserializeError::ArchitectureMismatch {
+			trait_object,
+			theirs: arch(a),
+			ours: arch(b),
+		},
+		([_, a, _], [_, b, _]) if a != b => DeserializeError::ArchitectureMismatch {
+			trait_object,
+			theirs: endian(a),
+			ours: endian(b),
+		},
+		([.., a], [.., b]) if a != b => DeserializeError::PointerWidthMismatch {
+			trait_object,
+			theirs: a,
+			ours: b,
+		},
+		_ => return Ok(()),
+	};
+	Err(error.raise())
+}
+
+/// The address `relative` measures vtable offsets from.
+fn vtable_base() -> usize {
+	let base: *const (dyn any::Any + Sync) = relative::RELATIVE_VTABLE_BASE;
+	let base: metatype::TraitObject = type_coerce(metatype::Type::meta(base));
+	ptr::from_ref(base.vtable) as usize
+}
+/// The offset of `vtable` from [`vtable_base`], or from the [registered anchor](anchors::register) within the same loaded object, as wrapped by [`Vtable`], sign-extended to 64 bits such that it means the same on targets of any pointer width.
+fn vtable_offset(vtable: *const ()) -> u64 {
+	#[cfg(all(feature = "symbols", any(target_os = "linux", target_os = "android")))]
+	if let Some(offset) = symbols::offset(vtable as usize) {
+		return offset;
+	}
+	if let Some(offset) = anchors::offset(vtable as usize) {
+		return offset;
+	}
+	((vtable as usize).wrapping_sub(vtable_base()).cast_signed() as i64).cast_unsigned()
+}
+/// Shift a vtable offset from another binary into this one, by the shift [registered](builds::register) for it.
+fn shifted(offset: u64, shift: i64) -> u64 {
+	offset.wrapping_add(shift.cast_unsigned())
+}
+/// Recreate a [`Vtable`] from its offset, having validated the build it came from, as given by its build id if known and the shift to apply to its offset if it can be trusted, and the type id of the trait object, as [`Vtable`]'s own deserialization does.
+fn vtable_from_offset<T: ?Sized + 'static, E: serde::de::Error>(
+	build: Option<u128>, shift: Option<i64>, trait_type_id: u64, offset: u64,
+) -> Result<Vtable<T>, E> {
+	check_origin::<T, E>(shift.is_some(), trait_type_id)?;
+	vtable_at(build, shifted(offset, shift.unwrap_or(0)), None)
+}
+/// A hash of the entries every vtable starts with: the drop glue, size and alignment of the concrete type, with the drop glue taken as its offset, as given by [`vtable_offset`], such that the hash is the same across invocations of the binary, and unshifted by the `shift` of the binary the hash came from.
+///
+/// The vtable must lie within this binary, as checked by [`vtable_at`].
+#[cfg(feature = "paranoid")]
+fn vtable_fingerprint(vtable: *const (), shift: i64) -> u64 {
+	let entries = unsafe { *vtable.cast::<[usize; 3]>() };
+	let drop_glue = match entries[0] {
+		0 => 0,
+		drop_glue => vtable_offset(drop_glue as *const ()).wrapping_sub(shift.cast_unsigned()),
+	};
+	[drop_glue, entries[1] as u64, entries[2] as u64]
+		.iter()
+		.fold(0, |hash, &entry| cipher::mix(hash ^ entry))
+}
+/// Recreate a [`Vtable`] from its offset, which must have been validated, checking that it lies within this binary, and invoking the installed [`validate::Validator`], if any, if the type id of the concrete type is known. Otherwise it's left to the caller to invoke once it is, before the vtable is used. With the `audit` feature enabled, the installed [`audit::Sink`], if any, is then invoked with the outcome, and the build id of the binary it came from, if known.
+#[cfg_attr(not(feature = "audit"), allow(unused_variables))]
+fn vtable_at<T: ?Sized + 'static, E: serde::de::Error>(
+	build: Option<u128>, offset: u64, type_id: Option<u64>,
+) -> Result<Vtable<T>, E> {
+	let result = type_id
+		.map_or(Ok(()), |type_id| check_validator::<T, E>(offset, type_id))
+		.and_then(|()| {
+			let vtable = vtable_address(offset, !trusted()).ok_or_else(|| {
+				DeserializeError::InvalidOffset {
+					trait_object: type_name::<T>(),
+					offset,
+				}
+				.raise()
+			})?;
+			Ok(unsafe { Vtable::from(&*(vtable as *const ())) })
+		});
+	#[cfg(feature = "audit")]
+	audit::record(build, Some(offset), type_id, &result);
+	result
+}
+/// The address of the vtable at `offset`, if it resolves, and if `checked`, lies within this binary, aligned.
+fn vtable_address(offset: u64, checked: bool) -> Option<usize> {
+	// Every vtable starts with the drop glue, size and alignment of the concrete type.
+	let len = size_of::<[usize; 3]>();
+	#[cfg(not(all(feature = "symbols", any(target_os = "linux", target_os = "android"))))]
+	let vtable = {
+		let (base, vtable) = if anchors::anchored(offset) {
+			anchors::resolve(offset)?
+		} else {
+			let signed = isize::try_from(offset.cast_signed()).ok()?;
+			let base = vtable_base();
+			(base, base.wrapping_add_signed(signed))
+		};
+		if checked && !image::contains(base, vtable, len) {
+			return None;
+		}
+		vtable
+	};
+	#[cfg(all(feature = "symbols", any(target_os = "linux", target_os = "android")))]
+	let vtable = symbols::resolve(offset, if checked { len } else { 0 })?;
+	(!checked || vtable % align_of::<usize>() == 0).then_some(vtable)
+}
+/// Invoke the installed [`validate::Validator`], if any, on a trait object about to be reconstructed.
+fn check_validator<T: ?Sized + 'static, E: serde::de::Error>(
+	offset: u64, type_id: u64,
+) -> Result<(), E> {
+	let concrete = validate::scoped_type_name();
+	let info = validate::HeaderInfo::new::<T>(offset, Some(type_id), concrete.as_deref());
+	validate::validate(&info).map_err(|error| {
+		DeserializeError::Rejected {
+			trait_object: type_name::<T>(),
+			error,
+		}
+		.raise()
+	})
+}
+/// Check that the concrete type with id `type_id` is [allowed](validate::allow) for `T` trait objects, and is that of the trait object being replaced by [`deserialize_in_place`], if any.
+fn check_allowed<T: ?Sized + 'static, E: serde::de::Error>(type_id: u64) -> Result<(), E> {
+	// Only the outermost trait object is the one being replaced.
+	if let Some(replacing) = REPLACING.with(std::cell::Cell::take) {
+		if type_id != replacing {
+			return Err(serde::de::Error::custom(format_args!(
+				"the deserialized \"{}\" has a different concrete type to the value it's replacing",
+				type_name::<T>()
+			)));
+		}
+	}
+	if !validate::allowed::<T>(type_id) {
+		return Err(DeserializeError::Disallowed {
+			trait_object: type_name::<T>(),
+			type_id,
+		}
+		.raise());
+	}
+	Ok(())
+}
+/// Invoke the installed [`hook::Hook`], if any, on a trait object just deserialized.
+fn notify_deserialize<T: Deserialize + ?Sized + 'static, E: fmt::Display>(
+	result: &Result<*mut T, E>,
+) {
+	let Some(hook) = hook::hook() else {
+		return;
+	};
+	match result {
+		Ok(t) => {
+			let trait_object: metatype::TraitObject =
+				type_coerce(metatype::Type::meta(t.cast_const()));
+			let type_id = deserialize::Sealed::type_id(t.cast_const());
+			let info = validate::HeaderInfo::new::<T>(
+				vtable_offset(trait_object.vtable),
+				Some(type_id),
+				None,
+			);
+			hook.on_deserialize(type_name::<T>(), Ok(&info));
+		}
+		Err(error) => {
+			hook.on_deserialize(type_name::<T>(), Err(&DeserializeError::failed(error)));
+		}
+	}
+}
+/// Reject a trait object in an encoding that can't be authenticated, if a key is installed with [`auth`].
+#[cfg_attr(not(feature = "hmac"), allow(clippy::unnecessary_wraps))]
+fn check_unauthenticated<E: serde::de::Error>() -> Result<(), E> {
+	#[cfg(feature = "hmac")]
+	if auth::enabled() {
+		return Err(DeserializeError::Unauthenticated.raise());
+	}
+	Ok(())
+}
+/// Whether validation is skipped, as [`trusted`] mode has been entered.
+#[inline]
+fn trusted() -> bool {
+	#[cfg(feature = "trusted")]
+	return trusted::enabled();
+	#[cfg(not(feature = "trusted"))]
+	false
+}
+/// Check that a trait object was serialized by this binary, as a `T`.
+fn check_origin<T: ?Sized + 'static, E: serde::de::Error>(
+	same_build: bool, trait_type_id: u64,
+) -> Result<(), E> {
+	if trusted() {
+		return Ok(());
+	}
+	if !same_build {
+		return Err(DeserializeError::BuildIdMismatch {
+			trait_object: type_name::<T>(),
+		}
+		.raise());
+	}
+	if !constant_time::eq_u64(trait_type_id, metatype::type_id::<T>()) {
+		return Err(DeserializeError::TraitTypeIdMismatch {
+			trait_object: type_name::<T>(),
+		}
+		.raise());
+	}
+	Ok(())
+}
+
+/// A [`Vtable`] that, for human-readable formats, is written with its type id and offset as [`Hex`] strings. Both forms are accepted from human-readable formats.
+struct RelativeVtable<T: ?Sized>(Vtable<T>, u64);
+impl<T: ?Sized + 'static> serde::ser::Serialize for RelativeVtable<T> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		if serializer.is_human_readable() {
+			serde::ser::Serialize::serialize(
+				&(build_id::get(), Hex(metatype::type_id::<T>()), Hex(self.1)),
+				serializer,
+			)
+		} else {
+			serde::ser::Serialize::serialize(&self.0, serializer)
+		}
+	}
+}
+impl<'de, T: ?Sized + 'static> serde::de::Deserialize<'de> for RelativeVtable<T> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		if deserializer.is_human_readable() {
+			let (build, Hex(trait_type_id), Hex(offset)) =
+				<(String, Hex, Hex) as serde::de::Deserialize>::deserialize(deserializer)?;
+			let build = builds::parse(&build);
+			let shift = build.and_then(builds::shift);
+			let vtable = vtable_from_offset(build, shift, trait_type_id, offset)?;
+			Ok(Self(vtable, shifted(offset, shift.unwrap_or(0))))
+		} else {
+			let result = <Vtable<T> as serde::de::Deserialize>::deserialize(deserializer);
+			#[cfg(feature = "audit")]
+			audit::record(
+				result.as_ref().ok().map(|_| build_id::get().as_u128()),
+				result
+					.as_ref()
+					.ok()
+					.map(|vtable| vtable_offset(vtable.to())),
+				None,
+				&result,
+			);
+			let vtable = result?;
+			Ok(Self(vtable, vtable_offset(vtable.to())))
+		}
+	}
+}
+
+/// A `u64` that, for human-readable formats, is written as a fixed-width hex string, as integers beyond 2<sup>53</sup> lose precision in some consumers of JSON. Both forms are accepted from human-readable formats.
+struct Hex(u64);
+impl serde::ser::Serialize for Hex {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		if serializer.is_human_readable() {
+			serializer.collect_str(&format_args!("{:016x}", self.0))
+		} else {
+			serializer.serialize_u64(self.0)
+		}
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for Hex {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl serde::de::Visitor<'_> for Visitor {
+			type Value = Hex;
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				formatter.write_str("a u64, or a 16 digit hex string")
+			}
+			fn visit_u64<E>(self, v: u64) -> Result<Hex, E>
+			where
+				E: serde::de::Error,
+			{
+				Ok(Hex(v))
+			}
+			fn visit_str<E>(self, v: &str) -> Result<Hex, E>
+			where
+				E: serde::de::Error,
+			{
+				if v.len() != 16 {
+					return Err(E::invalid_length(v.len(), &self));
+				}
+				u64::from_str_radix(v, 16)
+					.map(Hex)
+					.map_err(|_| E::invalid_value(serde::de::Unexpected::Str(v), &self))
+			}
+		}
+		if deserializer.is_human_readable() {
+			deserializer.deserialize_any(Visitor)
+		} else {
+			deserializer.deserialize_u64(Visitor)
+		}
+	}
+}
+impl serde::ser::Serialize for Header {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		if let Some(index) = dedup::intern(&self.0) {
+			let mut backref = [FORMAT_VERSION_BACKREF; 5];
+			backref[1..].copy_from_slice(&index.to_le_bytes());
+			return serializer.serialize_bytes(&backref);
+		}
+		#[cfg(feature = "hmac")]
+		if let Some(tag) = auth::sign(&self.0) {
+			let mut authenticated = [0; Header::MAX_LEN];
+			authenticated[..Header::LEN].copy_from_slice(&self.0);
+			authenticated[Header::LEN..].copy_from_slice(&tag);
+			return serializer.serialize_bytes(&authenticated);
+		}
+		serializer.serialize_bytes(&self.0)
+	}
+}
+impl<'de> serde::de::Deserialize<'de> for Header {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl<'de> serde::de::Visitor<'de> for Visitor {
+			type Value = Header;
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				write!(formatter, "a {} byte trait object header", Header::LEN)
+			}
+			fn visit_bytes<E>(self, v: &[u8]) -> Result<Header, E>
+			where
+				E: serde::de::Error,
+			{
+				#[cfg(feature = "hmac")]
+				let (v, tag) = if v.len() == Header::MAX_LEN {
+					(&v[..Header::LEN], Some(&v[Header::LEN..]))
+				} else {
+					(v, None)
+				};
+				if let Ok(header) = <[u8; Header::LEN]>::try_from(v) {
+					#[cfg(feature = "hmac")]
+					auth::verify(&header, tag).map_err(DeserializeError::raise)?;
+					dedup::record(&header);
+					return Ok(Header(header));
+				}
+				match v {
+					[FORMAT_VERSION_BACKREF, index @ ..] if index.len() == 4 => {
+						let index = u32::from_le_bytes(index.try_into().unwrap());
+						dedup::resolve(index)
+							.map(Header)
+							.ok_or_else(|| DeserializeError::MissingBackReference { index }.raise())
+					}
+					_ => Err(E::invalid_length(v.len(), &self)),
+				}
+			}
+			fn visit_seq<A>(self, mut seq: A) -> Result<Header, A::Error>
+			where
+				A: serde::de::SeqAccess<'de>,
+			{
+				let (mut header, mut len) = ([0; Header::MAX_LEN], 0);
+				while let Some(byte) = seq.next_element()? {
+					if len == Header::MAX_LEN {
+						return Err(serde::de::Error::invalid_length(Header::MAX_LEN + 1, &self));
+					}
+					header[len] = byte;
+					len += 1;
+				}
+				self.visit_bytes(&header[..len])
+			}
+		}
+		deserializer.deserialize_bytes(Visitor)
 	}
 }
 
@@ -349,13 +1324,13 @@ mod deserialize {
 /// concrete, slices and traitobjects.
 struct Serializer<T: Serialize + ?Sized + 'static>(marker::PhantomData<fn(T)>);
 trait SerializerTrait<T: Serialize + ?Sized> {
-	fn serialize<S>(t: &T, serializer: S) -> Result<S::Ok, S::Error>
+	fn serialize<S>(t: &T, serializer: S, encoding: Encoding) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer;
 }
 impl<T: Serialize> SerializerTrait<T> for Serializer<T> {
 	#[inline]
-	fn serialize<S>(t: &T, serializer: S) -> Result<S::Ok, S::Error>
+	fn serialize<S>(t: &T, serializer: S, _encoding: Encoding) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
 	{
@@ -364,7 +1339,7 @@ impl<T: Serialize> SerializerTrait<T> for Serializer<T> {
 }
 impl SerializerTrait<str> for Serializer<str> {
 	#[inline]
-	fn serialize<S>(t: &str, serializer: S) -> Result<S::Ok, S::Error>
+	fn serialize<S>(t: &str, serializer: S, _encoding: Encoding) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
 	{
@@ -373,7 +1348,7 @@ impl SerializerTrait<str> for Serializer<str> {
 }
 impl<T: serde::ser::Serialize> SerializerTrait<[T]> for Serializer<[T]> {
 	#[inline]
-	fn serialize<S>(t: &[T], serializer: S) -> Result<S::Ok, S::Error>
+	fn serialize<S>(t: &[T], serializer: S, _encoding: Encoding) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
 	{
@@ -382,7 +1357,7 @@ impl<T: serde::ser::Serialize> SerializerTrait<[T]> for Serializer<[T]> {
 }
 impl<T: Serialize + ?Sized + 'static> SerializerTrait<T> for Serializer<T> {
 	#[inline]
-	default fn serialize<S>(t: &T, serializer: S) -> Result<S::Ok, S::Error>
+	default fn serialize<S>(t: &T, serializer: S, encoding: Encoding) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
 	{
@@ -390,28 +1365,90 @@ impl<T: Serialize + ?Sized + 'static> SerializerTrait<T> for Serializer<T> {
 			let trait_object: metatype::TraitObject = type_coerce(metatype::Type::meta(t));
 			trait_object.vtable
 		} else {
-			panic!()
+			return Err(serde::ser::Error::custom(format_args!(
+				"\"{}\" isn't a trait object",
+				type_name::<T>()
+			)));
 		};
-		let mut tup = serializer.serialize_tuple(3)?;
 		// We're making the assumption that the vtable is positioned the same
 		// relative to the base vtable in every invocation, through e.g. being
 		// in the same segment, or the binary being statically linked.
 		//
 		// See the [`relative`](https://github.com/alecmocatta/relative) crate
 		// for more information.
-		tup.serialize_element::<Vtable<T>>(&unsafe { Vtable::<T>::from(vtable) })?;
-		tup.serialize_element::<u64>(&<T as serialize::Sealed>::type_id(t))?;
-		tup.serialize_element::<SerializeErased<T>>(&SerializeErased(t))?;
-		tup.end()
+		let type_id = <T as serialize::Sealed>::type_id(t);
+		if let Some(hook) = hook::hook().filter(|_| !measuring()) {
+			hook.on_serialize(&validate::HeaderInfo::new::<T>(
+				vtable_offset(vtable),
+				Some(type_id),
+				Some(<T as serialize::Sealed>::serialize_type_name(t)),
+			));
+		}
+		let type_name =
+			cfg!(feature = "diagnostics").then(|| <T as serialize::Sealed>::serialize_type_name(t));
+		let version = match type_name {
+			Some(_) => FORMAT_VERSION_DIAGNOSTICS,
+			None => FORMAT_VERSION,
+		};
+		let len = usize::from(type_name.is_some());
+		match encoding.resolve(serializer.is_human_readable()) {
+			Encoding::Auto => unreachable!(),
+			Encoding::Bytes => {
+				let header = Header::new(version, t);
+				serialize_with_header(serializer, &header, type_name, t)
+			}
+			#[cfg(feature = "msgpack")]
+			Encoding::MessagePack => {
+				let header = msgpack::Ext(Header::new(version, t));
+				serialize_with_header(serializer, &header, type_name, t)
+			}
+			Encoding::Batched => {
+				let mut tup = serializer.serialize_tuple(3)?;
+				tup.serialize_element::<u64>(&vtable_offset(vtable))?;
+				tup.serialize_element::<u64>(&type_id)?;
+				tup.serialize_element::<SerializeErased<T>>(&SerializeErased(t))?;
+				tup.end()
+			}
+			Encoding::Named => {
+				let vtable =
+					RelativeVtable(unsafe { Vtable::<T>::from(vtable) }, vtable_offset(vtable));
+				let type_id = Hex(type_id);
+				let mut state = serializer.serialize_struct("TraitObject", len + 4)?;
+				state.serialize_field::<u8>(FIELDS[0], &version)?;
+				if let Some(type_name) = type_name {
+					state.serialize_field::<str>(FIELDS[1], type_name)?;
+				}
+				state.serialize_field::<RelativeVtable<T>>(FIELDS[2], &vtable)?;
+				state.serialize_field::<Hex>(FIELDS[3], &type_id)?;
+				state.serialize_field::<SerializeErased<T>>(FIELDS[4], &SerializeErased(t))?;
+				state.end()
+			}
+		}
 	}
 }
+/// Serialize the tuple of [`Encoding::Bytes`], with the header wrapped as needed by the encoding.
+fn serialize_with_header<T: Serialize + ?Sized + 'static, H: serde::ser::Serialize, S>(
+	serializer: S, header: &H, type_name: Option<&str>, t: &T,
+) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	let mut tup = serializer.serialize_tuple(usize::from(type_name.is_some()) + 2)?;
+	tup.serialize_element::<H>(header)?;
+	if let Some(type_name) = type_name {
+		tup.serialize_element::<str>(type_name)?;
+	}
+	tup.serialize_element::<SerializeErased<T>>(&SerializeErased(t))?;
+	tup.end()
+}
 struct SerializeErased<'a, T: Serialize + ?Sized + 'a>(&'a T);
-impl<'a, T: Serialize + ?Sized> serde::ser::Serialize for SerializeErased<'a, T> {
+impl<T: Serialize + ?Sized> serde::ser::Serialize for SerializeErased<'_, T> {
 	#[inline]
 	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
 	where
 		S: serde::Serializer,
 	{
+		serialize::Sealed::pre_serialize(self.0);
 		erased_serde::serialize(self.0, serializer)
 	}
 }
@@ -420,78 +1457,289 @@ impl<'a, T: Serialize + ?Sized> serde::ser::Serialize for SerializeErased<'a, T>
 /// concrete, slices and traitobjects.
 struct Deserializer<T: Deserialize + ?Sized + 'static>(marker::PhantomData<T>);
 trait DeserializerTrait<T: Deserialize + ?Sized> {
-	fn deserialize<'de, D>(deserializer: D) -> Result<boxed::Box<T>, D::Error>
+	fn deserialize<'de, D>(deserializer: D, encoding: Encoding) -> Result<boxed::Box<T>, D::Error>
+	where
+		D: serde::Deserializer<'de>;
+	fn deserialize_in<'de, D>(
+		deserializer: D, place: &mut deserialize::Place<'_>, encoding: Encoding,
+	) -> Result<*mut T, D::Error>
 	where
 		D: serde::Deserializer<'de>;
 }
 impl<T: Deserialize> DeserializerTrait<T> for Deserializer<T> {
 	#[inline]
-	fn deserialize<'de, D>(deserializer: D) -> Result<boxed::Box<T>, D::Error>
+	fn deserialize<'de, D>(deserializer: D, _encoding: Encoding) -> Result<boxed::Box<T>, D::Error>
 	where
 		D: serde::Deserializer<'de>,
 	{
 		<T as deserialize::Sealed>::deserialize_box(deserializer)
 	}
+	#[inline]
+	fn deserialize_in<'de, D>(
+		deserializer: D, place: &mut deserialize::Place<'_>, _encoding: Encoding,
+	) -> Result<*mut T, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		<T as deserialize::Sealed>::deserialize_in(deserializer, place)
+	}
 }
 impl DeserializerTrait<str> for Deserializer<str> {
 	#[inline]
-	fn deserialize<'de, D>(deserializer: D) -> Result<boxed::Box<str>, D::Error>
+	fn deserialize<'de, D>(
+		deserializer: D, _encoding: Encoding,
+	) -> Result<boxed::Box<str>, D::Error>
 	where
 		D: serde::Deserializer<'de>,
 	{
 		serde::de::Deserialize::deserialize(deserializer)
 	}
+	#[inline]
+	fn deserialize_in<'de, D>(
+		deserializer: D, place: &mut deserialize::Place<'_>, encoding: Encoding,
+	) -> Result<*mut str, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		Self::deserialize(deserializer, encoding).map(|t| deserialize::unbox(t, place))
+	}
 }
 impl<T: serde::de::DeserializeOwned> DeserializerTrait<[T]> for Deserializer<[T]> {
 	#[inline]
-	fn deserialize<'de, D>(deserializer: D) -> Result<boxed::Box<[T]>, D::Error>
+	fn deserialize<'de, D>(
+		deserializer: D, _encoding: Encoding,
+	) -> Result<boxed::Box<[T]>, D::Error>
 	where
 		D: serde::Deserializer<'de>,
 	{
 		serde::de::Deserialize::deserialize(deserializer)
 	}
+	#[inline]
+	fn deserialize_in<'de, D>(
+		deserializer: D, place: &mut deserialize::Place<'_>, encoding: Encoding,
+	) -> Result<*mut [T], D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		Self::deserialize(deserializer, encoding).map(|t| deserialize::unbox(t, place))
+	}
 }
 impl<T: Deserialize + ?Sized + 'static> DeserializerTrait<T> for Deserializer<T> {
 	#[inline]
-	default fn deserialize<'de, D>(deserializer: D) -> Result<boxed::Box<T>, D::Error>
+	default fn deserialize<'de, D>(
+		deserializer: D, encoding: Encoding,
+	) -> Result<boxed::Box<T>, D::Error>
 	where
 		D: serde::Deserializer<'de>,
 	{
-		struct Visitor<T: Deserialize + ?Sized>(marker::PhantomData<T>);
-		impl<'de, T: Deserialize + ?Sized + 'static> serde::de::Visitor<'de> for Visitor<T> {
-			type Value = boxed::Box<T>;
-			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-				write!(formatter, "a \"{}\" trait object", type_name::<T>())
-			}
-			#[inline]
-			fn visit_seq<A>(self, mut seq: A) -> Result<boxed::Box<T>, A::Error>
-			where
-				A: serde::de::SeqAccess<'de>,
-			{
-				let t0: Vtable<T> = match seq.next_element()? {
-					Some(value) => value,
-					None => return Err(serde::de::Error::invalid_length(0, &self)),
-				};
-				let t1: u64 = match seq.next_element()? {
-					Some(value) => value,
-					None => return Err(serde::de::Error::invalid_length(1, &self)),
-				};
-				let meta = metatype::TraitObject { vtable: t0.to() };
-				let object: *const T = metatype::Type::dangling(type_coerce(meta)).as_ptr();
-				assert_eq!(t1, object.type_id(), "Deserializing the trait object \"{}\" failed in a way that should never happen. Please file an issue! https://github.com/alecmocatta/serde_traitobject/issues/new", type_name::<T>());
-				let t2: boxed::Box<T> = match seq.next_element_seed(DeserializeErased(object))? {
-					Some(value) => value,
-					None => return Err(serde::de::Error::invalid_length(2, &self)),
+		Self::deserialize_in(deserializer, &mut deserialize::heap, encoding)
+			.map(|raw| unsafe { boxed::Box::from_raw(raw) })
+	}
+	#[inline]
+	default fn deserialize_in<'de, D>(
+		deserializer: D, place: &mut deserialize::Place<'_>, encoding: Encoding,
+	) -> Result<*mut T, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		DeserializeError::clear();
+		let encoding = encoding.resolve(deserializer.is_human_readable());
+		let visitor = TraitObjectVisitor(place, encoding, marker::PhantomData);
+		// The named encoding is read from either a map or a sequence, as
+		// non-self-describing formats write structs as sequences.
+		let result = depth::nested::<T, _, _, _>(|| match encoding {
+			Encoding::Auto => unreachable!(),
+			Encoding::Bytes => deserializer.deserialize_tuple(3, visitor),
+			#[cfg(feature = "msgpack")]
+			Encoding::MessagePack => deserializer.deserialize_tuple(3, visitor),
+			Encoding::Batched => deserializer.deserialize_tuple(3, visitor),
+			Encoding::Named => deserializer.deserialize_struct("TraitObject", &FIELDS, visitor),
+		});
+		notify_deserialize(&result);
+		if result.is_ok() {
+			DeserializeError::clear();
+		}
+		result
+	}
+}
+/// Visits the tuple, named and bytes encodings of a trait object, deserializing it into the `Place`.
+struct TraitObjectVisitor<'a, 'b, T: Deserialize + ?Sized>(
+	&'a mut deserialize::Place<'b>,
+	Encoding,
+	marker::PhantomData<T>,
+);
+impl<'de, T: Deserialize + ?Sized + 'static> serde::de::Visitor<'de>
+	for TraitObjectVisitor<'_, '_, T>
+{
+	type Value = *mut T;
+	fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+		write!(formatter, "a \"{}\" trait object", type_name::<T>())
+	}
+	#[inline]
+	fn visit_seq<A>(self, mut seq: A) -> Result<*mut T, A::Error>
+	where
+		A: serde::de::SeqAccess<'de>,
+	{
+		if let Encoding::Batched = self.1 {
+			check_unauthenticated::<A::Error>()?;
+			let Some(offset) = seq.next_element()? else {
+				return Err(serde::de::Error::invalid_length(0, &self));
+			};
+			let Some(t1) = seq.next_element()? else {
+				return Err(serde::de::Error::invalid_length(1, &self));
+			};
+			let t0 = vtable_from_offset(
+				batch::build(),
+				Some(batch::shift()),
+				metatype::type_id::<T>(),
+				offset,
+			)?;
+			let t0 = RelativeVtable(t0, shifted(offset, batch::shift()));
+			let object = validated_object(&t0, t1, None)?;
+			return match seq.next_element_seed(DeserializeErased(object, self.0))? {
+				Some(value) => Ok(value),
+				None => Err(serde::de::Error::invalid_length(2, &self)),
+			};
+		}
+		if self.1 != Encoding::Named {
+			let header = match self.1 {
+				#[cfg(feature = "msgpack")]
+				Encoding::MessagePack => seq.next_element::<msgpack::Ext<Header>>()?.map(|ext| ext.0),
+				_ => seq.next_element::<Header>()?,
+			};
+			let Some(header) = header else {
+				return Err(serde::de::Error::invalid_length(0, &self));
+			};
+			let type_name: Option<String> = if check_version(header.version())? {
+				let Some(type_name) = seq.next_element()? else {
+					return Err(serde::de::Error::invalid_length(1, &self));
 				};
-				Ok(t2)
+				Some(type_name)
+			} else {
+				None
+			};
+			let i = usize::from(type_name.is_some());
+			let (t0, t1) = validate::with_type_name(type_name.as_deref(), || header.parse())
+				.map_err(|e| diagnose(e, type_name.as_deref()))?;
+			let object = trait_object(t0, t1)?;
+			header.check_wide_type_id(object)?;
+			return match seq.next_element_seed(DeserializeErased(object, self.0))? {
+				Some(value) => Ok(value),
+				None => Err(serde::de::Error::invalid_length(i + 1, &self)),
+			};
+		}
+		// The named encoding can't be authenticated, whether it's read from a sequence or a map.
+		check_unauthenticated::<A::Error>()?;
+		let Some(version) = seq.next_element()? else {
+			return Err(serde::de::Error::invalid_length(0, &self));
+		};
+		let type_name: Option<String> = if check_version(version)? {
+			let Some(type_name) = seq.next_element()? else {
+				return Err(serde::de::Error::invalid_length(1, &self));
+			};
+			Some(type_name)
+		} else {
+			None
+		};
+		let i = usize::from(type_name.is_some());
+		let t0 = validate::with_type_name(type_name.as_deref(), || seq.next_element())
+			.map_err(|e| diagnose(e, type_name.as_deref()))?;
+		let Some(t0) = t0 else {
+			return Err(serde::de::Error::invalid_length(i + 1, &self));
+		};
+		let Some(Hex(t1)) = seq.next_element()? else {
+			return Err(serde::de::Error::invalid_length(i + 2, &self));
+		};
+		let object = validated_object(&t0, t1, type_name.as_deref())?;
+		let t2: *mut T = match seq.next_element_seed(DeserializeErased(object, self.0))? {
+			Some(value) => value,
+			None => return Err(serde::de::Error::invalid_length(i + 3, &self)),
+		};
+		Ok(t2)
+	}
+	#[inline]
+	fn visit_map<A>(self, mut map: A) -> Result<*mut T, A::Error>
+	where
+		A: serde::de::MapAccess<'de>,
+	{
+		check_unauthenticated::<A::Error>()?;
+		let (mut version, mut type_name, mut t0, mut t1): (
+			Option<u8>,
+			Option<String>,
+			Option<RelativeVtable<T>>,
+			Option<u64>,
+		) = (None, None, None, None);
+		while let Some(field) = map.next_key::<Field>()? {
+			match field {
+				Field::Version if version.is_none() => {
+					let v = map.next_value()?;
+					let _ = check_version(v)?;
+					version = Some(v);
+				}
+				Field::TypeName if type_name.is_none() => type_name = Some(map.next_value()?),
+				Field::Vtable if t0.is_none() => {
+					let vtable = validate::with_type_name(type_name.as_deref(), || {
+						map.next_value::<RelativeVtable<T>>()
+					})
+					.map_err(|e| diagnose(e, type_name.as_deref()))?;
+					t0 = Some(vtable);
+				}
+				Field::TypeId if t1.is_none() => t1 = Some(map.next_value::<Hex>()?.0),
+				Field::Value => {
+					let object = match (version, t0, t1) {
+						(Some(_), Some(t0), Some(t1)) => {
+							validated_object(&t0, t1, type_name.as_deref())?
+						}
+						(None, _, _) => return Err(serde::de::Error::missing_field(FIELDS[0])),
+						(_, None, _) => return Err(serde::de::Error::missing_field(FIELDS[2])),
+						(_, _, None) => return Err(serde::de::Error::missing_field(FIELDS[3])),
+					};
+					let t2: *mut T = map.next_value_seed(DeserializeErased(object, self.0))?;
+					return Ok(t2);
+				}
+				Field::Version | Field::TypeName | Field::Vtable | Field::TypeId => {
+					return Err(serde::de::Error::duplicate_field(FIELDS[field as usize]))
+				}
 			}
 		}
-		deserializer.deserialize_tuple(3, Visitor(marker::PhantomData))
+		Err(serde::de::Error::missing_field(FIELDS[4]))
 	}
 }
-struct DeserializeErased<T: Deserialize + ?Sized>(*const T);
-impl<'de, T: Deserialize + ?Sized> serde::de::DeserializeSeed<'de> for DeserializeErased<T> {
-	type Value = boxed::Box<T>;
+/// A dangling trait object with the given vtable, checking it's of the concrete type `t1`.
+fn trait_object<T: Deserialize + ?Sized + 'static, E: serde::de::Error>(
+	t0: Vtable<T>, t1: u64,
+) -> Result<*const T, E> {
+	let object = dangling(t0);
+	if !trusted() && !constant_time::eq_u64(t1, object.type_id()) {
+		return Err(DeserializeError::ConcreteTypeIdMismatch {
+			trait_object: type_name::<T>(),
+		}
+		.raise());
+	}
+	check_allowed::<T, E>(object.type_id())?;
+	Ok(object)
+}
+/// A dangling trait object with the given vtable, read from the named encoding or a [`batch::Batch`], checking it's of the concrete type `t1`.
+///
+/// The vtable is read before the type id in these encodings, so the installed [`validate::Validator`], if any, is invoked here, once the type id is known, rather than when the vtable is read.
+fn validated_object<T: Deserialize + ?Sized + 'static, E: serde::de::Error>(
+	RelativeVtable(t0, offset): &RelativeVtable<T>, t1: u64, type_name: Option<&str>,
+) -> Result<*const T, E> {
+	validate::with_type_name(type_name, || check_validator::<T, E>(*offset, t1))?;
+	trait_object(*t0, t1)
+}
+/// A dangling trait object with the given vtable.
+fn dangling<T: ?Sized>(vtable: Vtable<T>) -> *const T {
+	let meta = metatype::TraitObject {
+		vtable: vtable.to(),
+	};
+	metatype::Type::dangling(type_coerce(meta)).as_ptr()
+}
+struct DeserializeErased<'a, 'b, T: Deserialize + ?Sized>(*const T, &'a mut deserialize::Place<'b>);
+impl<'de, T: Deserialize + ?Sized> serde::de::DeserializeSeed<'de>
+	for DeserializeErased<'_, '_, T>
+{
+	type Value = *mut T;
 
 	#[inline]
 	fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
@@ -499,12 +1747,49 @@ impl<'de, T: Deserialize + ?Sized> serde::de::DeserializeSeed<'de> for Deseriali
 		D: serde::de::Deserializer<'de>,
 	{
 		let deserializer = &mut <dyn erased_serde::Deserializer>::erase(deserializer);
-		deserialize::deserialize_erased(self.0, deserializer).map_err(serde::de::Error::custom)
+		deserialize::deserialize_erased(self.0, deserializer, self.1)
+			.map_err(|error| serde::de::Error::custom(DeserializeError::payload(error)))
+	}
+}
+
+/// Deserialize a `T` into memory provided by `place`, returning a pointer to it.
+///
+/// This lets wrappers that don't use the global allocator, like [`SmallBox`], reuse the trait object deserialization.
+pub(crate) fn deserialize_in<'de, T: Deserialize + ?Sized + 'static, D>(
+	deserializer: D, place: &mut deserialize::Place<'_>,
+) -> Result<*mut T, D::Error>
+where
+	D: serde::Deserializer<'de>,
+{
+	Deserializer::<T>::deserialize_in(deserializer, place, Encoding::Auto)
+}
+
+/// Serialize a `T` by reference, for pointers that don't implement `AsRef<T>`.
+pub(crate) fn serialize_ref<T: Serialize + ?Sized + 'static, S>(
+	t: &T, serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	Serializer::<T>::serialize(t, serializer, Encoding::Auto)
+}
+
+/// Wraps a `&T` so that it can be passed to serde methods expecting a `Serialize` value, like `serialize_some` or `serialize_element`.
+pub(crate) struct SerializeRef<'a, T: ?Sized>(pub(crate) &'a T);
+impl<T: Serialize + ?Sized + 'static> serde::ser::Serialize for SerializeRef<'_, T> {
+	#[inline]
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize_ref(self.0, serializer)
 	}
 }
 
 /// Serialize a value by reference.
 ///
+/// Trait objects are written as a struct with `version`, `vtable`, `type_id` and `value` fields for human-readable formats like JSON, and otherwise as a compact tuple of a fixed-width header and value.
+///
 /// This is intended to enable:
 /// ```
 /// # use serde_derive::{Serialize, Deserialize};
@@ -532,7 +1817,7 @@ pub fn serialize<T: Serialize + ?Sized + 'static, B: AsRef<T> + ?Sized, S>(
 where
 	S: serde::Serializer,
 {
-	Serializer::<T>::serialize(t.as_ref(), serializer)
+	Serializer::<T>::serialize(t.as_ref(), serializer, Encoding::Auto)
 }
 
 /// Deserialize a value `T` into `B` where `Box<T>: Into<B>`.
@@ -565,5 +1850,102 @@ where
 	D: serde::Deserializer<'de>,
 	boxed::Box<T>: Into<B>,
 {
-	Deserializer::<T>::deserialize(deserializer).map(<boxed::Box<T> as Into<B>>::into)
+	Deserializer::<T>::deserialize(deserializer, Encoding::Auto)
+		.map(<boxed::Box<T> as Into<B>>::into)
+}
+
+thread_local! {
+	/// The concrete type id of the trait object [`deserialize_in_place`] is replacing, if any.
+	static REPLACING: std::cell::Cell<Option<u64>> = const { std::cell::Cell::new(None) };
+}
+
+/// Deserialize a value into an existing `T`, reusing its allocation.
+///
+/// The deserialized value must have the same concrete type as (and, for slices, the same length as) the value it's replacing, otherwise an error is returned and `t` is left unchanged. On success the previous value is dropped.
+///
+/// For trait objects, the concrete type is checked from the header before the value is read. The value is then deserialized directly into `t`'s storage, with the previous value moved out beforehand and then either dropped, or moved back if the deserializer fails after the value is read, such that `t` holds a valid value even if that drop panics.
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// let mut worker: Box<dyn s::Debug> = Box::new(String::from("old"));
+///
+/// let new: s::Box<dyn s::Debug> = s::Box::new(String::from("new"));
+/// let serialized = serde_json::to_string(&new).unwrap();
+/// let mut deserializer = serde_json::Deserializer::from_str(&serialized);
+/// s::deserialize_in_place(&mut *worker, &mut deserializer).unwrap();
+/// assert_eq!(format!("{:?}", worker), "\"new\"");
+///
+/// let mismatched: s::Box<dyn s::Debug> = s::Box::new(123u8);
+/// let serialized = serde_json::to_string(&mismatched).unwrap();
+/// let mut deserializer = serde_json::Deserializer::from_str(&serialized);
+/// assert!(s::deserialize_in_place(&mut *worker, &mut deserializer).is_err());
+/// assert_eq!(format!("{:?}", worker), "\"new\"");
+/// ```
+pub fn deserialize_in_place<'de, T: Deserialize + ?Sized + 'static, D>(
+	t: &mut T, deserializer: D,
+) -> Result<(), D::Error>
+where
+	D: serde::Deserializer<'de>,
+{
+	struct Guard(Option<u64>);
+	impl Drop for Guard {
+		fn drop(&mut self) {
+			REPLACING.with(|replacing| replacing.set(self.0));
+		}
+	}
+	let old: *mut T = t;
+	let layout = alloc::Layout::for_value::<T>(t);
+	// The concrete type of anything other than a trait object is already known.
+	let replacing = matches!(
+		metatype::Type::meta_type(old.cast_const()),
+		metatype::MetaType::TraitObject
+	)
+	.then(|| deserialize::Sealed::type_id(old.cast_const()));
+	let _guard = Guard(REPLACING.with(|scoped| scoped.replace(replacing)));
+	let mut stash = None;
+	let result: Result<*mut T, D::Error> = deserialize_in(deserializer, &mut |new| {
+		if new != layout {
+			return deserialize::heap(new);
+		}
+		// Move the previous value out, such that the new one can be written over it.
+		let dst = deserialize::heap(layout);
+		unsafe { ptr::copy_nonoverlapping(old.cast::<u8>(), dst.as_ptr(), layout.size()) };
+		stash = Some(dst);
+		unsafe { ptr::NonNull::new_unchecked(old.cast::<u8>()) }
+	});
+	let new = match (result, stash) {
+		(Ok(new), _) => new,
+		(Err(error), None) => return Err(error),
+		// The new value was written over `t`, but the deserializer failed
+		// afterwards, e.g. on trailing input. Swap the previous value back in
+		// before dropping the new one, such that `t` is unchanged even if that
+		// drop panics.
+		(Err(error), Some(stash)) => {
+			unsafe {
+				ptr::swap_nonoverlapping(old.cast::<u8>(), stash.as_ptr(), layout.size());
+				drop(boxed::Box::<T>::from_raw(metatype::Type::fatten(
+					stash.as_ptr().cast(),
+					metatype::Type::meta(old.cast_const()),
+				)));
+			}
+			return Err(error);
+		}
+	};
+	let Some(stash) = stash else {
+		drop(unsafe { boxed::Box::from_raw(new) });
+		return Err(serde::de::Error::custom(format_args!(
+			"the deserialized \"{}\" has a different size to the value it's replacing",
+			type_name::<T>()
+		)));
+	};
+	drop(unsafe {
+		boxed::Box::<T>::from_raw(metatype::Type::fatten(
+			stash.as_ptr().cast(),
+			metatype::Type::meta(old.cast_const()),
+		))
+	});
+	Ok(())
 }
@@ -0,0 +1,75 @@
+//! (De)serialize a map whose values are trait object pointers, like `HashMap<K, Box<dyn Trait>>` or `BTreeMap<K, Box<dyn Trait>>`.
+//!
+//! The keys are (de)serialized normally.
+//!
+//! This is intended to enable:
+//! ```
+//! # use serde_derive::{Serialize, Deserialize};
+//! use std::collections::{BTreeMap, HashMap};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct MyStruct {
+//!     #[serde(with = "serde_traitobject::map")]
+//!     hash_map: HashMap<String, Box<dyn serde_traitobject::Any>>,
+//!     #[serde(with = "serde_traitobject::map")]
+//!     btree_map: BTreeMap<u64, Box<dyn serde_traitobject::Any>>,
+//! }
+//! ```
+
+use std::{boxed, fmt, marker};
+
+use super::{Box, Deserialize, Serialize, SerializeRef};
+
+/// Serialize a map with values `B` where `B: AsRef<T>`.
+pub fn serialize<'a, T: Serialize + ?Sized + 'static, K, B, M, S>(
+	t: &'a M, serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	&'a M: IntoIterator<Item = (&'a K, &'a B)>,
+	K: serde::ser::Serialize + 'a,
+	B: AsRef<T> + 'a,
+	S: serde::Serializer,
+{
+	serializer.collect_map(t.into_iter().map(|(k, v)| (k, SerializeRef(v.as_ref()))))
+}
+
+/// Deserialize a map with values `B` where `Box<T>: Into<B>`.
+pub fn deserialize<'de, T: Deserialize + ?Sized + 'static, K, B, M, D>(
+	deserializer: D,
+) -> Result<M, D::Error>
+where
+	K: serde::de::Deserialize<'de>,
+	boxed::Box<T>: Into<B>,
+	M: Default + Extend<(K, B)>,
+	D: serde::Deserializer<'de>,
+{
+	struct Visitor<T: ?Sized, K, B, M>(marker::PhantomData<fn(K, B) -> M>, marker::PhantomData<T>);
+	impl<'de, T: Deserialize + ?Sized + 'static, K, B, M> serde::de::Visitor<'de>
+		for Visitor<T, K, B, M>
+	where
+		K: serde::de::Deserialize<'de>,
+		boxed::Box<T>: Into<B>,
+		M: Default + Extend<(K, B)>,
+	{
+		type Value = M;
+
+		fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+			formatter.write_str("a map")
+		}
+
+		fn visit_map<A>(self, mut map: A) -> Result<Self::Value, A::Error>
+		where
+			A: serde::de::MapAccess<'de>,
+		{
+			let mut ret = M::default();
+			while let Some((k, v)) = map.next_entry::<K, Box<T>>()? {
+				ret.extend(Some((k, v.into_box().into())));
+			}
+			Ok(ret)
+		}
+	}
+	deserializer.deserialize_map(Visitor::<T, K, B, M>(
+		marker::PhantomData,
+		marker::PhantomData,
+	))
+}
@@ -0,0 +1,99 @@
+//! (De)serialize a trait object with its header as an msgpack ext type.
+//!
+//! With the `msgpack` feature enabled, this module writes a trait object in the [`bytes`](crate::bytes) encoding, but with the header wrapped in an msgpack ext type with the type code [`EXT_TYPE`], such that non-Rust middleware can recognize the header and forward it opaquely. It relies on the ext type support of `rmp-serde`, so it should only be used with `rmp-serde`.
+//!
+//! This is intended to enable:
+//! ```
+//! # use serde_derive::{Serialize, Deserialize};
+//! extern crate rmp_serde;
+//! extern crate serde_traitobject as s;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct MyStruct {
+//!     #[serde(with = "serde_traitobject::msgpack")]
+//!     field: Box<dyn serde_traitobject::Debug>,
+//! }
+//!
+//! let my_struct = MyStruct {
+//!     field: Box::new(String::from("ext")),
+//! };
+//! let serialized = rmp_serde::to_vec(&my_struct).unwrap();
+//! # assert!(serialized.windows(3).any(|w| w[0] == 0xc7 && w[2] == s::msgpack::EXT_TYPE as u8));
+//! let deserialized: MyStruct = rmp_serde::from_slice(&serialized).unwrap();
+//!
+//! println!("{:?}", deserialized.field);
+//! # assert_eq!(format!("{:?}", deserialized.field), "\"ext\"");
+//! // "ext"
+//! ```
+
+use std::{fmt, marker};
+
+use super::{
+	Deserialize, Deserializer, DeserializerTrait, Encoding, Serialize, Serializer, SerializerTrait
+};
+
+/// The msgpack ext type code the header is written with, from the range reserved for applications.
+pub const EXT_TYPE: i8 = 0x53;
+
+/// The name of the newtype struct `rmp-serde` (de)serializes as an ext type.
+const EXT_STRUCT_NAME: &str = "_ExtStruct";
+
+/// A value (de)serialized as the payload of an msgpack ext type with the type code [`EXT_TYPE`].
+pub(crate) struct Ext<T>(pub(crate) T);
+impl<T: serde::ser::Serialize> serde::ser::Serialize for Ext<T> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serializer.serialize_newtype_struct(EXT_STRUCT_NAME, &(EXT_TYPE, &self.0))
+	}
+}
+impl<'de, T: serde::de::Deserialize<'de>> serde::de::Deserialize<'de> for Ext<T> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		struct Visitor<T>(marker::PhantomData<fn() -> T>);
+		impl<'de, T: serde::de::Deserialize<'de>> serde::de::Visitor<'de> for Visitor<T> {
+			type Value = Ext<T>;
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				write!(formatter, "a MessagePack ext type {EXT_TYPE}")
+			}
+			fn visit_newtype_struct<D>(self, deserializer: D) -> Result<Ext<T>, D::Error>
+			where
+				D: serde::Deserializer<'de>,
+			{
+				let (ext_type, t) = <(i8, T) as serde::de::Deserialize>::deserialize(deserializer)?;
+				if ext_type != EXT_TYPE {
+					return Err(serde::de::Error::custom(format_args!(
+						"unexpected MessagePack ext type {ext_type}, expected {EXT_TYPE}"
+					)));
+				}
+				Ok(Ext(t))
+			}
+		}
+		deserializer.deserialize_newtype_struct(EXT_STRUCT_NAME, Visitor(marker::PhantomData))
+	}
+}
+
+/// Serialize a value by reference, with its header as an msgpack ext type.
+pub fn serialize<T: Serialize + ?Sized + 'static, B: AsRef<T> + ?Sized, S>(
+	t: &B, serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	Serializer::<T>::serialize(t.as_ref(), serializer, Encoding::MessagePack)
+}
+
+/// Deserialize a value `T` into `B` where `Box<T>: Into<B>`, with its header as an msgpack ext type.
+pub fn deserialize<'de, T: Deserialize + ?Sized + 'static, B, D>(
+	deserializer: D,
+) -> Result<B, D::Error>
+where
+	D: serde::Deserializer<'de>,
+	Box<T>: Into<B>,
+{
+	Deserializer::<T>::deserialize(deserializer, Encoding::MessagePack)
+		.map(<Box<T> as Into<B>>::into)
+}
@@ -0,0 +1,53 @@
+//! (De)serialize a trait object as a struct with named fields, whatever the format.
+//!
+//! By default this encoding is only used for human-readable formats, with binary formats getting a more compact tuple. Forcing it lets the trait object be used within `#[serde(flatten)]`, which requires flattened values to be maps.
+//!
+//! This is intended to enable:
+//! ```
+//! # use serde_derive::{Serialize, Deserialize};
+//! extern crate serde_json;
+//! extern crate serde_traitobject as s;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct MyStruct {
+//!     id: u64,
+//!     #[serde(flatten, with = "serde_traitobject::named")]
+//!     field: Box<dyn serde_traitobject::Debug>,
+//! }
+//!
+//! let my_struct = MyStruct {
+//!     id: 1,
+//!     field: Box::new(String::from("flattened")),
+//! };
+//! let serialized = serde_json::to_string(&my_struct).unwrap();
+//! let deserialized: MyStruct = serde_json::from_str(&serialized).unwrap();
+//!
+//! println!("{:?}", deserialized.field);
+//! # assert_eq!(format!("{:?}", deserialized.field), "\"flattened\"");
+//! // "flattened"
+//! ```
+
+use super::{
+	Deserialize, Deserializer, DeserializerTrait, Encoding, Serialize, Serializer, SerializerTrait
+};
+
+/// Serialize a value by reference, as a struct with named fields.
+pub fn serialize<T: Serialize + ?Sized + 'static, B: AsRef<T> + ?Sized, S>(
+	t: &B, serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	Serializer::<T>::serialize(t.as_ref(), serializer, Encoding::Named)
+}
+
+/// Deserialize a value `T` into `B` where `Box<T>: Into<B>`, from a struct with named fields.
+pub fn deserialize<'de, T: Deserialize + ?Sized + 'static, B, D>(
+	deserializer: D,
+) -> Result<B, D::Error>
+where
+	D: serde::Deserializer<'de>,
+	Box<T>: Into<B>,
+{
+	Deserializer::<T>::deserialize(deserializer, Encoding::Named).map(<Box<T> as Into<B>>::into)
+}
@@ -0,0 +1,69 @@
+//! (De)serialize an `Option` of a trait object pointer.
+//!
+//! This is intended to enable:
+//! ```
+//! # use serde_derive::{Serialize, Deserialize};
+//! use std::rc::Rc;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct MyStruct {
+//!     #[serde(with = "serde_traitobject::option")]
+//!     field: Option<Box<dyn serde_traitobject::Any>>,
+//!     #[serde(with = "serde_traitobject::option")]
+//!     rc: Option<Rc<dyn serde_traitobject::Any>>,
+//! }
+//! ```
+
+use std::{boxed, ops::Deref};
+
+use super::{Box, Deserialize, Serialize, SerializeRef};
+
+/// Serialize an `Option<B>` where `B: AsRef<T>`.
+pub fn serialize<T: Serialize + ?Sized + 'static, B: AsRef<T>, S>(
+	t: &Option<B>, serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	match t {
+		Some(t) => serializer.serialize_some(&SerializeRef(t.as_ref())),
+		None => serializer.serialize_none(),
+	}
+}
+
+/// Serialize an `Option` of a borrowed trait object, like `Option<&mut dyn Trait>`.
+///
+/// This accepts any `Option<R>` where `R: Deref<Target = T>`, and is intended to enable:
+/// ```
+/// # use serde_derive::Serialize;
+/// #[derive(Serialize)]
+/// struct MyStruct<'a> {
+///     #[serde(serialize_with = "serde_traitobject::option::serialize_ref")]
+///     field: Option<&'a mut (dyn serde_traitobject::Display + 'static)>,
+/// }
+/// ```
+///
+/// For a shared reference, prefer a field of `Option<Ref<'a, dyn Trait>>`, using [`Ref`](crate::Ref), which serializes without this. `serialize_with` passes the field by reference, and the `&Option<&T>` that results is flagged by clippy's `ref_option_ref`.
+pub fn serialize_ref<T: Serialize + ?Sized + 'static, R: Deref<Target = T>, S>(
+	t: &Option<R>, serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	match t {
+		Some(t) => serializer.serialize_some(&SerializeRef(&**t)),
+		None => serializer.serialize_none(),
+	}
+}
+
+/// Deserialize an `Option<B>` where `Box<T>: Into<B>`.
+pub fn deserialize<'de, T: Deserialize + ?Sized + 'static, B, D>(
+	deserializer: D,
+) -> Result<Option<B>, D::Error>
+where
+	D: serde::Deserializer<'de>,
+	boxed::Box<T>: Into<B>,
+{
+	<Option<Box<T>> as serde::de::Deserialize>::deserialize(deserializer)
+		.map(|t| t.map(|t| t.into_box().into()))
+}
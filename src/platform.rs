@@ -0,0 +1,83 @@
+//! A self-check that vtables can be (de)serialized on the current platform.
+//!
+//! Vtables are placed differently across platforms and linkers: in ELF objects on Linux, Mach-O images on macOS, and PE modules on Windows, where ASLR and incremental linking come into play. Where an unusual setup leaves vtable offsets unstable, trait objects would otherwise fail to deserialize, or resolve to the wrong vtable, only once they're exchanged. [`self_check`] is cheap enough to run at startup: it round-trips the vtables of some known trait objects in-process through the offsets they're serialized as, and returns a [`SelfCheckError`] describing what's wrong if they don't lie within the image they're taken relative to, or don't resolve back to themselves.
+//!
+//! # Example
+//! ```
+//! extern crate serde_traitobject as s;
+//!
+//! if let Err(error) = s::platform::self_check() {
+//!     panic!("trait objects can't be exchanged: {}", error);
+//! }
+//! ```
+
+use std::{any, env::consts::OS, error, fmt, ptr};
+
+use super::{image, type_coerce, vtable_address, vtable_base, vtable_offset};
+
+/// A type defined in this crate, such that its vtables are too.
+struct Known;
+static KNOWN: Known = Known;
+
+/// Check that the vtables of known trait objects can be serialized as their offsets, and deserialized from them, on the current platform.
+pub fn self_check() -> Result<(), SelfCheckError> {
+	let vtable = |object: &'static (dyn any::Any + Sync)| {
+		let trait_object: metatype::TraitObject = type_coerce(metatype::Type::meta(object));
+		ptr::from_ref(trait_object.vtable) as usize
+	};
+	let base = vtable_base();
+	let known = [
+		("the anchor", base),
+		("a type of this crate", vtable(&KNOWN)),
+		("a type of the standard library", vtable(&0_u64)),
+	];
+	for (trait_object, vtable) in known {
+		if !image::contains(base, vtable, size_of::<[usize; 3]>()) {
+			return Err(SelfCheckError::OutsideImage { trait_object });
+		}
+		let offset = vtable_offset(vtable as *const ());
+		if vtable_address(offset, true) != Some(vtable) {
+			return Err(SelfCheckError::Unresolved {
+				trait_object,
+				offset,
+			});
+		}
+	}
+	Ok(())
+}
+
+/// The ways [`self_check`] can fail.
+#[derive(Clone, PartialEq, Eq, Debug)]
+#[non_exhaustive]
+pub enum SelfCheckError {
+	/// The vtable of a trait object of `trait_object` lies outside the image containing the anchor vtable offsets are taken relative to, such that its offset can differ across invocations.
+	OutsideImage {
+		/// A description of the trait object.
+		trait_object: &'static str,
+	},
+	/// The vtable of a trait object of `trait_object` didn't resolve back to itself from its `offset`.
+	Unresolved {
+		/// A description of the trait object.
+		trait_object: &'static str,
+		/// The offset it was serialized as.
+		offset: u64,
+	},
+}
+impl fmt::Display for SelfCheckError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		match self {
+			Self::OutsideImage { trait_object } => write!(
+				f,
+				"the vtable of {trait_object} lies outside the image containing serde_traitobject's anchor on {OS}, so its offset may differ across invocations; link serde_traitobject into the same image, or register an anchor there with anchors::register"
+			),
+			Self::Unresolved {
+				trait_object,
+				offset,
+			} => write!(
+				f,
+				"the vtable of {trait_object} didn't resolve from its offset {offset:#x} on {OS}, so trait objects can't be deserialized on this platform"
+			),
+		}
+	}
+}
+impl error::Error for SelfCheckError {}
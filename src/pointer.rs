@@ -0,0 +1,135 @@
+use std::{
+	borrow::Borrow, fmt, ops::{Deref, DerefMut}, rc, sync
+};
+
+use super::{deserialize, serialize_ref, Deserialize, Serialize};
+
+/// Implemented on smart pointers that can be constructed from a [std::boxed::Box<T>](std::boxed::Box) of their target, so that they can be used with [`Pointer`].
+///
+/// It's implemented for the stdlib `Box`, `Rc` and `Arc`. Implement it on your own smart pointers (or on a newtype around a third-party one) to make them (de)serializable when pointing at trait objects.
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::{ops::Deref, sync::Arc};
+///
+/// struct MyArc<T: ?Sized>(Arc<T>);
+/// impl<T: ?Sized> Deref for MyArc<T> {
+///     type Target = T;
+///     fn deref(&self) -> &T {
+///         &self.0
+///     }
+/// }
+/// impl<T: ?Sized> s::SmartPointer for MyArc<T> {
+///     fn from_box(t: Box<T>) -> Self {
+///         MyArc(t.into())
+///     }
+/// }
+///
+/// let erased: s::Pointer<MyArc<dyn s::Debug>> = s::Pointer::new(MyArc(Arc::new(123u8)));
+///
+/// let serialized = serde_json::to_string(&erased).unwrap();
+/// let deserialized: s::Pointer<MyArc<dyn s::Debug>> = serde_json::from_str(&serialized).unwrap();
+///
+/// println!("{:?}", &**deserialized);
+/// # assert_eq!(format!("{:?}", &**deserialized), "123");
+/// // 123
+/// ```
+pub trait SmartPointer: Deref {
+	/// Construct the pointer from a `std::boxed::Box` of its target.
+	fn from_box(t: Box<Self::Target>) -> Self;
+}
+impl<T: ?Sized> SmartPointer for Box<T> {
+	fn from_box(t: Box<T>) -> Self {
+		t
+	}
+}
+impl<T: ?Sized> SmartPointer for rc::Rc<T> {
+	fn from_box(t: Box<T>) -> Self {
+		t.into()
+	}
+}
+impl<T: ?Sized> SmartPointer for sync::Arc<T> {
+	fn from_box(t: Box<T>) -> Self {
+		t.into()
+	}
+}
+
+/// Convenience wrapper around any [`SmartPointer`] that automatically uses `serde_traitobject` for (de)serialization of its target.
+#[derive(Clone, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Pointer<P>(P);
+impl<P> Pointer<P> {
+	/// Create a new Pointer wrapper
+	pub fn new(p: P) -> Self {
+		Self(p)
+	}
+	/// Convert to the wrapped pointer.
+	pub fn into_inner(self) -> P {
+		self.0
+	}
+}
+impl<P> Deref for Pointer<P> {
+	type Target = P;
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+impl<P> DerefMut for Pointer<P> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		&mut self.0
+	}
+}
+impl<P> AsRef<P> for Pointer<P> {
+	fn as_ref(&self) -> &P {
+		&self.0
+	}
+}
+impl<P> AsMut<P> for Pointer<P> {
+	fn as_mut(&mut self) -> &mut P {
+		&mut self.0
+	}
+}
+impl<P> Borrow<P> for Pointer<P> {
+	fn borrow(&self) -> &P {
+		&self.0
+	}
+}
+impl<P> From<P> for Pointer<P> {
+	fn from(p: P) -> Self {
+		Self(p)
+	}
+}
+impl<P: fmt::Debug> fmt::Debug for Pointer<P> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		self.0.fmt(f)
+	}
+}
+impl<P: fmt::Display> fmt::Display for Pointer<P> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		self.0.fmt(f)
+	}
+}
+impl<P: SmartPointer> serde::ser::Serialize for Pointer<P>
+where
+	P::Target: Serialize + 'static,
+{
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize_ref(&*self.0, serializer)
+	}
+}
+impl<'de, P: SmartPointer> serde::de::Deserialize<'de> for Pointer<P>
+where
+	P::Target: Deserialize + 'static,
+{
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		deserialize::<P::Target, _, D>(deserializer).map(|t: Box<P::Target>| Self(P::from_box(t)))
+	}
+}
@@ -0,0 +1,69 @@
+//! The policy for trait objects that fail validation: whether they're returned as errors, panicked on, or aborted on.
+//!
+//! By default, a trait object that fails validation, in any of the ways enumerated by [`DeserializeError`] for which [`is_validation_failure`](DeserializeError::is_validation_failure) holds, like having come from a different build, a corrupted header, or being rejected by a [`Validator`](crate::validate::Validator), causes deserialization to return an error, like any other malformed input. Where failing validation can only mean tampering, it may be preferable to fail loudly instead. The `panic_on_invalid` and `abort_on_invalid` features change the default policy to [`Policy::Panic`] and [`Policy::Abort`] respectively, and [`set_policy`] overrides it at runtime.
+//!
+//! Other errors, like an unsupported format version, exceeding the [depth](crate::depth) limit, or deserializing the value of a trait object that passed validation, are always returned.
+//!
+//! # Example
+//! ```
+//! extern crate bincode;
+//! extern crate serde_traitobject as s;
+//!
+//! use s::policy::Policy;
+//! use std::panic;
+//!
+//! let message: s::Box<dyn s::Debug> = s::Box::new(String::from("checked"));
+//! let serialized = bincode::serialize(&message).unwrap();
+//!
+//! s::policy::set_policy(Policy::Error);
+//! let result: Result<s::Box<dyn s::Any>, _> = bincode::deserialize(&serialized);
+//! assert!(result.is_err());
+//!
+//! s::policy::set_policy(Policy::Panic);
+//! let result = panic::catch_unwind(|| {
+//!     let _: Result<s::Box<dyn s::Any>, _> = bincode::deserialize(&serialized);
+//! });
+//! assert!(result.is_err());
+//! ```
+
+use std::sync::atomic::{AtomicU8, Ordering};
+
+#[cfg(doc)]
+use super::error::DeserializeError;
+
+/// How trait objects that fail validation are handled.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+#[repr(u8)]
+pub enum Policy {
+	/// Return an error from deserialization.
+	Error = 0,
+	/// Panic, with the [`DeserializeError`] as the message.
+	Panic = 1,
+	/// Print the [`DeserializeError`] to stderr and abort the process.
+	Abort = 2,
+}
+
+/// The policy, unless overridden with [`set_policy`]: [`Policy::Abort`] with the `abort_on_invalid` feature enabled, otherwise [`Policy::Panic`] with the `panic_on_invalid` feature enabled, otherwise [`Policy::Error`].
+pub const DEFAULT_POLICY: Policy = if cfg!(feature = "abort_on_invalid") {
+	Policy::Abort
+} else if cfg!(feature = "panic_on_invalid") {
+	Policy::Panic
+} else {
+	Policy::Error
+};
+
+static POLICY: AtomicU8 = AtomicU8::new(DEFAULT_POLICY as u8);
+
+/// Set the policy for all threads, overriding [`DEFAULT_POLICY`].
+pub fn set_policy(policy: Policy) {
+	POLICY.store(policy as u8, Ordering::Relaxed);
+}
+
+/// The policy for trait objects that fail validation.
+pub fn policy() -> Policy {
+	match POLICY.load(Ordering::Relaxed) {
+		1 => Policy::Panic,
+		2 => Policy::Abort,
+		_ => Policy::Error,
+	}
+}
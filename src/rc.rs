@@ -0,0 +1,39 @@
+//! (De)serialize a [`std::rc::Rc<T>`](std::rc::Rc), where `T` may be unsized, such as a trait object.
+//!
+//! Unlike using [`serialize`](crate::serialize) and [`deserialize`](crate::deserialize) directly, `T` is always inferred from the field type.
+//!
+//! This is intended to enable:
+//! ```
+//! # use serde_derive::{Serialize, Deserialize};
+//! use std::rc::Rc;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct MyStruct {
+//!     #[serde(with = "serde_traitobject::rc")]
+//!     field: Rc<dyn serde_traitobject::Any>,
+//! }
+//! ```
+
+use std::rc;
+
+use super::{serialize_ref, Deserialize, Serialize};
+
+/// Serialize a `Rc<T>`.
+pub fn serialize<T: Serialize + ?Sized + 'static, S>(
+	t: &rc::Rc<T>, serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	serialize_ref(&**t, serializer)
+}
+
+/// Deserialize a `Rc<T>`.
+pub fn deserialize<'de, T: Deserialize + ?Sized + 'static, D>(
+	deserializer: D,
+) -> Result<rc::Rc<T>, D::Error>
+where
+	D: serde::Deserializer<'de>,
+{
+	super::deserialize::<T, Box<T>, D>(deserializer).map(rc::Rc::from)
+}
@@ -0,0 +1,79 @@
+//! (De)serialization through a representation, for concrete types that can't themselves be serialized, like those wrapping OS resources.
+//!
+//! A concrete type that implements [`TraitObjectSerde`] is (de)serialized as the [`Repr`](TraitObjectSerde::Repr) it's converted to with [`to_repr`](TraitObjectSerde::to_repr), and reconstructed from it with [`from_repr`](TraitObjectSerde::from_repr), rather than from its fields. Its `Serialize` and `Deserialize` impls delegate to [`serialize`] and [`deserialize`], such that it can still be a trait object.
+//!
+//! # Example
+//! ```
+//! extern crate bincode;
+//! extern crate serde;
+//! extern crate serde_traitobject as s;
+//!
+//! use s::repr::TraitObjectSerde;
+//! use std::{fs::File, io};
+//!
+//! #[derive(Debug)]
+//! struct Log {
+//!     path: String,
+//!     file: File,
+//! }
+//! impl TraitObjectSerde for Log {
+//!     type Repr = String;
+//!     type Error = io::Error;
+//!
+//!     fn to_repr(&self) -> String {
+//!         self.path.clone()
+//!     }
+//!     fn from_repr(path: String) -> Result<Self, io::Error> {
+//!         let file = File::open(&path)?;
+//!         Ok(Log { path, file })
+//!     }
+//! }
+//! impl serde::Serialize for Log {
+//!     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+//!         s::repr::serialize(self, serializer)
+//!     }
+//! }
+//! impl<'de> serde::Deserialize<'de> for Log {
+//!     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+//!         s::repr::deserialize(deserializer)
+//!     }
+//! }
+//!
+//! let path = String::from(env!("CARGO_MANIFEST_DIR")) + "/Cargo.toml";
+//! let log: s::Box<dyn s::Debug> = s::Box::new(Log { file: File::open(&path).unwrap(), path });
+//! let serialized = bincode::serialize(&log).unwrap();
+//! let deserialized: s::Box<dyn s::Debug> = bincode::deserialize(&serialized).unwrap();
+//! assert!(format!("{:?}", deserialized).contains("Cargo.toml"));
+//! ```
+
+use std::fmt;
+
+/// A type that's (de)serialized as a representation it can be reconstructed from.
+pub trait TraitObjectSerde: Sized {
+	/// The representation it's (de)serialized as, like a path or other descriptor.
+	type Repr: serde::ser::Serialize + serde::de::DeserializeOwned;
+	/// The error reconstructing it can fail with.
+	type Error: fmt::Display;
+
+	/// The representation of `self` to serialize.
+	fn to_repr(&self) -> Self::Repr;
+	/// Reconstruct a value from its deserialized representation.
+	fn from_repr(repr: Self::Repr) -> Result<Self, Self::Error>;
+}
+
+/// Serialize `t` as its [`Repr`](TraitObjectSerde::Repr), for use in its `Serialize` impl.
+pub fn serialize<T: TraitObjectSerde, S>(t: &T, serializer: S) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	serde::ser::Serialize::serialize(&t.to_repr(), serializer)
+}
+
+/// Deserialize a `T` from its [`Repr`](TraitObjectSerde::Repr), for use in its `Deserialize` impl.
+pub fn deserialize<'de, T: TraitObjectSerde, D>(deserializer: D) -> Result<T, D::Error>
+where
+	D: serde::Deserializer<'de>,
+{
+	let repr = serde::de::Deserialize::deserialize(deserializer)?;
+	T::from_repr(repr).map_err(serde::de::Error::custom)
+}
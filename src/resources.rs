@@ -0,0 +1,114 @@
+//! Transferring resources, like file descriptors or handles, alongside the trait objects that refer to them.
+//!
+//! Resources can't be serialized as bytes: when trait objects are sent between processes on the same host, the resources within them have to be transferred out of band, like over a Unix domain socket with `SCM_RIGHTS`. A [`Transfer`], installed with [`set_transfer`], does the actual transfer: each [`Resource`] serialized is passed to it to send, and serialized as the placeholder it returns; each placeholder deserialized is passed to it to resolve back into the live resource received. Concrete types refer to their resources with `#[serde(with = "serde_traitobject::resources")]`.
+//!
+//! # Example
+//! ```
+//! extern crate bincode;
+//! extern crate serde_traitobject as s;
+//! # use serde_derive::{Deserialize, Serialize};
+//!
+//! use s::resources::{Resource, Transfer};
+//! use std::{io, sync::Mutex};
+//!
+//! // Stands in for a socket the resources are sent over.
+//! struct Channel(Mutex<Vec<Resource>>);
+//! impl Transfer for Channel {
+//!     fn send(&self, resource: Resource) -> io::Result<u64> {
+//!         let mut sent = self.0.lock().unwrap();
+//!         sent.push(resource);
+//!         Ok(sent.len() as u64 - 1)
+//!     }
+//!     fn receive(&self, placeholder: u64) -> io::Result<Resource> {
+//!         let sent = self.0.lock().unwrap();
+//!         sent.get(placeholder as usize).copied().ok_or_else(|| io::ErrorKind::NotFound.into())
+//!     }
+//! }
+//! s::resources::set_transfer(Channel(Mutex::new(Vec::new())));
+//!
+//! #[derive(Serialize, Deserialize, Debug)]
+//! struct Connection {
+//!     peer: String,
+//!     #[serde(with = "s::resources")]
+//!     socket: Resource,
+//! }
+//!
+//! let connection: s::Box<dyn s::Debug> =
+//!     s::Box::new(Connection { peer: String::from("db"), socket: Resource::from_raw(7) });
+//! let serialized = bincode::serialize(&connection).unwrap();
+//! let deserialized: s::Box<dyn s::Debug> = bincode::deserialize(&serialized).unwrap();
+//! assert_eq!(format!("{:?}", deserialized), "Connection { peer: \"db\", socket: Resource(7) }");
+//! ```
+
+use std::{
+	io, sync::{Arc, PoisonError, RwLock}
+};
+
+/// A resource, like a file descriptor on Unix or a handle on Windows, by its raw value.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub struct Resource(u64);
+impl Resource {
+	/// The resource with raw value `raw`.
+	pub const fn from_raw(raw: u64) -> Self {
+		Self(raw)
+	}
+	/// The raw value of the resource.
+	pub const fn as_raw(self) -> u64 {
+		self.0
+	}
+}
+
+/// The out-of-band transfer of the resources serialized.
+pub trait Transfer: Send + Sync {
+	/// Send `resource`, returning the placeholder it's serialized as.
+	fn send(&self, resource: Resource) -> io::Result<u64>;
+	/// Resolve a deserialized `placeholder` into the resource received for it.
+	fn receive(&self, placeholder: u64) -> io::Result<Resource>;
+}
+
+static TRANSFER: RwLock<Option<Arc<dyn Transfer>>> = RwLock::new(None);
+
+/// Install `transfer` for all threads, replacing any installed before.
+pub fn set_transfer<T: Transfer + 'static>(transfer: T) {
+	*TRANSFER.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(transfer));
+}
+
+/// Remove the transfer installed with [`set_transfer`], if any.
+pub fn remove_transfer() {
+	*TRANSFER.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// The installed transfer, if any.
+fn transfer() -> io::Result<Arc<dyn Transfer>> {
+	TRANSFER
+		.read()
+		.unwrap_or_else(PoisonError::into_inner)
+		.clone()
+		.ok_or_else(|| {
+			io::Error::other(
+				"no resource transfer installed; install one with resources::set_transfer",
+			)
+		})
+}
+
+/// Send `resource` with the installed [`Transfer`], and serialize the placeholder it returns.
+pub fn serialize<S>(resource: &Resource, serializer: S) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	let placeholder = transfer()
+		.and_then(|transfer| transfer.send(*resource))
+		.map_err(serde::ser::Error::custom)?;
+	serializer.serialize_u64(placeholder)
+}
+
+/// Deserialize a placeholder, and resolve it into the resource received for it with the installed [`Transfer`].
+pub fn deserialize<'de, D>(deserializer: D) -> Result<Resource, D::Error>
+where
+	D: serde::Deserializer<'de>,
+{
+	let placeholder: u64 = serde::de::Deserialize::deserialize(deserializer)?;
+	transfer()
+		.and_then(|transfer| transfer.receive(placeholder))
+		.map_err(serde::de::Error::custom)
+}
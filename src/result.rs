@@ -0,0 +1,41 @@
+//! (De)serialize a `Result` whose error is a trait object pointer, like `Result<T, Box<dyn Error>>`.
+//!
+//! The `Ok` value is (de)serialized normally, and the encoding otherwise matches that of `Result`.
+//!
+//! This is intended to enable:
+//! ```
+//! # use serde_derive::{Serialize, Deserialize};
+//! #[derive(Serialize, Deserialize)]
+//! struct MyStruct {
+//!     #[serde(with = "serde_traitobject::result")]
+//!     field: Result<u64, Box<dyn serde_traitobject::Error>>,
+//! }
+//! ```
+
+use std::boxed;
+
+use super::{Box, Deserialize, Serialize, SerializeRef};
+
+/// Serialize a `Result<V, Box<E>>`.
+pub fn serialize<V: serde::ser::Serialize, E: Serialize + ?Sized + 'static, S>(
+	t: &Result<V, boxed::Box<E>>, serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	match t {
+		Ok(v) => serializer.serialize_newtype_variant("Result", 0, "Ok", v),
+		Err(e) => serializer.serialize_newtype_variant("Result", 1, "Err", &SerializeRef(&**e)),
+	}
+}
+
+/// Deserialize a `Result<V, Box<E>>`.
+pub fn deserialize<'de, V: serde::de::Deserialize<'de>, E: Deserialize + ?Sized + 'static, D>(
+	deserializer: D,
+) -> Result<Result<V, boxed::Box<E>>, D::Error>
+where
+	D: serde::Deserializer<'de>,
+{
+	<Result<V, Box<E>> as serde::de::Deserialize>::deserialize(deserializer)
+		.map(|t| t.map_err(Box::into_box))
+}
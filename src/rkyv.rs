@@ -0,0 +1,112 @@
+//! Archive trait objects with `rkyv`.
+//!
+//! With the `rkyv` feature enabled, a trait object can be converted to a [`TraitObject`], which implements `rkyv`'s `Archive` and `Serialize`. Its archived form, [`ArchivedTraitObject`], holds the same fixed-width header as the [`bytes`](crate::bytes) encoding, which is validated in place, and the value in the `bincode` encoding, which can be accessed without copying.
+//!
+//! As the concrete type of a trait object isn't known statically, the value itself can't be archived with `rkyv`, and must be deserialized from its bytes to be used. Likewise, as serializing the value can fail in ways `rkyv`'s serializers can't represent, it's serialized when the [`TraitObject`] is created, rather than during archiving.
+//!
+//! # Example
+//! ```
+//! extern crate rkyv;
+//! extern crate serde_traitobject as s;
+//!
+//! let value: s::Box<dyn s::Debug> = s::Box::new(String::from("archived"));
+//! let trait_object = s::rkyv::TraitObject::<dyn s::Debug>::new(&value).unwrap();
+//! let bytes = rkyv::to_bytes::<_, 256>(&trait_object).unwrap();
+//!
+//! let archived = unsafe { rkyv::archived_root::<s::rkyv::TraitObject<dyn s::Debug>>(&bytes) };
+//! archived.check().unwrap();
+//! let value = archived.to_box().unwrap();
+//! assert_eq!(format!("{:?}", value), "\"archived\"");
+//! ```
+
+use ::rkyv::{
+	out_field, vec::{ArchivedVec, VecResolver}, Archive, Fallible
+};
+use std::{fmt, marker};
+
+use super::{codec, Deserialize, Header, Serialize};
+
+/// A trait object with its value serialized, ready to be archived with `rkyv`.
+pub struct TraitObject<T: ?Sized> {
+	header: [u8; Header::LEN],
+	value: Vec<u8>,
+	marker: marker::PhantomData<fn() -> Box<T>>,
+}
+impl<T: Serialize + ?Sized + 'static> TraitObject<T> {
+	/// Serialize the value of `t`, erroring if it fails to serialize or if `T` isn't a trait object.
+	pub fn new<B: AsRef<T> + ?Sized>(t: &B) -> Result<Self, bincode::Error> {
+		let (header, value) = codec::to_header(t.as_ref())?;
+		Ok(Self {
+			header,
+			value,
+			marker: marker::PhantomData,
+		})
+	}
+}
+impl<T: ?Sized> fmt::Debug for TraitObject<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("TraitObject")
+			.field("value", &self.value)
+			.finish_non_exhaustive()
+	}
+}
+impl<T: ?Sized> Archive for TraitObject<T> {
+	type Archived = ArchivedTraitObject<T>;
+	type Resolver = VecResolver;
+
+	unsafe fn resolve(&self, pos: usize, resolver: VecResolver, out: *mut ArchivedTraitObject<T>) {
+		let (_, header) = out_field!(out.header);
+		header.write(self.header);
+		let (offset, value) = out_field!(out.value);
+		ArchivedVec::resolve_from_len(self.value.len(), pos + offset, resolver, value);
+	}
+}
+impl<T: ?Sized, S: ::rkyv::ser::ScratchSpace + ::rkyv::ser::Serializer + ?Sized>
+	::rkyv::Serialize<S> for TraitObject<T>
+{
+	fn serialize(&self, serializer: &mut S) -> Result<VecResolver, S::Error> {
+		ArchivedVec::serialize_from_slice(&self.value, serializer)
+	}
+}
+
+/// The archived form of a [`TraitObject`].
+#[repr(C)]
+pub struct ArchivedTraitObject<T: ?Sized> {
+	header: [u8; Header::LEN],
+	value: ArchivedVec<u8>,
+	marker: marker::PhantomData<fn() -> Box<T>>,
+}
+impl<T: ?Sized> fmt::Debug for ArchivedTraitObject<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("ArchivedTraitObject")
+			.field("value", &self.value.as_slice())
+			.finish_non_exhaustive()
+	}
+}
+impl<T: ?Sized + 'static> ArchivedTraitObject<T> {
+	/// Validate that the trait object was archived by this binary, as a `T`.
+	pub fn check(&self) -> Result<(), bincode::Error> {
+		codec::check_header::<T>(&self.header)
+	}
+	/// The value, serialized with `bincode`.
+	pub fn value(&self) -> &[u8] {
+		self.value.as_slice()
+	}
+}
+impl<T: Deserialize + ?Sized + 'static> ArchivedTraitObject<T> {
+	/// Validate and deserialize the trait object.
+	pub fn to_box(&self) -> Result<Box<T>, bincode::Error> {
+		codec::from_header(&self.header, self.value())
+	}
+}
+impl<T: ?Sized, D: Fallible + ?Sized> ::rkyv::Deserialize<TraitObject<T>, D>
+	for ArchivedTraitObject<T>
+{
+	fn deserialize(&self, _deserializer: &mut D) -> Result<TraitObject<T>, D::Error> {
+		Ok(TraitObject {
+			header: self.header,
+			value: self.value.as_slice().to_vec(),
+			marker: marker::PhantomData,
+		})
+	}
+}
@@ -0,0 +1,130 @@
+//! JSON Schema generation for trait objects, with `schemars`.
+//!
+//! With the `schemars` feature enabled, the [`Box`], [`Rc`] and [`Arc`] wrappers implement `JsonSchema`. The schema describes the envelope trait objects are written in by human-readable formats like JSON: the format version, the vtable and the type id of the concrete type. As the concrete type isn't known statically, the schema of the value is deferred to the concrete types [registered](register) for the trait object. If none are registered, any value is accepted.
+//!
+//! # Example
+//! ```
+//! extern crate schemars;
+//! extern crate serde_traitobject as s;
+//!
+//! s::schema::register::<dyn s::Debug, String>();
+//! s::schema::register::<dyn s::Debug, u32>();
+//!
+//! let schema = schemars::schema_for!(s::Box<dyn s::Debug>);
+//! let value = &schema.as_value()["properties"]["value"]["anyOf"];
+//! assert_eq!(value[0]["type"], "string");
+//! assert_eq!(value[1]["type"], "integer");
+//! ```
+
+use schemars::{json_schema, JsonSchema, Schema, SchemaGenerator};
+use std::{
+	any, borrow::Cow, sync::{PoisonError, RwLock}
+};
+
+use super::{Arc, Box, Rc, FIELDS, FORMAT_VERSION, FORMAT_VERSION_DIAGNOSTICS};
+
+/// A concrete type registered for a trait object: the trait object's type id, the concrete type's type id, and a function generating the concrete type's schema.
+type Entry = (any::TypeId, any::TypeId, fn(&mut SchemaGenerator) -> Schema);
+
+static REGISTRY: RwLock<Vec<Entry>> = RwLock::new(Vec::new());
+
+/// Register `C` as a concrete type that `T` trait objects may hold, such that their schema includes `C`'s as a possible value.
+///
+/// Registering the same concrete type for a trait object more than once has no further effect.
+pub fn register<T: ?Sized + 'static, C: JsonSchema + 'static>() {
+	let entry: Entry = (
+		any::TypeId::of::<T>(),
+		any::TypeId::of::<C>(),
+		SchemaGenerator::subschema_for::<C>,
+	);
+	let mut registry = REGISTRY.write().unwrap_or_else(PoisonError::into_inner);
+	if !registry
+		.iter()
+		.any(|&(t, c, _)| (t, c) == (entry.0, entry.1))
+	{
+		registry.push(entry);
+	}
+}
+
+fn schema_id<T: ?Sized>() -> Cow<'static, str> {
+	Cow::Owned(format!(
+		"serde_traitobject::TraitObject<{}>",
+		any::type_name::<T>()
+	))
+}
+
+fn json_schema<T: ?Sized + 'static>(generator: &mut SchemaGenerator) -> Schema {
+	let values = REGISTRY
+		.read()
+		.unwrap_or_else(PoisonError::into_inner)
+		.iter()
+		.filter(|&&(t, _, _)| t == any::TypeId::of::<T>())
+		.map(|&(_, _, schema)| schema)
+		.collect::<Vec<_>>();
+	let value = if values.is_empty() {
+		Schema::from(true)
+	} else {
+		let values = values
+			.into_iter()
+			.map(|schema| schema(generator))
+			.collect::<Vec<_>>();
+		json_schema!({ "anyOf": values })
+	};
+	let hex = json_schema!({
+		"anyOf": [
+			{ "type": "string", "pattern": "^[0-9a-fA-F]{16}$" },
+			{ "type": "integer", "minimum": 0 }
+		]
+	});
+	json_schema!({
+		"description": format!("A `{}` trait object.", any::type_name::<T>()),
+		"type": "object",
+		"properties": {
+			FIELDS[0]: { "enum": [FORMAT_VERSION, FORMAT_VERSION_DIAGNOSTICS] },
+			FIELDS[1]: { "type": "string" },
+			FIELDS[2]: {
+				"type": "array",
+				"prefixItems": [{ "type": "string" }, hex, hex],
+				"items": false
+			},
+			FIELDS[3]: hex,
+			FIELDS[4]: value
+		},
+		"required": [FIELDS[0], FIELDS[2], FIELDS[3], FIELDS[4]],
+		"additionalProperties": false
+	})
+}
+
+impl<T: ?Sized + 'static> JsonSchema for Box<T> {
+	fn schema_name() -> Cow<'static, str> {
+		Cow::Borrowed("TraitObject")
+	}
+	fn schema_id() -> Cow<'static, str> {
+		schema_id::<T>()
+	}
+	fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+		json_schema::<T>(generator)
+	}
+}
+impl<T: ?Sized + 'static> JsonSchema for Rc<T> {
+	fn schema_name() -> Cow<'static, str> {
+		Cow::Borrowed("TraitObject")
+	}
+	fn schema_id() -> Cow<'static, str> {
+		schema_id::<T>()
+	}
+	fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+		json_schema::<T>(generator)
+	}
+}
+impl<T: ?Sized + 'static> JsonSchema for Arc<T> {
+	fn schema_name() -> Cow<'static, str> {
+		Cow::Borrowed("TraitObject")
+	}
+	fn schema_id() -> Cow<'static, str> {
+		schema_id::<T>()
+	}
+	fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+		json_schema::<T>(generator)
+	}
+}
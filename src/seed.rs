@@ -0,0 +1,104 @@
+use std::{any, cell::Cell, fmt, marker};
+
+use super::{Box, Deserialize};
+
+thread_local! {
+	static CONTEXT: Cell<Option<*const dyn any::Any>> = const { Cell::new(None) };
+}
+
+/// A [`DeserializeSeed`](serde::de::DeserializeSeed) for a `Box<T>` that makes a context, like a handle to a thread pool or a database connection pool, available to the concrete types of the trait objects being deserialized, via [`with_context`].
+///
+/// The concrete types themselves still implement `serde::de::DeserializeOwned`; they access the context from within their `Deserialize` impls.
+///
+/// # Example
+/// ```
+/// extern crate serde;
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+/// # use serde_derive::{Deserialize, Serialize};
+///
+/// use serde::de::DeserializeSeed;
+///
+/// struct Pool(&'static str);
+///
+/// #[derive(Serialize, Debug)]
+/// struct Query {
+///     sql: String,
+///     #[serde(skip)]
+///     pool: &'static str,
+/// }
+/// impl<'de> serde::Deserialize<'de> for Query {
+///     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+///         #[derive(Deserialize)]
+///         struct Fields {
+///             sql: String,
+///         }
+///         let Fields { sql } = serde::Deserialize::deserialize(deserializer)?;
+///         let pool = s::with_context(|pool: &Pool| pool.0).unwrap_or("none");
+///         Ok(Query { sql, pool })
+///     }
+/// }
+///
+/// let query: s::Box<dyn s::Debug> = s::Box::new(Query { sql: String::from("SELECT 1"), pool: "" });
+/// let serialized = serde_json::to_string(&query).unwrap();
+///
+/// let pool = Pool("primary");
+/// let seed = s::Seed::<dyn s::Debug>::new(&pool);
+/// let query = seed.deserialize(&mut serde_json::Deserializer::from_str(&serialized)).unwrap();
+/// assert_eq!(format!("{:?}", query), "Query { sql: \"SELECT 1\", pool: \"primary\" }");
+/// ```
+pub struct Seed<'ctx, T: ?Sized> {
+	context: &'ctx (dyn any::Any + 'static),
+	marker: marker::PhantomData<fn() -> Box<T>>,
+}
+impl<'ctx, T: Deserialize + ?Sized + 'static> Seed<'ctx, T> {
+	/// Create a seed that makes `context` available to the concrete types being deserialized.
+	pub fn new<C: any::Any>(context: &'ctx C) -> Self {
+		Self {
+			context,
+			marker: marker::PhantomData,
+		}
+	}
+}
+impl<T: ?Sized> Clone for Seed<'_, T> {
+	fn clone(&self) -> Self {
+		*self
+	}
+}
+impl<T: ?Sized> Copy for Seed<'_, T> {}
+impl<T: ?Sized> fmt::Debug for Seed<'_, T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("Seed")
+			.field("trait_object", &any::type_name::<T>())
+			.finish_non_exhaustive()
+	}
+}
+impl<'de, T: Deserialize + ?Sized + 'static> serde::de::DeserializeSeed<'de> for Seed<'_, T> {
+	type Value = Box<T>;
+
+	fn deserialize<D>(self, deserializer: D) -> Result<Box<T>, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		struct Guard(Option<*const dyn any::Any>);
+		impl Drop for Guard {
+			fn drop(&mut self) {
+				CONTEXT.with(|scoped| scoped.set(self.0));
+			}
+		}
+		// The context is only accessed through the pointer while the guard is alive.
+		let context: *const dyn any::Any = self.context;
+		let _guard = Guard(CONTEXT.with(|scoped| scoped.replace(Some(context))));
+		<Box<T> as serde::de::Deserialize>::deserialize(deserializer)
+	}
+}
+
+/// Run `f` with the context of the innermost [`Seed`] currently deserializing on this thread, if there is one and it's a `C`.
+pub fn with_context<C: any::Any, F, R>(f: F) -> Option<R>
+where
+	F: FnOnce(&C) -> R,
+{
+	let context = CONTEXT.with(Cell::get)?;
+	let context = unsafe { &*context }.downcast_ref::<C>()?;
+	Some(f(context))
+}
@@ -0,0 +1,108 @@
+use serde_with::{DeserializeAs, SerializeAs};
+use std::{rc, sync};
+
+use super::{deserialize, serialize, Deserialize, Serialize};
+
+/// A [`serde_with`] adapter that (de)serializes [`Box`](std::boxed::Box), [`Rc`](std::rc::Rc) and [`Arc`](std::sync::Arc) pointers to trait objects using `serde_traitobject`.
+///
+/// As with other `serde_with` adapters, it can be nested arbitrarily within containers like `Vec<Option<As>>` and `HashMap<K, Vec<As>>`, so combinations of containers don't each need their own with-module.
+///
+/// # Example
+/// ```
+/// # use serde_derive::{Serialize, Deserialize};
+/// use serde_with::serde_as;
+/// use std::sync::Arc;
+///
+/// #[serde_as]
+/// #[derive(Serialize, Deserialize)]
+/// struct MyStruct {
+///     #[serde_as(as = "serde_traitobject::As")]
+///     field: Box<dyn serde_traitobject::Any>,
+///     #[serde_as(as = "Vec<serde_traitobject::As>")]
+///     vec: Vec<Box<dyn serde_traitobject::Any>>,
+///     #[serde_as(as = "Option<serde_traitobject::As>")]
+///     option: Option<Arc<dyn serde_traitobject::Any>>,
+/// }
+/// ```
+///
+/// Nested containers:
+/// ```
+/// # use serde_derive::{Serialize, Deserialize};
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use serde_with::serde_as;
+/// use std::collections::HashMap;
+///
+/// #[serde_as]
+/// #[derive(Serialize, Deserialize)]
+/// struct Message {
+///     #[serde_as(as = "Vec<Option<s::As>>")]
+///     vec: Vec<Option<Box<dyn s::Debug>>>,
+///     #[serde_as(as = "HashMap<_, Vec<s::As>>")]
+///     map: HashMap<String, Vec<Box<dyn s::Debug>>>,
+/// }
+///
+/// let message = Message {
+///     vec: vec![Some(Box::new(1u8)), None],
+///     map: vec![(String::from("a"), vec![Box::new(String::from("b")) as Box<dyn s::Debug>])]
+///         .into_iter()
+///         .collect(),
+/// };
+/// let serialized = serde_json::to_string(&message).unwrap();
+/// let deserialized: Message = serde_json::from_str(&serialized).unwrap();
+///
+/// assert_eq!(format!("{:?}", deserialized.vec), "[Some(1), None]");
+/// assert_eq!(format!("{:?}", deserialized.map["a"]), "[\"b\"]");
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct As;
+
+impl<T: Serialize + ?Sized + 'static> SerializeAs<Box<T>> for As {
+	fn serialize_as<S>(source: &Box<T>, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(source, serializer)
+	}
+}
+impl<'de, T: Deserialize + ?Sized + 'static> DeserializeAs<'de, Box<T>> for As {
+	fn deserialize_as<D>(deserializer: D) -> Result<Box<T>, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		deserialize(deserializer)
+	}
+}
+impl<T: Serialize + ?Sized + 'static> SerializeAs<rc::Rc<T>> for As {
+	fn serialize_as<S>(source: &rc::Rc<T>, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(source, serializer)
+	}
+}
+impl<'de, T: Deserialize + ?Sized + 'static> DeserializeAs<'de, rc::Rc<T>> for As {
+	fn deserialize_as<D>(deserializer: D) -> Result<rc::Rc<T>, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		deserialize::<T, _, D>(deserializer)
+	}
+}
+impl<T: Serialize + ?Sized + 'static> SerializeAs<sync::Arc<T>> for As {
+	fn serialize_as<S>(source: &sync::Arc<T>, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(source, serializer)
+	}
+}
+impl<'de, T: Deserialize + ?Sized + 'static> DeserializeAs<'de, sync::Arc<T>> for As {
+	fn deserialize_as<D>(deserializer: D) -> Result<sync::Arc<T>, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		deserialize::<T, _, D>(deserializer)
+	}
+}
@@ -0,0 +1,231 @@
+//! Identity-preserving (de)serialization of shared pointers.
+//!
+//! By default, every occurrence of an [`Arc`](crate::Arc) is serialized in full, and deserialized into a separate allocation. Within a [`Shared`], the [`Arc`] in this module instead serializes each distinct allocation once, with subsequent occurrences serialized as a back-reference. On deserialization of a [`Shared`], back-references are restored as clones of the same `Arc`, preserving sharing.
+//!
+//! The session the back-references refer within starts afresh each time a [`Shared`] is serialized, so formats that traverse the value more than once, like `bincode::serialize`, which measures the value before writing it, produce the same output on each pass.
+//!
+//! # Example
+//! ```
+//! extern crate serde_json;
+//! extern crate serde_traitobject as s;
+//!
+//! use s::shared::Shared;
+//! use std::sync::Arc;
+//!
+//! let node: Arc<dyn s::Debug> = Arc::new(String::from("shared"));
+//! let message = (s::shared::Arc::from(node.clone()), s::shared::Arc::from(node));
+//!
+//! let serialized = serde_json::to_string(&Shared(&message)).unwrap();
+//! let Shared((a, b)): Shared<(s::shared::Arc<dyn s::Debug>, s::shared::Arc<dyn s::Debug>)> =
+//!     serde_json::from_str(&serialized).unwrap();
+//!
+//! assert!(Arc::ptr_eq(&a, &b));
+//! ```
+
+use serde::ser::SerializeTuple;
+use std::{
+	any, borrow::Borrow, cell::RefCell, collections::HashMap, fmt, mem, ops::Deref, rc::Rc, sync
+};
+
+use super::{Box, Deserialize, Serialize, SerializeRef};
+
+#[derive(Clone, Default)]
+struct Session {
+	/// The id assigned to each allocation serialized so far, keyed by address. A clone of each `Arc` is held so that addresses aren't reused within the session.
+	serialized: HashMap<usize, (u64, Rc<dyn any::Any>)>,
+	/// Each allocation deserialized so far, keyed by id.
+	deserialized: HashMap<u64, Rc<dyn any::Any>>,
+}
+
+thread_local! {
+	static SESSION: RefCell<Option<Session>> = const { RefCell::new(None) };
+}
+
+/// A value that's (de)serialized within its own session, such that the [`Arc`]s within it preserve their sharing.
+///
+/// A [`Shared`] nested within another is (de)serialized within the outermost's session.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Shared<T>(pub T);
+impl<T: serde::ser::Serialize> serde::ser::Serialize for Shared<T> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		session(|| self.0.serialize(serializer))
+	}
+}
+impl<'de, T: serde::de::Deserialize<'de>> serde::de::Deserialize<'de> for Shared<T> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		session(|| T::deserialize(deserializer).map(Shared))
+	}
+}
+
+/// Run `f` within a session, unless it's already within one, in which case that's used.
+fn session<F, R>(f: F) -> R
+where
+	F: FnOnce() -> R,
+{
+	struct Guard(bool);
+	impl Drop for Guard {
+		fn drop(&mut self) {
+			if self.0 {
+				SESSION.with(|session| *session.borrow_mut() = None);
+			}
+		}
+	}
+	let _guard = Guard(SESSION.with(|session| {
+		let mut session = session.borrow_mut();
+		session.is_none() && {
+			*session = Some(Session::default());
+			true
+		}
+	}));
+	f()
+}
+
+/// Run `f` within a copy of the current session, if any, such that the allocations it serializes aren't recorded in the session itself.
+pub(crate) fn scratch<F, R>(f: F) -> R
+where
+	F: FnOnce() -> R,
+{
+	struct Guard(Option<Session>);
+	impl Drop for Guard {
+		fn drop(&mut self) {
+			SESSION.with(|session| *session.borrow_mut() = self.0.take());
+		}
+	}
+	let _guard = Guard(SESSION.with(|session| {
+		let mut session = session.borrow_mut();
+		let copy = session.clone();
+		mem::replace(&mut *session, copy)
+	}));
+	f()
+}
+
+/// Convenience wrapper around [std::sync::Arc<T>](std::sync::Arc) that automatically uses `serde_traitobject` for (de)serialization, preserving sharing within a [`Shared`].
+///
+/// Outside of a [`Shared`] it behaves like [`crate::Arc`], albeit with a different encoding.
+pub struct Arc<T: ?Sized>(sync::Arc<T>);
+impl<T> Arc<T> {
+	/// Create a new Arc wrapper
+	pub fn new(t: T) -> Self {
+		Self(sync::Arc::new(t))
+	}
+}
+impl<T: ?Sized> Deref for Arc<T> {
+	type Target = sync::Arc<T>;
+	fn deref(&self) -> &Self::Target {
+		&self.0
+	}
+}
+impl<T: ?Sized> AsRef<sync::Arc<T>> for Arc<T> {
+	fn as_ref(&self) -> &sync::Arc<T> {
+		&self.0
+	}
+}
+impl<T: ?Sized> AsRef<T> for Arc<T> {
+	fn as_ref(&self) -> &T {
+		&self.0
+	}
+}
+impl<T: ?Sized> Borrow<T> for Arc<T> {
+	fn borrow(&self) -> &T {
+		&self.0
+	}
+}
+impl<T: ?Sized> From<sync::Arc<T>> for Arc<T> {
+	fn from(t: sync::Arc<T>) -> Self {
+		Self(t)
+	}
+}
+impl<T: ?Sized> From<Arc<T>> for sync::Arc<T> {
+	fn from(v: Arc<T>) -> Self {
+		v.0
+	}
+}
+impl<T: ?Sized> Clone for Arc<T> {
+	fn clone(&self) -> Self {
+		Self(self.0.clone())
+	}
+}
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Arc<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		self.0.fmt(f)
+	}
+}
+impl<T: fmt::Display + ?Sized> fmt::Display for Arc<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		self.0.fmt(f)
+	}
+}
+impl<T: Serialize + ?Sized + 'static> serde::ser::Serialize for Arc<T> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		// Id 0 is reserved for allocations serialized outside of a session.
+		let (id, first) = SESSION.with(|session| {
+			session.borrow_mut().as_mut().map_or((0, true), |session| {
+				let address = sync::Arc::as_ptr(&self.0).cast::<()>() as usize;
+				let next = session.serialized.len() as u64 + 1;
+				let mut first = false;
+				let &mut (id, _) = session.serialized.entry(address).or_insert_with(|| {
+					first = true;
+					(next, Rc::new(self.0.clone()))
+				});
+				(id, first)
+			})
+		});
+		let mut tup = serializer.serialize_tuple(2)?;
+		tup.serialize_element(&id)?;
+		tup.serialize_element(
+			&(if first {
+				Some(SerializeRef(&*self.0))
+			} else {
+				None
+			}),
+		)?;
+		tup.end()
+	}
+}
+impl<'de, T: Deserialize + ?Sized + 'static> serde::de::Deserialize<'de> for Arc<T> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let (id, payload) = <(u64, Option<Box<T>>)>::deserialize(deserializer)?;
+		let arc = match payload {
+			Some(payload) => {
+				let arc: sync::Arc<T> = payload.into_box().into();
+				if id != 0 {
+					SESSION.with(|session| {
+						if let Some(session) = session.borrow_mut().as_mut() {
+							let _ = session.deserialized.insert(id, Rc::new(arc.clone()));
+						}
+					});
+				}
+				arc
+			}
+			None => SESSION
+				.with(|session| {
+					session
+						.borrow()
+						.as_ref()
+						.and_then(|session| session.deserialized.get(&id))
+						.and_then(|arc| arc.downcast_ref::<sync::Arc<T>>())
+						.cloned()
+				})
+				.ok_or_else(|| {
+					serde::de::Error::custom(format_args!(
+						"back-reference to shared \"{}\" {} that hasn't been deserialized in this session",
+						any::type_name::<T>(),
+						id
+					))
+				})?,
+		};
+		Ok(Self(arc))
+	}
+}
@@ -0,0 +1,61 @@
+//! Trait objects signed with Ed25519, for channels where the other end must be authenticated.
+//!
+//! The validation data serialized alongside a trait object guards against accidental misuse, but a malicious actor with a copy of the binary could craft a trait object that passes validation and gives them control of where to jump to. With the `ed25519` feature enabled, [`to_signed_bytes`] serializes a trait object like [`to_bytes`](crate::to_bytes), and appends a detached signature over the whole envelope, made with a [`SigningKey`]. [`from_signed_bytes`] verifies it with the corresponding [`VerifyingKey`] before any validation or decoding of the payload, such that only trait objects serialized by a holder of the signing key are deserialized.
+//!
+//! # Example
+//! ```
+//! extern crate serde_traitobject as s;
+//! # s::policy::set_policy(s::policy::Policy::Error);
+//!
+//! use s::signed::{SigningKey, VerifyingKey};
+//!
+//! let signing_key = SigningKey::from_bytes(&[7; 32]);
+//! let verifying_key: VerifyingKey = signing_key.verifying_key();
+//!
+//! let bytes = s::signed::to_signed_bytes::<dyn s::Debug>(&String::from("signed"), &signing_key).unwrap();
+//! let value: s::Box<dyn s::Debug> = s::signed::from_signed_bytes(&bytes, &verifying_key).unwrap();
+//! assert_eq!(format!("{:?}", value), "\"signed\"");
+//!
+//! let other_key = SigningKey::from_bytes(&[8; 32]).verifying_key();
+//! let result: Result<s::Box<dyn s::Debug>, _> = s::signed::from_signed_bytes(&bytes, &other_key);
+//! assert!(result.is_err());
+//! ```
+
+use ed25519_dalek::{Signature, Signer};
+
+use super::{error::DeserializeError, from_bytes, to_bytes, Box, Deserialize, Serialize};
+
+pub use ed25519_dalek::{SigningKey, VerifyingKey};
+
+/// The length of the signature appended to the envelope.
+pub const SIGNATURE_LEN: usize = Signature::BYTE_SIZE;
+
+/// Serialize a trait object to bytes, using `bincode`, followed by a signature over them made with `key`.
+///
+/// The bytes can be deserialized with [`from_signed_bytes`] by the same binary.
+pub fn to_signed_bytes<T: Serialize + ?Sized + 'static>(
+	t: &T, key: &SigningKey,
+) -> Result<Vec<u8>, bincode::Error> {
+	let mut bytes = to_bytes(t)?;
+	let signature = key.sign(&bytes);
+	bytes.extend_from_slice(&signature.to_bytes());
+	Ok(bytes)
+}
+
+/// Deserialize a trait object from bytes written by [`to_signed_bytes`], having verified their signature with `key`.
+pub fn from_signed_bytes<T: Deserialize + ?Sized + 'static>(
+	bytes: &[u8], key: &VerifyingKey,
+) -> Result<Box<T>, bincode::Error> {
+	let verified = bytes
+		.len()
+		.checked_sub(SIGNATURE_LEN)
+		.map(|len| bytes.split_at(len))
+		.and_then(|(bytes, signature)| {
+			let signature = Signature::from_slice(signature).ok()?;
+			key.verify_strict(bytes, &signature).ok().map(|()| bytes)
+		});
+	let Some(bytes) = verified else {
+		return Err(DeserializeError::BadSignature.raise());
+	};
+	from_bytes(bytes)
+}
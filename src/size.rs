@@ -0,0 +1,270 @@
+//! Measuring the serialized size of a trait object without serializing it.
+
+use serde::ser::{self, Serialize as _};
+use std::cell::Cell;
+
+use super::{dedup, error::Message, shared, Serialize, SerializeRef};
+
+thread_local! {
+	/// Whether [`serialized_size`] is measuring on this thread.
+	static MEASURING: Cell<bool> = const { Cell::new(false) };
+}
+
+/// The number of bytes `t` serializes to with `bincode`'s default configuration, including the trait object's header.
+///
+/// This runs the serialization without writing anything, so it's cheap relative to serializing, but not free: the value is still traversed in full.
+///
+/// Measuring has no side effects: the installed [`Hook`](crate::hook::Hook), if any, isn't invoked, and the headers and allocations measured aren't recorded in an enclosing [`Dedup`](crate::dedup::Dedup) or [`Shared`](crate::shared::Shared), though they're measured as back-references where they've already been serialized.
+///
+/// # Example
+/// ```
+/// extern crate bincode;
+/// extern crate serde_traitobject as s;
+///
+/// let value: s::Box<dyn s::Debug> = s::Box::new(vec![1_u32, 2, 3]);
+/// let size = s::serialized_size::<dyn s::Debug>(&**value).unwrap();
+/// assert_eq!(size, bincode::serialize(&value).unwrap().len() as u64);
+/// ```
+pub fn serialized_size<T: Serialize + ?Sized + 'static>(t: &T) -> Result<u64, Message> {
+	struct Guard(bool);
+	impl Drop for Guard {
+		fn drop(&mut self) {
+			MEASURING.with(|measuring| measuring.set(self.0));
+		}
+	}
+	let _guard = Guard(MEASURING.with(|measuring| measuring.replace(true)));
+	let mut counter = Counter(0);
+	dedup::scratch(|| shared::scratch(|| SerializeRef(t).serialize(&mut counter)))?;
+	Ok(counter.0)
+}
+
+/// Whether [`serialized_size`] is measuring on this thread, in which case serializing a trait object mustn't invoke the hook.
+pub(crate) fn measuring() -> bool {
+	MEASURING.with(Cell::get)
+}
+
+/// A serializer that counts the bytes `bincode` would write, mirroring its fixed-width integer encoding and `u64` length prefixes.
+struct Counter(u64);
+impl Counter {
+	fn add(&mut self, size: usize) {
+		self.0 += size as u64;
+	}
+}
+impl ser::Serializer for &mut Counter {
+	type Ok = ();
+	type Error = Message;
+	type SerializeSeq = Self;
+	type SerializeTuple = Self;
+	type SerializeTupleStruct = Self;
+	type SerializeTupleVariant = Self;
+	type SerializeMap = Self;
+	type SerializeStruct = Self;
+	type SerializeStructVariant = Self;
+
+	fn serialize_bool(self, _v: bool) -> Result<(), Message> {
+		self.add(1);
+		Ok(())
+	}
+	fn serialize_i8(self, _v: i8) -> Result<(), Message> {
+		self.add(1);
+		Ok(())
+	}
+	fn serialize_i16(self, _v: i16) -> Result<(), Message> {
+		self.add(2);
+		Ok(())
+	}
+	fn serialize_i32(self, _v: i32) -> Result<(), Message> {
+		self.add(4);
+		Ok(())
+	}
+	fn serialize_i64(self, _v: i64) -> Result<(), Message> {
+		self.add(8);
+		Ok(())
+	}
+	fn serialize_i128(self, _v: i128) -> Result<(), Message> {
+		self.add(16);
+		Ok(())
+	}
+	fn serialize_u8(self, _v: u8) -> Result<(), Message> {
+		self.add(1);
+		Ok(())
+	}
+	fn serialize_u16(self, _v: u16) -> Result<(), Message> {
+		self.add(2);
+		Ok(())
+	}
+	fn serialize_u32(self, _v: u32) -> Result<(), Message> {
+		self.add(4);
+		Ok(())
+	}
+	fn serialize_u64(self, _v: u64) -> Result<(), Message> {
+		self.add(8);
+		Ok(())
+	}
+	fn serialize_u128(self, _v: u128) -> Result<(), Message> {
+		self.add(16);
+		Ok(())
+	}
+	fn serialize_f32(self, _v: f32) -> Result<(), Message> {
+		self.add(4);
+		Ok(())
+	}
+	fn serialize_f64(self, _v: f64) -> Result<(), Message> {
+		self.add(8);
+		Ok(())
+	}
+	fn serialize_char(self, v: char) -> Result<(), Message> {
+		self.add(v.len_utf8());
+		Ok(())
+	}
+	fn serialize_str(self, v: &str) -> Result<(), Message> {
+		self.add(8 + v.len());
+		Ok(())
+	}
+	fn serialize_bytes(self, v: &[u8]) -> Result<(), Message> {
+		self.add(8 + v.len());
+		Ok(())
+	}
+	fn serialize_none(self) -> Result<(), Message> {
+		self.add(1);
+		Ok(())
+	}
+	fn serialize_some<T: ser::Serialize + ?Sized>(self, value: &T) -> Result<(), Message> {
+		self.add(1);
+		value.serialize(self)
+	}
+	fn serialize_unit(self) -> Result<(), Message> {
+		Ok(())
+	}
+	fn serialize_unit_struct(self, _name: &'static str) -> Result<(), Message> {
+		Ok(())
+	}
+	fn serialize_unit_variant(
+		self, _name: &'static str, _variant_index: u32, _variant: &'static str,
+	) -> Result<(), Message> {
+		self.add(4);
+		Ok(())
+	}
+	fn serialize_newtype_struct<T: ser::Serialize + ?Sized>(
+		self, _name: &'static str, value: &T,
+	) -> Result<(), Message> {
+		value.serialize(self)
+	}
+	fn serialize_newtype_variant<T: ser::Serialize + ?Sized>(
+		self, _name: &'static str, _variant_index: u32, _variant: &'static str, value: &T,
+	) -> Result<(), Message> {
+		self.add(4);
+		value.serialize(self)
+	}
+	fn serialize_seq(self, len: Option<usize>) -> Result<Self, Message> {
+		let _ = len.ok_or_else(|| Message::new("sequences must have a known length"))?;
+		self.add(8);
+		Ok(self)
+	}
+	fn serialize_tuple(self, _len: usize) -> Result<Self, Message> {
+		Ok(self)
+	}
+	fn serialize_tuple_struct(self, _name: &'static str, _len: usize) -> Result<Self, Message> {
+		Ok(self)
+	}
+	fn serialize_tuple_variant(
+		self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize,
+	) -> Result<Self, Message> {
+		self.add(4);
+		Ok(self)
+	}
+	fn serialize_map(self, len: Option<usize>) -> Result<Self, Message> {
+		let _ = len.ok_or_else(|| Message::new("maps must have a known length"))?;
+		self.add(8);
+		Ok(self)
+	}
+	fn serialize_struct(self, _name: &'static str, _len: usize) -> Result<Self, Message> {
+		Ok(self)
+	}
+	fn serialize_struct_variant(
+		self, _name: &'static str, _variant_index: u32, _variant: &'static str, _len: usize,
+	) -> Result<Self, Message> {
+		self.add(4);
+		Ok(self)
+	}
+	fn is_human_readable(&self) -> bool {
+		false
+	}
+}
+impl ser::SerializeSeq for &mut Counter {
+	type Ok = ();
+	type Error = Message;
+	fn serialize_element<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Message> {
+		value.serialize(&mut **self)
+	}
+	fn end(self) -> Result<(), Message> {
+		Ok(())
+	}
+}
+impl ser::SerializeTuple for &mut Counter {
+	type Ok = ();
+	type Error = Message;
+	fn serialize_element<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Message> {
+		value.serialize(&mut **self)
+	}
+	fn end(self) -> Result<(), Message> {
+		Ok(())
+	}
+}
+impl ser::SerializeTupleStruct for &mut Counter {
+	type Ok = ();
+	type Error = Message;
+	fn serialize_field<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Message> {
+		value.serialize(&mut **self)
+	}
+	fn end(self) -> Result<(), Message> {
+		Ok(())
+	}
+}
+impl ser::SerializeTupleVariant for &mut Counter {
+	type Ok = ();
+	type Error = Message;
+	fn serialize_field<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Message> {
+		value.serialize(&mut **self)
+	}
+	fn end(self) -> Result<(), Message> {
+		Ok(())
+	}
+}
+impl ser::SerializeMap for &mut Counter {
+	type Ok = ();
+	type Error = Message;
+	fn serialize_key<T: ser::Serialize + ?Sized>(&mut self, key: &T) -> Result<(), Message> {
+		key.serialize(&mut **self)
+	}
+	fn serialize_value<T: ser::Serialize + ?Sized>(&mut self, value: &T) -> Result<(), Message> {
+		value.serialize(&mut **self)
+	}
+	fn end(self) -> Result<(), Message> {
+		Ok(())
+	}
+}
+impl ser::SerializeStruct for &mut Counter {
+	type Ok = ();
+	type Error = Message;
+	fn serialize_field<T: ser::Serialize + ?Sized>(
+		&mut self, _key: &'static str, value: &T,
+	) -> Result<(), Message> {
+		value.serialize(&mut **self)
+	}
+	fn end(self) -> Result<(), Message> {
+		Ok(())
+	}
+}
+impl ser::SerializeStructVariant for &mut Counter {
+	type Ok = ();
+	type Error = Message;
+	fn serialize_field<T: ser::Serialize + ?Sized>(
+		&mut self, _key: &'static str, value: &T,
+	) -> Result<(), Message> {
+		value.serialize(&mut **self)
+	}
+	fn end(self) -> Result<(), Message> {
+		Ok(())
+	}
+}
@@ -0,0 +1,210 @@
+use std::{
+	alloc::{self, Layout}, borrow::{Borrow, BorrowMut}, fmt, marker, mem::{self, align_of, size_of, MaybeUninit}, ops::{Deref, DerefMut}, ptr::{self, NonNull}
+};
+
+use super::{deserialize, deserialize_in, serialize, Deserialize, Serialize};
+
+/// A [std::boxed::Box<T>](std::boxed::Box) alternative that stores values that fit within `S` inline rather than on the heap, and automatically uses `serde_traitobject` for (de)serialization.
+///
+/// Values larger than `S`, or more strictly aligned than `S`, spill to the heap. Deserialization writes concrete values directly into the inline storage where they fit, so deserializing small values, whether trait objects or not, doesn't allocate.
+///
+/// It serializes identically to [`Box<T>`](crate::Box), so the two are interchangeable on the wire.
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// let small: s::SmallBox<dyn s::Debug> = s::SmallBox::new_unsize(123u8);
+/// assert!(small.is_inline());
+///
+/// let serialized = serde_json::to_string(&small).unwrap();
+/// let deserialized: s::SmallBox<dyn s::Debug, [usize; 1]> = serde_json::from_str(&serialized).unwrap();
+/// assert!(deserialized.is_inline());
+///
+/// println!("{:?}", deserialized);
+/// # assert_eq!(format!("{:?}", deserialized), "123");
+/// // 123
+///
+/// let sized: s::SmallBox<u64> = serde_json::from_str("456").unwrap();
+/// assert!(sized.is_inline());
+/// # assert_eq!(*sized, 456);
+/// ```
+pub struct SmallBox<T: ?Sized, S = [usize; 3]> {
+	meta: <T as metatype::Type>::Meta,
+	storage: MaybeUninit<S>,
+	heap: Option<NonNull<u8>>,
+	marker: marker::PhantomData<T>,
+}
+unsafe impl<T: ?Sized + Send, S> Send for SmallBox<T, S> {}
+unsafe impl<T: ?Sized + Sync, S> Sync for SmallBox<T, S> {}
+impl<T, S> SmallBox<T, S> {
+	/// Create a new `SmallBox` wrapper
+	pub fn new(t: T) -> Self {
+		let mut storage = MaybeUninit::<S>::uninit();
+		let mut heap = None;
+		let raw = place::<S>(&mut storage, &mut heap, Layout::new::<T>()).cast::<T>();
+		unsafe { raw.as_ptr().write(t) };
+		Self::from_parts(metatype::Concrete, storage, heap)
+	}
+}
+impl<T: ?Sized, S> SmallBox<T, S> {
+	/// Create a new `SmallBox` wrapper from a value that can be unsized to `T`, such as a concrete implementor of the trait object `T`.
+	pub fn new_unsize<U: marker::Unsize<T>>(u: U) -> Self {
+		let mut storage = MaybeUninit::<S>::uninit();
+		let mut heap = None;
+		let raw = place::<S>(&mut storage, &mut heap, Layout::new::<U>()).cast::<U>();
+		unsafe { raw.as_ptr().write(u) };
+		let raw: *mut T = raw.as_ptr();
+		Self::from_parts(metatype::Type::meta(raw), storage, heap)
+	}
+	/// Move the contents of a `std::boxed::Box<T>` into a new `SmallBox`, moving it inline if it fits.
+	pub fn from_box(t: Box<T>) -> Self {
+		let mut storage = MaybeUninit::<S>::uninit();
+		let mut heap = None;
+		let raw = deserialize::unbox(t, &mut |layout| place::<S>(&mut storage, &mut heap, layout));
+		Self::from_parts(metatype::Type::meta(raw), storage, heap)
+	}
+	/// Convert to a regular `std::boxed::Box<T>`. Coherence rules currently prevent `impl Into<std::boxed::Box<T>> for SmallBox<T, S>`.
+	pub fn into_box(self) -> Box<T> {
+		let mut this = mem::ManuallyDrop::new(self);
+		let raw: *mut T = this.as_mut_ptr();
+		if this.heap.is_some() {
+			return unsafe { Box::from_raw(raw) };
+		}
+		let layout = Layout::for_value::<T>(unsafe { &*raw });
+		let dst = deserialize::heap(layout);
+		unsafe {
+			ptr::copy_nonoverlapping(raw.cast::<u8>(), dst.as_ptr(), layout.size());
+			Box::from_raw(metatype::Type::fatten(
+				dst.as_ptr().cast(),
+				metatype::Type::meta(raw),
+			))
+		}
+	}
+	/// Whether the value is stored inline rather than on the heap.
+	pub fn is_inline(&self) -> bool {
+		self.heap.is_none()
+	}
+	fn from_parts(
+		meta: <T as metatype::Type>::Meta, storage: MaybeUninit<S>, heap: Option<NonNull<u8>>,
+	) -> Self {
+		Self {
+			meta,
+			storage,
+			heap,
+			marker: marker::PhantomData,
+		}
+	}
+	fn as_ptr(&self) -> *const T {
+		let data = self
+			.heap
+			.map_or(self.storage.as_ptr().cast::<u8>(), |heap| {
+				heap.as_ptr().cast_const()
+			});
+		// The metadata types (vtable references, lengths and `()`) are all
+		// `Copy`, so reading them out leaves `self.meta` intact.
+		metatype::Type::fatten(data.cast_mut().cast(), unsafe {
+			ptr::read(ptr::addr_of!(self.meta))
+		})
+	}
+	fn as_mut_ptr(&mut self) -> *mut T {
+		let data = self
+			.heap
+			.map_or(self.storage.as_mut_ptr().cast::<u8>(), NonNull::as_ptr);
+		metatype::Type::fatten(data.cast(), unsafe { ptr::read(ptr::addr_of!(self.meta)) })
+	}
+}
+
+/// Returns a pointer into `storage` if `layout` fits within it, otherwise allocates on the heap and records the allocation in `heap`.
+fn place<S>(
+	storage: &mut MaybeUninit<S>, heap: &mut Option<NonNull<u8>>, layout: Layout,
+) -> NonNull<u8> {
+	if layout.size() <= size_of::<S>() && layout.align() <= align_of::<S>() {
+		NonNull::from(storage).cast()
+	} else {
+		let raw = deserialize::heap(layout);
+		*heap = Some(raw);
+		raw
+	}
+}
+
+impl<T: ?Sized, S> Drop for SmallBox<T, S> {
+	fn drop(&mut self) {
+		let raw: *mut T = self.as_mut_ptr();
+		let layout = Layout::for_value::<T>(unsafe { &*raw });
+		unsafe { ptr::drop_in_place(raw) };
+		if let Some(heap) = self.heap {
+			if layout.size() != 0 {
+				unsafe { alloc::dealloc(heap.as_ptr(), layout) };
+			}
+		}
+	}
+}
+impl<T: ?Sized, S> Deref for SmallBox<T, S> {
+	type Target = T;
+	fn deref(&self) -> &Self::Target {
+		unsafe { &*self.as_ptr() }
+	}
+}
+impl<T: ?Sized, S> DerefMut for SmallBox<T, S> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		unsafe { &mut *self.as_mut_ptr() }
+	}
+}
+impl<T: ?Sized, S> AsRef<T> for SmallBox<T, S> {
+	fn as_ref(&self) -> &T {
+		self
+	}
+}
+impl<T: ?Sized, S> AsMut<T> for SmallBox<T, S> {
+	fn as_mut(&mut self) -> &mut T {
+		self
+	}
+}
+impl<T: ?Sized, S> Borrow<T> for SmallBox<T, S> {
+	fn borrow(&self) -> &T {
+		self
+	}
+}
+impl<T: ?Sized, S> BorrowMut<T> for SmallBox<T, S> {
+	fn borrow_mut(&mut self) -> &mut T {
+		self
+	}
+}
+impl<T: ?Sized, S> From<Box<T>> for SmallBox<T, S> {
+	fn from(t: Box<T>) -> Self {
+		Self::from_box(t)
+	}
+}
+impl<T: fmt::Debug + ?Sized, S> fmt::Debug for SmallBox<T, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		(**self).fmt(f)
+	}
+}
+impl<T: fmt::Display + ?Sized, S> fmt::Display for SmallBox<T, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		(**self).fmt(f)
+	}
+}
+impl<T: Serialize + ?Sized + 'static, S> serde::ser::Serialize for SmallBox<T, S> {
+	fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+	where
+		Ser: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, T: Deserialize + ?Sized + 'static, S> serde::de::Deserialize<'de> for SmallBox<T, S> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		let mut storage = MaybeUninit::<S>::uninit();
+		let mut heap = None;
+		let raw: *mut T = deserialize_in(deserializer, &mut |layout| {
+			place::<S>(&mut storage, &mut heap, layout)
+		})?;
+		Ok(Self::from_parts(metatype::Type::meta(raw), storage, heap))
+	}
+}
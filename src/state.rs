@@ -0,0 +1,208 @@
+//! Stateful (de)serialization, in the style of `serde_state`, for concrete types that need state beyond their serialized form.
+//!
+//! A concrete type that implements [`SerializeState`] and [`DeserializeState`] is (de)serialized with access to a state, like an interning table or a session, provided at the top-level call: [`WithState`] serializes a trait object with a `&S`, and [`StateSeed`] deserializes one with a `&mut S`. The state is threaded through the erased path to the concrete type, whose `Serialize` and `Deserialize` impls delegate to [`serialize`] and [`deserialize`], such that it can still be a trait object.
+//!
+//! While a concrete type's [`DeserializeState::deserialize_state`] has the state, it isn't available to any trait objects nested within it unless passed on, by deserializing them with a [`StateSeed`] of its own.
+//!
+//! # Example
+//! ```
+//! extern crate serde;
+//! extern crate serde_json;
+//! extern crate serde_traitobject as s;
+//!
+//! use s::state::{DeserializeState, SerializeState, StateSeed, WithState};
+//! use serde::de::DeserializeSeed;
+//!
+//! struct Session {
+//!     names: Vec<String>,
+//!     loaded: usize,
+//! }
+//!
+//! #[derive(Debug)]
+//! struct User {
+//!     name: String,
+//! }
+//! impl SerializeState<Session> for User {
+//!     fn serialize_state<S: serde::Serializer>(
+//!         &self, serializer: S, state: &Session,
+//!     ) -> Result<S::Ok, S::Error> {
+//!         let index = state.names.iter().position(|name| *name == self.name).unwrap();
+//!         serializer.serialize_u64(index as u64)
+//!     }
+//! }
+//! impl DeserializeState<Session> for User {
+//!     fn deserialize_state<'de, D: serde::Deserializer<'de>>(
+//!         state: &mut Session, deserializer: D,
+//!     ) -> Result<Self, D::Error> {
+//!         let index: u64 = serde::Deserialize::deserialize(deserializer)?;
+//!         state.loaded += 1;
+//!         Ok(User { name: state.names[index as usize].clone() })
+//!     }
+//! }
+//! impl serde::Serialize for User {
+//!     fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+//!         s::state::serialize::<Session, _, _>(self, serializer)
+//!     }
+//! }
+//! impl<'de> serde::Deserialize<'de> for User {
+//!     fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+//!         s::state::deserialize::<Session, _, _>(deserializer)
+//!     }
+//! }
+//!
+//! let mut session = Session { names: vec![String::from("ada")], loaded: 0 };
+//! let user: s::Box<dyn s::Debug> = s::Box::new(User { name: String::from("ada") });
+//!
+//! let serialized = serde_json::to_string(&WithState::new(&user, &session)).unwrap();
+//! let user = StateSeed::<dyn s::Debug, _>::new(&mut session)
+//!     .deserialize(&mut serde_json::Deserializer::from_str(&serialized))
+//!     .unwrap();
+//! assert_eq!(format!("{:?}", user), "User { name: \"ada\" }");
+//! assert_eq!(session.loaded, 1);
+//! ```
+
+use std::{any, cell::Cell, fmt, marker};
+
+use super::{Box, Deserialize};
+
+thread_local! {
+	static SERIALIZE_STATE: Cell<Option<*const dyn any::Any>> = const { Cell::new(None) };
+	static DESERIALIZE_STATE: Cell<Option<*mut dyn any::Any>> = const { Cell::new(None) };
+}
+
+/// A type that serializes with access to a state `S`.
+pub trait SerializeState<S: ?Sized> {
+	/// Serialize `self` with `state`.
+	fn serialize_state<Ser>(&self, serializer: Ser, state: &S) -> Result<Ser::Ok, Ser::Error>
+	where
+		Ser: serde::Serializer;
+}
+
+/// A type that deserializes with access to a state `S`.
+pub trait DeserializeState<S: ?Sized>: Sized {
+	/// Deserialize a value with `state`.
+	fn deserialize_state<'de, D>(state: &mut S, deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>;
+}
+
+/// Serialize `t` with the state provided by the [`WithState`] being serialized, for use in its `Serialize` impl.
+pub fn serialize<S: any::Any, T: SerializeState<S> + ?Sized, Ser>(
+	t: &T, serializer: Ser,
+) -> Result<Ser::Ok, Ser::Error>
+where
+	Ser: serde::Serializer,
+{
+	let state = SERIALIZE_STATE
+		.with(Cell::get)
+		.and_then(|state| unsafe { &*state }.downcast_ref::<S>());
+	let Some(state) = state else {
+		return Err(serde::ser::Error::custom(format_args!(
+			"no \"{}\" state to serialize with; serialize with state::WithState",
+			any::type_name::<S>()
+		)));
+	};
+	t.serialize_state(serializer, state)
+}
+
+/// Deserialize a `T` with the state provided by the [`StateSeed`] deserializing, for use in its `Deserialize` impl.
+pub fn deserialize<'de, S: any::Any, T: DeserializeState<S>, D>(
+	deserializer: D,
+) -> Result<T, D::Error>
+where
+	D: serde::Deserializer<'de>,
+{
+	// The state is taken while it's mutably borrowed, such that nothing nested can borrow it again.
+	let guard = Guard(DESERIALIZE_STATE.with(Cell::take));
+	let state = guard
+		.0
+		.and_then(|state| unsafe { &mut *state }.downcast_mut::<S>());
+	let Some(state) = state else {
+		return Err(serde::de::Error::custom(format_args!(
+			"no \"{}\" state to deserialize with; deserialize with state::StateSeed",
+			any::type_name::<S>()
+		)));
+	};
+	T::deserialize_state(state, deserializer)
+}
+
+/// Restores the state being deserialized with when dropped.
+struct Guard(Option<*mut dyn any::Any>);
+impl Drop for Guard {
+	fn drop(&mut self) {
+		DESERIALIZE_STATE.with(|scoped| scoped.set(self.0));
+	}
+}
+
+/// A value that serializes with `state` available to the [`SerializeState`] concrete types within it.
+pub struct WithState<'a, T: ?Sized, S> {
+	value: &'a T,
+	state: &'a S,
+}
+impl<'a, T: ?Sized, S> WithState<'a, T, S> {
+	/// Serialize `value` with `state`.
+	pub fn new(value: &'a T, state: &'a S) -> Self {
+		Self { value, state }
+	}
+}
+impl<T: ?Sized, S> fmt::Debug for WithState<'_, T, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("WithState")
+			.field("state", &any::type_name::<S>())
+			.finish_non_exhaustive()
+	}
+}
+impl<T: serde::ser::Serialize + ?Sized, S: any::Any> serde::ser::Serialize for WithState<'_, T, S> {
+	fn serialize<Ser>(&self, serializer: Ser) -> Result<Ser::Ok, Ser::Error>
+	where
+		Ser: serde::Serializer,
+	{
+		struct Guard(Option<*const dyn any::Any>);
+		impl Drop for Guard {
+			fn drop(&mut self) {
+				SERIALIZE_STATE.with(|scoped| scoped.set(self.0));
+			}
+		}
+		let state: *const dyn any::Any = self.state;
+		let _guard = Guard(SERIALIZE_STATE.with(|scoped| scoped.replace(Some(state))));
+		self.value.serialize(serializer)
+	}
+}
+
+/// A [`DeserializeSeed`](serde::de::DeserializeSeed) for a `Box<T>` with `state` available to the [`DeserializeState`] concrete types within it.
+pub struct StateSeed<'s, T: ?Sized, S> {
+	state: &'s mut S,
+	marker: marker::PhantomData<fn() -> Box<T>>,
+}
+impl<'s, T: Deserialize + ?Sized + 'static, S: any::Any> StateSeed<'s, T, S> {
+	/// Deserialize with `state`.
+	pub fn new(state: &'s mut S) -> Self {
+		Self {
+			state,
+			marker: marker::PhantomData,
+		}
+	}
+}
+impl<T: ?Sized, S> fmt::Debug for StateSeed<'_, T, S> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.debug_struct("StateSeed")
+			.field("trait_object", &any::type_name::<T>())
+			.field("state", &any::type_name::<S>())
+			.finish_non_exhaustive()
+	}
+}
+impl<'de, T: Deserialize + ?Sized + 'static, S: any::Any> serde::de::DeserializeSeed<'de>
+	for StateSeed<'_, T, S>
+{
+	type Value = Box<T>;
+
+	fn deserialize<D>(self, deserializer: D) -> Result<Box<T>, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		// The state is only accessed through the pointer while the guard is alive.
+		let state: *mut dyn any::Any = self.state;
+		let _guard = Guard(DESERIALIZE_STATE.with(|scoped| scoped.replace(Some(state))));
+		<Box<T> as serde::de::Deserialize>::deserialize(deserializer)
+	}
+}
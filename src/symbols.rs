@@ -0,0 +1,120 @@
+//! Resolution of vtables via the loaded object containing them, for dynamic linking conditions under which the offsets between objects differ across invocations.
+//!
+//! With the `symbols` feature enabled, rather than its offset from a vtable in this crate, a vtable is serialized as a hash of the file name of the loaded object containing it in the upper 32 bits, and its offset within that object in the lower 32 bits. It's resolved when deserialized by looking up the loaded object of that name, and checked to lie within that object's read-only segments, as given by [`image::contains`].
+//!
+//! This supersedes the [`anchors`](crate::anchors): with the feature enabled, vtables are neither serialized relative to registered anchors, nor resolved from them.
+//!
+//! The loaded objects are enumerated once and cached, and only enumerated afresh when an object can't be found among them, as when it's been loaded since.
+
+use std::{
+	convert::TryFrom, ffi::CStr, ops::Range, sync::{PoisonError, RwLock}
+};
+
+use super::{cipher, image};
+
+/// A loaded object.
+struct Object {
+	/// The hash of its file name.
+	name: u32,
+	/// The address it's loaded at, that offsets within it are relative to.
+	base: usize,
+	/// Its loaded segments.
+	segments: Vec<Range<usize>>,
+}
+
+/// The loaded objects, as of the last time they were enumerated.
+static OBJECTS: RwLock<Vec<Object>> = RwLock::new(Vec::new());
+
+unsafe extern "C" fn callback(
+	info: *mut libc::dl_phdr_info, _size: libc::size_t, data: *mut libc::c_void,
+) -> libc::c_int {
+	let objects = unsafe { &mut *data.cast::<Vec<Object>>() };
+	let info = unsafe { &*info };
+	if info.dlpi_phdr.is_null() {
+		return 0;
+	}
+	let Ok(base) = usize::try_from(info.dlpi_addr) else {
+		return 0;
+	};
+	let phdrs = unsafe { std::slice::from_raw_parts(info.dlpi_phdr, info.dlpi_phnum.into()) };
+	let segments = phdrs
+		.iter()
+		.filter(|phdr| phdr.p_type == libc::PT_LOAD)
+		.filter_map(|phdr| {
+			let start = usize::try_from(info.dlpi_addr.wrapping_add(phdr.p_vaddr)).ok()?;
+			let end = start.checked_add(usize::try_from(phdr.p_memsz).ok()?)?;
+			Some(start..end)
+		})
+		.collect();
+	let name = if info.dlpi_name.is_null() {
+		hash(b"")
+	} else {
+		hash(unsafe { CStr::from_ptr(info.dlpi_name) }.to_bytes())
+	};
+	objects.push(Object {
+		name,
+		base,
+		segments,
+	});
+	0
+}
+
+/// The first of the loaded objects that `f` returns `Some` for, enumerating them afresh if none of those cached does.
+fn find<F, R>(f: F) -> Option<R>
+where
+	F: Fn(&Object) -> Option<R>,
+{
+	if let Some(found) = OBJECTS
+		.read()
+		.unwrap_or_else(PoisonError::into_inner)
+		.iter()
+		.find_map(&f)
+	{
+		return Some(found);
+	}
+	let mut objects = Vec::new();
+	let _ = unsafe { libc::dl_iterate_phdr(Some(callback), (&raw mut objects).cast()) };
+	let found = objects.iter().find_map(&f);
+	*OBJECTS.write().unwrap_or_else(PoisonError::into_inner) = objects;
+	found
+}
+
+/// A 32-bit hash of the file name of a loaded object, ignoring the directory it was loaded from. The main program's is that of the empty name.
+fn hash(name: &[u8]) -> u32 {
+	let name = name.rsplit(|&byte| byte == b'/').next().unwrap_or(name);
+	let hash = name
+		.iter()
+		.fold(0, |hash, &byte| cipher::mix(hash ^ u64::from(byte)));
+	(hash >> 32) as u32
+}
+
+/// The serialized offset of `address`, or `None` if it lies within no loaded object, or more than 4 GiB into one.
+pub(crate) fn offset(address: usize) -> Option<u64> {
+	find(|object| {
+		let contains = object
+			.segments
+			.iter()
+			.any(|segment| segment.contains(&address));
+		contains.then(|| {
+			u32::try_from(address - object.base)
+				.ok()
+				.map(|offset| (u64::from(object.name) << 32) | u64::from(offset))
+		})
+	})
+	.flatten()
+}
+
+/// The address at the serialized `offset`, or `None` if the object it refers to isn't loaded, or the `len` bytes there don't lie within its read-only segments.
+pub(crate) fn resolve(offset: u64, len: usize) -> Option<usize> {
+	let name = (offset >> 32) as u32;
+	let offset = usize::try_from(offset & u64::from(u32::MAX)).ok()?;
+	let (address, segment) = find(|object| {
+		(object.name == name).then(|| {
+			let segment = object.segments.first().map(|segment| segment.start);
+			(object.base.wrapping_add(offset), segment)
+		})
+	})?;
+	// Any address within the object's loaded segments identifies it to `image::contains`.
+	(len == 0 || segment.is_some_and(|segment| image::contains(segment, address, len)))
+		.then_some(address)
+}
@@ -0,0 +1,283 @@
+//! (De)serialize a trait object tagged with the name of its concrete type, rather than its vtable.
+//!
+//! The default encoding identifies the concrete type by its vtable offset, which is only meaningful to the binary that wrote it. This module instead writes a name, which is resolved on deserialization via a registry that's populated with [`register`] or [`register_as`]. As such it can be used to persist trait objects in a form that survives recompilation, so long as the registered names and the concrete types' own encodings remain stable.
+//!
+//! Types that haven't been registered are serialized with their [`std::any::type_name`], which is what [`register`] registers them under. As that's not guaranteed to be stable across compiler versions, prefer [`register_as`] for data that outlives the build.
+//!
+//! # Example
+//! ```
+//! # use serde_derive::{Serialize, Deserialize};
+//! extern crate serde_json;
+//! extern crate serde_traitobject as s;
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct MyStruct {
+//!     #[serde(with = "serde_traitobject::tagged")]
+//!     field: Box<dyn serde_traitobject::Debug>,
+//! }
+//!
+//! s::tagged::register_as::<dyn s::Debug, String>("string");
+//!
+//! let my_struct = MyStruct {
+//!     field: Box::new(String::from("tagged")),
+//! };
+//! let serialized = serde_json::to_string(&my_struct).unwrap();
+//! assert_eq!(serialized, r#"{"field":{"type":"string","value":"tagged"}}"#);
+//!
+//! let deserialized: MyStruct = serde_json::from_str(&serialized).unwrap();
+//! println!("{:?}", deserialized.field);
+//! # assert_eq!(format!("{:?}", deserialized.field), "\"tagged\"");
+//! // "tagged"
+//! ```
+
+use serde::ser::SerializeStruct;
+use std::{any, collections::HashMap, fmt, marker, ptr, sync};
+
+use super::{deserialize as de, serialize as ser, type_coerce, Serialize, SerializeErased};
+
+/// Deserializes a concrete type and erases it to `T`.
+type Constructor<T> =
+	for<'de> fn(&mut dyn erased_serde::Deserializer<'de>) -> Result<Box<T>, erased_serde::Error>;
+
+struct Entry {
+	/// The id of the concrete type.
+	type_id: u64,
+	/// A [`Constructor`] for the trait object type.
+	constructor: Box<dyn any::Any + Send + Sync>,
+	/// The address of the concrete type's vtable for the trait object type, if it is one.
+	vtable: Option<usize>,
+}
+
+#[derive(Default)]
+struct Registry {
+	/// Keyed by the trait object type and the registered name.
+	constructors: HashMap<(any::TypeId, &'static str), Entry>,
+	/// The registered name, keyed by the trait object type and the id of the concrete type.
+	names: HashMap<(any::TypeId, u64), &'static str>,
+}
+
+static REGISTRY: sync::LazyLock<sync::RwLock<Registry>> = sync::LazyLock::new(Default::default);
+
+/// Register `T` under its [`std::any::type_name`], such that it can be deserialized as a `Trait` trait object.
+///
+/// # Panics
+///
+/// If a different type has already been registered under the same name for `Trait`.
+pub fn register<Trait: ?Sized + 'static, T>()
+where
+	T: serde::de::DeserializeOwned + marker::Unsize<Trait> + 'static,
+{
+	register_as::<Trait, T>(any::type_name::<T>());
+}
+
+/// Register `T` under `name`, such that it can be deserialized as a `Trait` trait object. `T` is then serialized with this name when serialized as a `Trait` trait object.
+///
+/// Registering the same type under the same name again has no effect.
+///
+/// # Panics
+///
+/// If a different type has already been registered under `name` for `Trait`.
+pub fn register_as<Trait: ?Sized + 'static, T>(name: &'static str)
+where
+	T: serde::de::DeserializeOwned + marker::Unsize<Trait> + 'static,
+{
+	fn construct<Trait: ?Sized, T>(
+		deserializer: &mut dyn erased_serde::Deserializer<'_>,
+	) -> Result<Box<Trait>, erased_serde::Error>
+	where
+		T: serde::de::DeserializeOwned + marker::Unsize<Trait>,
+	{
+		erased_serde::deserialize::<T>(deserializer).map(|mut t| -> Box<Trait> {
+			de::post_deserialize(&mut t);
+			Box::<T>::new(t)
+		})
+	}
+	let trait_ = any::TypeId::of::<Trait>();
+	let type_id = metatype::type_id::<T>();
+	let mut registry = REGISTRY
+		.write()
+		.unwrap_or_else(sync::PoisonError::into_inner);
+	if let Some(existing) = registry.constructors.get(&(trait_, name)) {
+		assert_eq!(
+			existing.type_id,
+			type_id,
+			"a different type has already been registered as \"{}\" for \"{}\"",
+			name,
+			any::type_name::<Trait>()
+		);
+		return;
+	}
+	let constructor: Constructor<Trait> = construct::<Trait, T>;
+	let object: *const Trait = ptr::NonNull::<T>::dangling().as_ptr();
+	let vtable = if let metatype::MetaType::TraitObject = metatype::Type::meta_type(object) {
+		let trait_object: metatype::TraitObject = type_coerce(metatype::Type::meta(object));
+		Some(ptr::from_ref(trait_object.vtable) as usize)
+	} else {
+		None
+	};
+	let _ = registry.constructors.insert(
+		(trait_, name),
+		Entry {
+			type_id,
+			constructor: Box::new(constructor),
+			vtable,
+		},
+	);
+	let _ = registry.names.insert((trait_, type_id), name);
+}
+
+/// The vtable of the concrete type registered with the id `type_id` for the `Trait` trait object type, if any.
+pub(crate) fn vtable<Trait: ?Sized + 'static>(type_id: u64) -> Option<&'static ()> {
+	let registry = REGISTRY
+		.read()
+		.unwrap_or_else(sync::PoisonError::into_inner);
+	let trait_ = any::TypeId::of::<Trait>();
+	let name = registry.names.get(&(trait_, type_id))?;
+	let vtable = registry.constructors.get(&(trait_, *name))?.vtable?;
+	Some(unsafe { &*(vtable as *const ()) })
+}
+
+/// The field names of the tagged encoding, in serialization order.
+const FIELDS: [&str; 2] = ["type", "value"];
+#[derive(Copy, Clone)]
+enum Field {
+	Type,
+	Value,
+}
+impl<'de> serde::de::Deserialize<'de> for Field {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		struct Visitor;
+		impl serde::de::Visitor<'_> for Visitor {
+			type Value = Field;
+			fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+				formatter.write_str("a tagged trait object field identifier")
+			}
+			fn visit_u64<E>(self, v: u64) -> Result<Field, E>
+			where
+				E: serde::de::Error,
+			{
+				match v {
+					0 => Ok(Field::Type),
+					1 => Ok(Field::Value),
+					_ => Err(E::invalid_value(serde::de::Unexpected::Unsigned(v), &self)),
+				}
+			}
+			fn visit_str<E>(self, v: &str) -> Result<Field, E>
+			where
+				E: serde::de::Error,
+			{
+				match v {
+					"type" => Ok(Field::Type),
+					"value" => Ok(Field::Value),
+					_ => Err(E::unknown_field(v, &FIELDS)),
+				}
+			}
+		}
+		deserializer.deserialize_identifier(Visitor)
+	}
+}
+
+/// Serialize a value by reference, tagged with the name of its concrete type.
+pub fn serialize<T: Serialize + ?Sized + 'static, B: AsRef<T> + ?Sized, S>(
+	t: &B, serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	let t = t.as_ref();
+	let name = REGISTRY
+		.read()
+		.unwrap_or_else(sync::PoisonError::into_inner)
+		.names
+		.get(&(any::TypeId::of::<T>(), <T as ser::Sealed>::type_id(t)))
+		.copied()
+		.unwrap_or_else(|| <T as ser::Sealed>::serialize_type_name(t));
+	let mut state = serializer.serialize_struct("TaggedTraitObject", 2)?;
+	state.serialize_field(FIELDS[0], name)?;
+	state.serialize_field(FIELDS[1], &SerializeErased(t))?;
+	state.end()
+}
+
+/// Deserialize a value `T` into `B` where `Box<T>: Into<B>`, resolving its concrete type by name from the registry.
+pub fn deserialize<'de, T: ?Sized + 'static, B, D>(deserializer: D) -> Result<B, D::Error>
+where
+	D: serde::Deserializer<'de>,
+	Box<T>: Into<B>,
+{
+	struct Visitor<T: ?Sized>(marker::PhantomData<fn() -> T>);
+	impl<'de, T: ?Sized + 'static> serde::de::Visitor<'de> for Visitor<T> {
+		type Value = Box<T>;
+		fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+			write!(
+				formatter,
+				"a tagged \"{}\" trait object",
+				any::type_name::<T>()
+			)
+		}
+		fn visit_seq<A>(self, mut seq: A) -> Result<Box<T>, A::Error>
+		where
+			A: serde::de::SeqAccess<'de>,
+		{
+			let name: String = match seq.next_element()? {
+				Some(value) => value,
+				None => return Err(serde::de::Error::invalid_length(0, &self)),
+			};
+			match seq.next_element_seed(Construct(constructor(&name)?))? {
+				Some(value) => Ok(value),
+				None => Err(serde::de::Error::invalid_length(1, &self)),
+			}
+		}
+		fn visit_map<A>(self, mut map: A) -> Result<Box<T>, A::Error>
+		where
+			A: serde::de::MapAccess<'de>,
+		{
+			let mut name: Option<String> = None;
+			while let Some(field) = map.next_key::<Field>()? {
+				match field {
+					Field::Type if name.is_none() => name = Some(map.next_value()?),
+					Field::Type => return Err(serde::de::Error::duplicate_field(FIELDS[0])),
+					Field::Value => {
+						let name =
+							name.ok_or_else(|| serde::de::Error::missing_field(FIELDS[0]))?;
+						return map.next_value_seed(Construct(constructor(&name)?));
+					}
+				}
+			}
+			Err(serde::de::Error::missing_field(FIELDS[1]))
+		}
+	}
+	fn constructor<T: ?Sized + 'static, E: serde::de::Error>(
+		name: &str,
+	) -> Result<Constructor<T>, E> {
+		REGISTRY
+			.read()
+			.unwrap_or_else(sync::PoisonError::into_inner)
+			.constructors
+			.get(&(any::TypeId::of::<T>(), name))
+			.map(|entry| *entry.constructor.downcast_ref::<Constructor<T>>().unwrap())
+			.ok_or_else(|| {
+				E::custom(format_args!(
+					"no type has been registered as \"{}\" for \"{}\"",
+					name,
+					any::type_name::<T>()
+				))
+			})
+	}
+	struct Construct<T: ?Sized>(Constructor<T>);
+	impl<'de, T: ?Sized> serde::de::DeserializeSeed<'de> for Construct<T> {
+		type Value = Box<T>;
+		fn deserialize<D>(self, deserializer: D) -> Result<Box<T>, D::Error>
+		where
+			D: serde::Deserializer<'de>,
+		{
+			let deserializer = &mut <dyn erased_serde::Deserializer>::erase(deserializer);
+			(self.0)(deserializer).map_err(serde::de::Error::custom)
+		}
+	}
+	deserializer
+		.deserialize_struct("TaggedTraitObject", &FIELDS, Visitor(marker::PhantomData))
+		.map(<Box<T> as Into<B>>::into)
+}
@@ -0,0 +1,232 @@
+use std::{
+	alloc::{self, Layout}, borrow::{Borrow, BorrowMut}, fmt, marker, mem, ops::{Deref, DerefMut}, ptr::{self, NonNull}
+};
+
+use super::{deserialize, deserialize_in, serialize, Deserialize, Serialize};
+
+/// The header stored at the start of every [`Thin`] allocation.
+///
+/// `offset` is the distance in bytes from the start of the allocation to the value, which depends on the value's alignment.
+#[repr(C)]
+struct Header<M> {
+	meta: M,
+	offset: usize,
+}
+
+/// A pointer-sized alternative to [std::boxed::Box<T>](std::boxed::Box) that stores the metadata of `T` (i.e. the vtable pointer for trait objects, or the length for slices) inline with the allocation, and automatically uses `serde_traitobject` for (de)serialization.
+///
+/// It serializes identically to [`Box<T>`](crate::Box), so the two are interchangeable on the wire.
+///
+/// # Example
+/// ```
+/// extern crate serde_json;
+/// extern crate serde_traitobject as s;
+///
+/// use std::mem::size_of;
+///
+/// let thin: s::Thin<dyn s::Debug> = s::Thin::from(Box::new(String::from("thin")) as Box<dyn s::Debug>);
+/// assert_eq!(size_of::<s::Thin<dyn s::Debug>>(), size_of::<usize>());
+///
+/// let serialized = serde_json::to_string(&thin).unwrap();
+/// let deserialized: s::Box<dyn s::Debug> = serde_json::from_str(&serialized).unwrap();
+///
+/// println!("{:?}", deserialized);
+/// # assert_eq!(format!("{:?}", deserialized), "\"thin\"");
+/// // "thin"
+/// ```
+pub struct Thin<T: ?Sized> {
+	ptr: NonNull<Header<<T as metatype::Type>::Meta>>,
+	marker: marker::PhantomData<T>,
+}
+unsafe impl<T: ?Sized + Send> Send for Thin<T> {}
+unsafe impl<T: ?Sized + Sync> Sync for Thin<T> {}
+impl<T> Thin<T> {
+	/// Create a new Thin wrapper
+	pub fn new(t: T) -> Self {
+		let (header, _, value) = Self::allocate(Layout::new::<T>());
+		let value = value.cast::<T>().as_ptr();
+		unsafe {
+			value.write(t);
+			Self::from_parts(header, metatype::Type::meta(value))
+		}
+	}
+}
+impl<T: ?Sized> Thin<T> {
+	/// Move the contents of a `std::boxed::Box<T>` into a new Thin allocation.
+	pub fn from_box(t: Box<T>) -> Self {
+		let mut header = NonNull::dangling();
+		let value = deserialize::unbox(t, &mut |layout| {
+			let (allocated, _, value) = Self::allocate(layout);
+			header = allocated;
+			value
+		});
+		unsafe { Self::from_parts(header, metatype::Type::meta(value)) }
+	}
+	/// Convert to a regular `std::boxed::Box<T>`.
+	pub fn into_box(self) -> Box<T> {
+		let this = mem::ManuallyDrop::new(self);
+		let value: *mut T = this.as_ptr();
+		let value_layout = Layout::for_value::<T>(unsafe { &*value });
+		unsafe {
+			let raw = if value_layout.size() == 0 {
+				value_layout.align() as *mut u8
+			} else {
+				let raw = alloc::alloc(value_layout);
+				if raw.is_null() {
+					alloc::handle_alloc_error(value_layout);
+				}
+				ptr::copy_nonoverlapping(value.cast::<u8>(), raw, value_layout.size());
+				raw
+			};
+			let boxed = Box::from_raw(metatype::Type::fatten(
+				raw.cast(),
+				ptr::read(ptr::addr_of!((*this.ptr.as_ptr()).meta)),
+			));
+			alloc::dealloc(this.ptr.as_ptr().cast(), Self::layout(value_layout).0);
+			boxed
+		}
+	}
+	/// Allocates a header followed by a value of `value_layout`, and writes the value's offset into the header. Returns the header, the layout of the allocation, and a pointer to where the value is to be written.
+	fn allocate(
+		value_layout: Layout,
+	) -> (
+		NonNull<Header<<T as metatype::Type>::Meta>>,
+		Layout,
+		NonNull<u8>,
+	) {
+		let (layout, offset) = Self::layout(value_layout);
+		// The header isn't zero-sized, so neither is the allocation.
+		let header = deserialize::heap(layout).cast::<Header<<T as metatype::Type>::Meta>>();
+		unsafe {
+			ptr::addr_of_mut!((*header.as_ptr()).offset).write(offset);
+			let value = NonNull::new_unchecked(header.as_ptr().cast::<u8>().add(offset));
+			(header, layout, value)
+		}
+	}
+	/// Completes an allocation made by [`allocate`](Self::allocate), once the value has been written, by writing its metadata into the header.
+	unsafe fn from_parts(
+		header: NonNull<Header<<T as metatype::Type>::Meta>>, meta: <T as metatype::Type>::Meta,
+	) -> Self {
+		unsafe { ptr::addr_of_mut!((*header.as_ptr()).meta).write(meta) };
+		Self {
+			ptr: header,
+			marker: marker::PhantomData,
+		}
+	}
+	fn layout(value_layout: Layout) -> (Layout, usize) {
+		let (layout, offset) = Layout::new::<Header<<T as metatype::Type>::Meta>>()
+			.extend(value_layout)
+			.expect("layout overflow");
+		(layout.pad_to_align(), offset)
+	}
+	fn as_ptr(&self) -> *mut T {
+		unsafe {
+			let header = self.ptr.as_ptr();
+			// The metadata types (vtable references, lengths and `()`) are all
+			// `Copy`, so reading them out leaves the header intact.
+			metatype::Type::fatten(
+				header.cast::<u8>().add((*header).offset).cast(),
+				ptr::read(ptr::addr_of!((*header).meta)),
+			)
+		}
+	}
+}
+impl<T: ?Sized> Drop for Thin<T> {
+	fn drop(&mut self) {
+		let value: *mut T = self.as_ptr();
+		let value_layout = Layout::for_value::<T>(unsafe { &*value });
+		unsafe {
+			ptr::drop_in_place(value);
+			alloc::dealloc(self.ptr.as_ptr().cast(), Self::layout(value_layout).0);
+		}
+	}
+}
+impl<T: ?Sized> Deref for Thin<T> {
+	type Target = T;
+	fn deref(&self) -> &Self::Target {
+		unsafe { &*self.as_ptr() }
+	}
+}
+impl<T: ?Sized> DerefMut for Thin<T> {
+	fn deref_mut(&mut self) -> &mut Self::Target {
+		unsafe { &mut *self.as_ptr() }
+	}
+}
+impl<T: ?Sized> AsRef<T> for Thin<T> {
+	fn as_ref(&self) -> &T {
+		self
+	}
+}
+impl<T: ?Sized> AsMut<T> for Thin<T> {
+	fn as_mut(&mut self) -> &mut T {
+		self
+	}
+}
+impl<T: ?Sized> Borrow<T> for Thin<T> {
+	fn borrow(&self) -> &T {
+		self
+	}
+}
+impl<T: ?Sized> BorrowMut<T> for Thin<T> {
+	fn borrow_mut(&mut self) -> &mut T {
+		self
+	}
+}
+impl<T: ?Sized> From<Box<T>> for Thin<T> {
+	fn from(t: Box<T>) -> Self {
+		Self::from_box(t)
+	}
+}
+impl<T> From<T> for Thin<T> {
+	fn from(t: T) -> Self {
+		Self::new(t)
+	}
+}
+impl<T: fmt::Debug + ?Sized> fmt::Debug for Thin<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		(**self).fmt(f)
+	}
+}
+impl<T: fmt::Display + ?Sized> fmt::Display for Thin<T> {
+	fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+		(**self).fmt(f)
+	}
+}
+impl<T: Serialize + ?Sized + 'static> serde::ser::Serialize for Thin<T> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		serialize(self, serializer)
+	}
+}
+impl<'de, T: Deserialize + ?Sized + 'static> serde::de::Deserialize<'de> for Thin<T> {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: serde::Deserializer<'de>,
+	{
+		// The header and value are allocated together, and the value is
+		// deserialized directly into it.
+		let mut allocation = None;
+		let raw: Result<*mut T, D::Error> = deserialize_in(deserializer, &mut |layout| {
+			let (header, layout, value) = Self::allocate(layout);
+			allocation = Some((header, layout));
+			value
+		});
+		match (raw, allocation) {
+			(Ok(raw), Some((header, _))) => {
+				Ok(unsafe { Self::from_parts(header, metatype::Type::meta(raw)) })
+			}
+			(Ok(_), None) => unreachable!(),
+			(Err(error), allocation) => {
+				// If the deserializer failed after the value was written, e.g.
+				// on trailing input, its metadata isn't known so it can't be
+				// dropped, but the allocation is freed.
+				if let Some((header, layout)) = allocation {
+					unsafe { alloc::dealloc(header.as_ptr().cast(), layout) };
+				}
+				Err(error)
+			}
+		}
+	}
+}
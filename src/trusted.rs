@@ -0,0 +1,65 @@
+//! Trusted mode, skipping validation of trait objects known to come from this process.
+//!
+//! When trait objects are only ever exchanged between threads of the same process, or with a process forked from it, the validation data serialized alongside them is pure overhead. With the `trusted` feature enabled, trusted mode can be entered, either globally with [`set_trusted`] or for the duration of a closure with [`with_trusted`], in which deserialization skips checking the target, build id, type ids and integrity of each trait object, and that its vtable lies within this binary.
+//!
+//! A key installed with [`auth`](crate::auth) is still verified, and an installed [`Validator`](crate::validate::Validator) still invoked.
+//!
+//! # Safety
+//!
+//! In trusted mode a corrupted or malicious trait object is used as is, which is undefined behavior. Only enter it for input that was serialized by this binary, in this process or one forked from it, and that can't have been modified since.
+//!
+//! # Example
+//! ```
+//! extern crate bincode;
+//! extern crate serde_traitobject as s;
+//!
+//! let message: s::Box<dyn s::Debug> = s::Box::new(String::from("same process"));
+//! let serialized = bincode::serialize(&message).unwrap();
+//!
+//! let deserialized: s::Box<dyn s::Debug> =
+//!     unsafe { s::trusted::with_trusted(|| bincode::deserialize(&serialized)) }.unwrap();
+//! assert_eq!(format!("{:?}", deserialized), "\"same process\"");
+//! ```
+
+use std::{
+	cell::Cell, sync::atomic::{AtomicBool, Ordering}
+};
+
+static TRUSTED: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+	static SCOPED_TRUSTED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Enter trusted mode for all threads, or leave it if `false`.
+///
+/// # Safety
+///
+/// While in trusted mode, only trait objects serialized by this binary, in this process or one forked from it, and unmodified since, may be deserialized. See the [module documentation](self).
+pub unsafe fn set_trusted(trusted: bool) {
+	TRUSTED.store(trusted, Ordering::Relaxed);
+}
+
+/// Run `f` in trusted mode on this thread, such that the trait objects deserialized by it skip validation.
+///
+/// # Safety
+///
+/// `f` may only deserialize trait objects serialized by this binary, in this process or one forked from it, and unmodified since. See the [module documentation](self).
+pub unsafe fn with_trusted<F, R>(f: F) -> R
+where
+	F: FnOnce() -> R,
+{
+	struct Guard(bool);
+	impl Drop for Guard {
+		fn drop(&mut self) {
+			SCOPED_TRUSTED.with(|scoped| scoped.set(self.0));
+		}
+	}
+	let _guard = Guard(SCOPED_TRUSTED.with(|scoped| scoped.replace(true)));
+	f()
+}
+
+/// Whether trusted mode has been entered, on this thread or globally.
+pub(crate) fn enabled() -> bool {
+	SCOPED_TRUSTED.with(Cell::get) || TRUSTED.load(Ordering::Relaxed)
+}
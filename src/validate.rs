@@ -0,0 +1,240 @@
+//! Custom validation of trait objects before they're reconstructed.
+//!
+//! A [`Validator`], installed either globally with [`set_validator`] or for the duration of a closure with [`with_validator`], is invoked with a [`HeaderInfo`] describing each trait object being deserialized. It's invoked after this crate's own validation, but before the vtable is used, giving a place to enforce allowlists of concrete types, log attempts, or reject payloads based on custom policy. With the `diagnostics` feature enabled, the [`HeaderInfo`] also carries the name of the concrete type.
+//!
+//! # Example
+//! ```
+//! extern crate bincode;
+//! extern crate serde_traitobject as s;
+//! # s::policy::set_policy(s::policy::Policy::Error);
+//!
+//! use s::validate::{HeaderInfo, ValidationError};
+//!
+//! let allow_strings = |info: &HeaderInfo| {
+//!     if info.is::<String>() {
+//!         Ok(())
+//!     } else {
+//!         Err(ValidationError::new("only strings are allowed"))
+//!     }
+//! };
+//!
+//! let string: s::Box<dyn s::Debug> = s::Box::new(String::from("allowed"));
+//! let string = bincode::serialize(&string).unwrap();
+//! let number: s::Box<dyn s::Debug> = s::Box::new(123_u32);
+//! let number = bincode::serialize(&number).unwrap();
+//!
+//! s::validate::with_validator(allow_strings, || {
+//!     let string: s::Box<dyn s::Debug> = bincode::deserialize(&string).unwrap();
+//!     assert_eq!(format!("{:?}", string), "\"allowed\"");
+//!     let number: Result<s::Box<dyn s::Debug>, _> = bincode::deserialize(&number);
+//!     assert!(number.unwrap_err().to_string().contains("only strings are allowed"));
+//! });
+//! ```
+//!
+//! For the common policy of only allowing certain concrete types for a trait object type, [`allow`] registers them, after which any other concrete type is rejected before its value is deserialized.
+//!
+//! ```
+//! extern crate bincode;
+//! extern crate serde_traitobject as s;
+//! # s::policy::set_policy(s::policy::Policy::Error);
+//!
+//! s::validate::allow::<dyn s::Debug, String>();
+//!
+//! let string: s::Box<dyn s::Debug> = s::Box::new(String::from("allowed"));
+//! let string = bincode::serialize(&string).unwrap();
+//! let number: s::Box<dyn s::Debug> = s::Box::new(123_u32);
+//! let number = bincode::serialize(&number).unwrap();
+//!
+//! let string: s::Box<dyn s::Debug> = bincode::deserialize(&string).unwrap();
+//! assert_eq!(format!("{:?}", string), "\"allowed\"");
+//! let number: Result<s::Box<dyn s::Debug>, _> = bincode::deserialize(&number);
+//! assert!(number.unwrap_err().to_string().contains("isn't allowed"));
+//! ```
+
+use std::{
+	any, cell::RefCell, error, fmt, sync::{Arc, PoisonError, RwLock}
+};
+
+/// A policy deciding whether a trait object may be deserialized.
+pub trait Validator: Send + Sync {
+	/// Accept or reject the trait object described by `info`.
+	fn validate(&self, info: &HeaderInfo<'_>) -> Result<(), ValidationError>;
+}
+impl<F> Validator for F
+where
+	F: Fn(&HeaderInfo<'_>) -> Result<(), ValidationError> + Send + Sync,
+{
+	fn validate(&self, info: &HeaderInfo<'_>) -> Result<(), ValidationError> {
+		self(info)
+	}
+}
+
+/// A description of a trait object being deserialized, as passed to a [`Validator`].
+#[derive(Clone, Copy, Debug)]
+pub struct HeaderInfo<'a> {
+	trait_object: &'static str,
+	trait_type_id: u64,
+	vtable_offset: u64,
+	type_id: Option<u64>,
+	type_name: Option<&'a str>,
+}
+impl<'a> HeaderInfo<'a> {
+	pub(crate) fn new<T: ?Sized + 'static>(
+		vtable_offset: u64, type_id: Option<u64>, type_name: Option<&'a str>,
+	) -> Self {
+		Self {
+			trait_object: any::type_name::<T>(),
+			trait_type_id: metatype::type_id::<T>(),
+			vtable_offset,
+			type_id,
+			type_name,
+		}
+	}
+	/// The name of the trait object type, like `dyn serde_traitobject::Debug`.
+	pub fn trait_object(&self) -> &'static str {
+		self.trait_object
+	}
+	/// The type id of the trait object type.
+	pub fn trait_type_id(&self) -> u64 {
+		self.trait_type_id
+	}
+	/// The offset of the vtable, relative to a static base.
+	pub fn vtable_offset(&self) -> u64 {
+		self.vtable_offset
+	}
+	/// The type id of the concrete type, if it's known before the vtable is used.
+	pub fn type_id(&self) -> Option<u64> {
+		self.type_id
+	}
+	/// The name of the concrete type, if it's known. It's serialized alongside trait objects with the `diagnostics` feature enabled, and as it's read from the payload, it's only as trustworthy as the payload.
+	pub fn type_name(&self) -> Option<&'a str> {
+		self.type_name
+	}
+	/// Whether the concrete type is known to be `C`.
+	pub fn is<C: ?Sized + 'static>(&self) -> bool {
+		self.type_id == Some(metatype::type_id::<C>())
+	}
+}
+
+/// The error a [`Validator`] rejects a trait object with.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct ValidationError(String);
+impl ValidationError {
+	/// Create a new `ValidationError` with the given message.
+	pub fn new(message: impl Into<String>) -> Self {
+		Self(message.into())
+	}
+}
+impl fmt::Display for ValidationError {
+	fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+		f.write_str(&self.0)
+	}
+}
+impl error::Error for ValidationError {}
+
+static VALIDATOR: RwLock<Option<Arc<dyn Validator>>> = RwLock::new(None);
+
+thread_local! {
+	static SCOPED_VALIDATOR: RefCell<Option<Arc<dyn Validator>>> = const { RefCell::new(None) };
+}
+
+/// Install `validator` for all threads, replacing any installed before.
+///
+/// A validator installed with [`with_validator`] takes precedence.
+pub fn set_validator<V: Validator + 'static>(validator: V) {
+	*VALIDATOR.write().unwrap_or_else(PoisonError::into_inner) = Some(Arc::new(validator));
+}
+
+/// Remove the validator installed with [`set_validator`], if any.
+pub fn remove_validator() {
+	*VALIDATOR.write().unwrap_or_else(PoisonError::into_inner) = None;
+}
+
+/// Run `f` with `validator` installed on this thread, such that it validates the trait objects deserialized by `f`.
+///
+/// This takes precedence over a validator installed with [`set_validator`], and any installed by an enclosing `with_validator`.
+pub fn with_validator<V: Validator + 'static, F, R>(validator: V, f: F) -> R
+where
+	F: FnOnce() -> R,
+{
+	struct Guard(Option<Arc<dyn Validator>>);
+	impl Drop for Guard {
+		fn drop(&mut self) {
+			SCOPED_VALIDATOR.with(|scoped| *scoped.borrow_mut() = self.0.take());
+		}
+	}
+	let validator: Arc<dyn Validator> = Arc::new(validator);
+	let _guard = Guard(SCOPED_VALIDATOR.with(|scoped| scoped.borrow_mut().replace(validator)));
+	f()
+}
+
+static ALLOWED: RwLock<Vec<(u64, u64)>> = RwLock::new(Vec::new());
+
+/// Allow `C` as a concrete type of `T` trait objects. Once any concrete type is allowed for `T`, trait objects of any other concrete type are rejected.
+///
+/// Allowing the same concrete type for a trait object type more than once has no further effect.
+pub fn allow<T: ?Sized + 'static, C: ?Sized + 'static>() {
+	let entry = (metatype::type_id::<T>(), metatype::type_id::<C>());
+	let mut allowed = ALLOWED.write().unwrap_or_else(PoisonError::into_inner);
+	if !allowed.contains(&entry) {
+		allowed.push(entry);
+	}
+}
+
+/// Remove the concrete types allowed for `T` trait objects with [`allow`], such that any concrete type is allowed again.
+pub fn disallow_all<T: ?Sized + 'static>() {
+	let trait_type_id = metatype::type_id::<T>();
+	ALLOWED
+		.write()
+		.unwrap_or_else(PoisonError::into_inner)
+		.retain(|&(t, _)| t != trait_type_id);
+}
+
+/// Whether the concrete type with id `type_id` is allowed for `T` trait objects: it has been [allowed](allow), or none have.
+pub(crate) fn allowed<T: ?Sized + 'static>(type_id: u64) -> bool {
+	let trait_type_id = metatype::type_id::<T>();
+	let allowed = ALLOWED.read().unwrap_or_else(PoisonError::into_inner);
+	let mut allowed = allowed
+		.iter()
+		.filter(|&&(t, _)| t == trait_type_id)
+		.peekable();
+	allowed.peek().is_none() || allowed.any(|&(_, c)| c == type_id)
+}
+
+thread_local! {
+	static TYPE_NAME: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Run `f`, which parses the vtable of a trait object, with the name of its concrete type, if it was serialized, available to the validator.
+pub(crate) fn with_type_name<F, R>(type_name: Option<&str>, f: F) -> R
+where
+	F: FnOnce() -> R,
+{
+	struct Guard(Option<String>);
+	impl Drop for Guard {
+		fn drop(&mut self) {
+			TYPE_NAME.with(|scoped| *scoped.borrow_mut() = self.0.take());
+		}
+	}
+	let type_name = type_name.map(str::to_owned);
+	let _guard = Guard(TYPE_NAME.with(|scoped| scoped.replace(type_name)));
+	f()
+}
+
+/// The name of the concrete type of the trait object whose vtable is being parsed, if it was serialized.
+pub(crate) fn scoped_type_name() -> Option<String> {
+	TYPE_NAME.with(|scoped| scoped.borrow().clone())
+}
+
+/// Invoke the installed validator, if any.
+pub(crate) fn validate(info: &HeaderInfo<'_>) -> Result<(), ValidationError> {
+	let validator = SCOPED_VALIDATOR
+		.with(|scoped| scoped.borrow().clone())
+		.or_else(|| {
+			VALIDATOR
+				.read()
+				.unwrap_or_else(PoisonError::into_inner)
+				.clone()
+		});
+	validator.map_or(Ok(()), |validator| validator.validate(info))
+}
@@ -0,0 +1,37 @@
+//! (De)serialize a `Vec` of trait object pointers.
+//!
+//! This is intended to enable:
+//! ```
+//! # use serde_derive::{Serialize, Deserialize};
+//! #[derive(Serialize, Deserialize)]
+//! struct MyStruct {
+//!     #[serde(with = "serde_traitobject::vec")]
+//!     field: Vec<Box<dyn serde_traitobject::Any>>,
+//! }
+//! ```
+
+use std::boxed;
+
+use super::{Box, Deserialize, Serialize, SerializeRef};
+
+/// Serialize a slice of `B` where `B: AsRef<T>`.
+pub fn serialize<T: Serialize + ?Sized + 'static, B: AsRef<T>, S>(
+	t: &[B], serializer: S,
+) -> Result<S::Ok, S::Error>
+where
+	S: serde::Serializer,
+{
+	serializer.collect_seq(t.iter().map(|t| SerializeRef(t.as_ref())))
+}
+
+/// Deserialize a `Vec<B>` where `Box<T>: Into<B>`.
+pub fn deserialize<'de, T: Deserialize + ?Sized + 'static, B, D>(
+	deserializer: D,
+) -> Result<Vec<B>, D::Error>
+where
+	D: serde::Deserializer<'de>,
+	boxed::Box<T>: Into<B>,
+{
+	<Vec<Box<T>> as serde::de::Deserialize>::deserialize(deserializer)
+		.map(|t| t.into_iter().map(|t| t.into_box().into()).collect())
+}
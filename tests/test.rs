@@ -10,13 +10,17 @@
 	unused_results,
 	clippy::pedantic
 )] // from https://github.com/rust-unofficial/patterns/blob/master/anti_patterns/deny-warnings.md
-#![allow(clippy::unseparated_literal_suffix, dead_code)]
+#![allow(clippy::unseparated_literal_suffix, clippy::box_collection, dead_code)]
 
 use serde_closure::Fn;
 use serde_derive::{Deserialize, Serialize};
 use serde_traitobject as st;
 use serde_traitobject::{Deserialize, Serialize};
-use std::{any, env, process, rc};
+use std::{
+	any, env, panic, process, rc, sync::{
+		atomic::{AtomicUsize, Ordering}, Arc
+	}, thread
+};
 use wasm_bindgen_test::wasm_bindgen_test;
 
 #[derive(Serialize, Deserialize)]
@@ -44,6 +48,64 @@ struct Abc {
 	o: Box<[u16]>,
 }
 
+#[derive(Serialize, Deserialize)]
+struct Tagged(#[serde(with = "st::tagged")] Box<dyn HelloSerialize>);
+
+#[derive(Serialize, Deserialize)]
+struct Bytes(#[serde(with = "st::bytes")] Box<dyn HelloSerialize>);
+
+#[derive(Serialize, Deserialize)]
+struct Named(#[serde(with = "st::named")] Box<dyn HelloSerialize>);
+
+#[derive(Serialize, Deserialize, Debug)]
+struct PanicOnDrop(bool);
+impl Drop for PanicOnDrop {
+	fn drop(&mut self) {
+		assert!(!self.0, "dropped");
+	}
+}
+
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+#[derive(Serialize, Deserialize, Debug)]
+struct CountDrops(u128);
+impl Drop for CountDrops {
+	fn drop(&mut self) {
+		let _ = DROPPED.fetch_add(1, Ordering::Relaxed);
+	}
+}
+
+static SERIALIZED: AtomicUsize = AtomicUsize::new(0);
+
+struct CountSerialized;
+impl st::hook::Hook for CountSerialized {
+	fn on_serialize(&self, _: &st::validate::HeaderInfo<'_>) {
+		let _ = SERIALIZED.fetch_add(1, Ordering::Relaxed);
+	}
+}
+
+/// What [`Measured`] deserializes as: the measured sizes, and the values.
+type Measurements = st::dedup::Dedup<(Vec<u64>, Vec<st::Box<dyn st::Debug>>)>;
+
+/// Measures each trait object before serializing it, as a scheduler might.
+struct Measured<'a>(&'a [st::Box<dyn st::Debug>]);
+impl serde::Serialize for Measured<'_> {
+	fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+	where
+		S: serde::Serializer,
+	{
+		let sizes = self
+			.0
+			.iter()
+			.map(|value| st::serialized_size::<dyn st::Debug>(&**value).unwrap())
+			.collect::<Vec<_>>();
+		serde::Serialize::serialize(&(sizes, self.0), serializer)
+	}
+}
+
+/// A `shared::Arc` of a trait object, as preserved within a `Shared`.
+type SharedDebug = st::shared::Arc<dyn st::Debug>;
+
 #[derive(Serialize)]
 struct Def<'a> {
 	a: &'a (dyn st::FnOnce<(), Output = ()> + 'static),
@@ -58,17 +120,17 @@ impl<T> HelloSerialize for T where T: Hello + Serialize + Deserialize {}
 
 impl Hello for u32 {
 	fn hi(&self) -> String {
-		format!("hi u32! {:?}", self)
+		format!("hi u32! {self:?}")
 	}
 }
 impl Hello for u16 {
 	fn hi(&self) -> String {
-		format!("hi u16! {:?}", self)
+		format!("hi u16! {self:?}")
 	}
 }
 impl Hello for u8 {
 	fn hi(&self) -> String {
-		format!("hi u8! {:?}", self)
+		format!("hi u8! {self:?}")
 	}
 }
 
@@ -77,6 +139,14 @@ struct Ghi<'a> {
 	#[serde(with = "st")]
 	e: &'a (dyn Hello2Serialize + 'static),
 }
+#[derive(Serialize)]
+struct Jkl<'a> {
+	a: st::Ref<'a, dyn Hello2Serialize>,
+	b: st::RefMut<'a, dyn Hello2Serialize>,
+	c: Option<st::Ref<'a, dyn Hello2Serialize>>,
+	#[serde(serialize_with = "st::option::serialize_ref")]
+	d: Option<&'a mut (dyn Hello2Serialize + 'static)>,
+}
 trait Hello2 {}
 trait Hello2Serialize: Hello2 + Serialize + Deserialize {}
 impl<T> Hello2Serialize for T where T: Hello2 + Serialize + Deserialize {}
@@ -94,7 +164,23 @@ fn _assert() {
 		T: serde::Serialize + for<'de> serde::Deserialize<'de>,
 	{
 	}
+	fn _into_std_error(
+		a: st::Box<dyn st::Error>, b: st::Box<dyn st::Error + Send>,
+		c: st::Box<dyn st::Error + Send + Sync>,
+	) -> (
+		Box<dyn std::error::Error>,
+		Box<dyn std::error::Error + Send>,
+		Box<dyn std::error::Error + Send + Sync>,
+	) {
+		(a.into(), b.into(), c.into())
+	}
 	assert_serializable::<Request>();
+	assert_serializable::<st::Box<dyn st::Any + Send + Sync>>();
+	assert_serializable::<st::Box<dyn st::Error + Send + Sync>>();
+	assert_serializable::<st::Box<dyn st::Debug + Sync>>();
+	assert_serializable::<st::Arc<dyn st::Fn<(usize,), Output = usize> + Send + Sync>>();
+	assert_serializable::<st::Box<dyn st::Display + Send + Sync>>();
+	assert_serializable::<st::Arc<dyn st::Iterator<Item = u8> + Send + Sync>>();
 }
 
 #[wasm_bindgen_test]
@@ -151,6 +237,26 @@ fn main() {
 		assert_eq!(&*o, &[1u16, 2, 3]);
 	};
 
+	assert_eq!(st::policy::policy(), st::policy::DEFAULT_POLICY);
+	if st::policy::DEFAULT_POLICY == st::policy::Policy::Panic {
+		let serialized =
+			serde_json::to_string(&(st::Box::new(78u8) as st::Box<dyn st::Debug>)).unwrap();
+		let hook = panic::take_hook();
+		panic::set_hook(Box::new(|_| {}));
+		let result = panic::catch_unwind(|| {
+			let _: Result<st::Box<dyn st::Any>, _> = serde_json::from_str(&serialized);
+		});
+		panic::set_hook(hook);
+		assert!(result.is_err());
+		let unsupported = serialized
+			.replacen("{\"version\":1,", "{\"version\":3,", 1)
+			.replacen("{\"version\":2,", "{\"version\":3,", 1);
+		let result: Result<st::Box<dyn st::Debug>, _> = serde_json::from_str(&unsupported);
+		assert!(result.is_err());
+	}
+	// The assertions below expect validation failures to be returned as errors, whatever the policy compiled in.
+	st::policy::set_policy(st::policy::Policy::Error);
+
 	for _ in 0..1_000 {
 		let a: Box<dyn any::Any> = Box::new(Box::new(1usize) as Box<dyn any::Any>);
 		let a: Box<Box<dyn any::Any>> = Box::<dyn any::Any>::downcast(a).unwrap();
@@ -188,15 +294,350 @@ fn main() {
 		test(a1);
 		test(a2);
 		let a1 = serde_json::to_string(&(st::Box::new(78u8) as st::Box<dyn st::Debug>)).unwrap();
+		let a1 = if cfg!(feature = "diagnostics") {
+			let diagnostics = "{\"version\":2,\"type_name\":\"u8\",";
+			assert!(a1.starts_with(diagnostics));
+			a1.replace(diagnostics, "{\"version\":1,")
+		} else {
+			a1
+		};
+		assert!(a1.starts_with("{\"version\":1,\"vtable\":") && a1.ends_with(",\"value\":78}"));
+		let a1r: Result<st::Box<dyn st::Debug>, _> =
+			serde_json::from_str(&a1.replace("{\"version\":1,", "{\"version\":3,"));
+		assert!(a1r
+			.unwrap_err()
+			.to_string()
+			.contains("unsupported format version 3"));
+		let a1r: Result<st::Box<dyn st::Any>, _> = serde_json::from_str(
+			&a1.replace("{\"version\":1,", "{\"version\":2,\"type_name\":\"u8\","),
+		);
+		assert!(a1r
+			.unwrap_err()
+			.to_string()
+			.contains("; the payload was a `u8`"));
+		let a1r: Result<st::Box<dyn st::Debug>, _> = serde_json::from_str(
+			&a1.replace("{\"version\":1,\"vtable\":", "[1,")
+				.replace(",\"type_id\":", ",")
+				.replace(",\"value\":78}", ",78]"),
+		);
+		assert!(a1r.is_ok());
+		#[cfg(feature = "hmac")]
+		{
+			let array = a1
+				.replace("{\"version\":1,\"vtable\":", "[1,")
+				.replace(",\"type_id\":", ",")
+				.replace(",\"value\":78}", ",78]");
+			let a1r: Result<st::Box<dyn st::Debug>, _> =
+				st::auth::with_key(b"secret", || serde_json::from_str(&array));
+			assert!(a1r.unwrap_err().to_string().contains("isn't authenticated"));
+			let named = bincode::serialize(&Named(Box::new(123u16))).unwrap();
+			let named: Result<Named, _> =
+				st::auth::with_key(b"secret", || bincode::deserialize(&named));
+			assert!(named
+				.err()
+				.unwrap()
+				.to_string()
+				.contains("isn't authenticated"));
+		}
 		let a1r: Result<st::Box<dyn st::Debug>, _> = serde_json::from_str(&a1);
 		assert!(a1r.is_ok());
+		let a1v: serde_json::Value = serde_json::from_str(&a1).unwrap();
+		assert!(a1v["vtable"][1].is_string() && a1v["type_id"].as_str().unwrap().len() == 16);
+		let mut a1v = a1v;
+		for pointer in ["/vtable/1", "/vtable/2", "/type_id"] {
+			let hex = a1v.pointer_mut(pointer).unwrap();
+			*hex = u64::from_str_radix(hex.as_str().unwrap(), 16)
+				.unwrap()
+				.into();
+		}
+		// Maps are sorted by key in a `Value`, so use the tuple encoding.
+		let a1v = serde_json::json!([a1v["version"], a1v["vtable"], a1v["type_id"], a1v["value"]]);
+		let a1r: Result<st::Box<dyn st::Debug>, _> = serde_json::from_value(a1v);
+		assert!(a1r.is_ok());
+		let a1v: serde_json::Value = serde_json::from_str(&a1).unwrap();
+		let offset = a1v["vtable"][2].as_str().unwrap();
+		let a1r: Result<st::Box<dyn st::Debug>, _> =
+			serde_json::from_str(&a1.replace(offset, "4000000000000000"));
+		assert!(a1r
+			.unwrap_err()
+			.to_string()
+			.contains("lies outside this binary"));
+		assert!(matches!(
+			st::error::DeserializeError::last(),
+			Some(st::error::DeserializeError::InvalidOffset {
+				offset: 0x4000_0000_0000_0000,
+				..
+			})
+		));
+		let build = a1v["vtable"][0].as_str().unwrap();
+		let other = a1.replace(build, "00000000-0000-0000-0000-000000000001");
+		let a1r: Result<st::Box<dyn st::Debug>, _> = serde_json::from_str(&other);
+		assert!(a1r
+			.unwrap_err()
+			.to_string()
+			.contains("came from a different binary"));
+		unsafe { st::builds::register(1, 0) };
+		let a1r: Result<st::Box<dyn st::Debug>, _> = serde_json::from_str(&other);
+		assert!(a1r.is_ok());
+		st::builds::unregister(1);
+		let type_id = a1v["type_id"].as_str().unwrap();
+		let a1r: Result<st::Box<dyn st::Debug>, _> =
+			serde_json::from_str(&a1.replace(type_id, "0000000000000001"));
+		assert!(a1r
+			.unwrap_err()
+			.to_string()
+			.contains("doesn't match the one it was serialized as"));
 		let a1r: Result<st::Box<dyn st::Any>, _> = serde_json::from_str(&a1);
 		assert!(a1r.is_err());
-		let a1 = bincode::serialize(&(st::Box::new(78u8) as st::Box<dyn st::Debug>)).unwrap();
-		let a1: Result<st::Box<dyn st::Any>, _> = bincode::deserialize(&a1);
-		assert!(a1.is_err());
+		// A trait object nested within another records its own error, as the cause.
+		let nested =
+			st::Box::new(st::Box::new(78u8) as st::Box<dyn st::Debug>) as st::Box<dyn st::Debug>;
+		let nested = serde_json::to_string(&nested).unwrap();
+		let nested = st::error::DeserializeError::capture(|| {
+			serde_json::from_str::<st::Box<dyn st::Debug>>(
+				&nested.replace(type_id, "0000000000000001"),
+			)
+		});
+		let nested = nested.unwrap_err();
+		assert!(matches!(
+			nested.cause(),
+			Some(st::error::DeserializeError::ConcreteTypeIdMismatch { .. })
+		));
+		assert!(matches!(
+			st::error::DeserializeError::of(&nested),
+			Some(st::error::DeserializeError::ConcreteTypeIdMismatch { .. })
+		));
+		assert!(st::error::DeserializeError::of(nested.error()).is_none());
+		// Succeeding clears the record, such that it isn't attributed to an unrelated error.
+		let a1r: Result<st::Box<dyn st::Debug>, _> = serde_json::from_str(&a1);
+		assert!(a1r.is_ok());
+		assert!(st::error::DeserializeError::last().is_none());
+		let unrelated = st::error::DeserializeError::capture(|| {
+			serde_json::from_str::<st::Box<dyn st::Debug>>(&nested.to_string())
+		});
+		assert!(unrelated.unwrap_err().cause().is_none());
+		// Errors are recorded per thread.
+		let a1 = a1.clone();
+		let spawned = thread::spawn(move || {
+			let a1r = st::error::DeserializeError::capture(|| {
+				serde_json::from_str::<st::Box<dyn st::Any>>(&a1)
+			});
+			matches!(
+				a1r.unwrap_err().cause(),
+				Some(st::error::DeserializeError::TraitTypeIdMismatch { .. })
+			)
+		});
+		assert!(spawned.join().unwrap());
+		assert!(st::error::DeserializeError::last().is_none());
+		let mut a1 = bincode::serialize(&(st::Box::new(78u8) as st::Box<dyn st::Debug>)).unwrap();
+		let a2: Result<st::Box<dyn st::Any>, _> = bincode::deserialize(&a1);
+		assert!(a2.is_err());
+		// The architecture, byte order and pointer width follow the header's length and format version.
+		let mut a2 = a1.clone();
+		a2[8 + 28] ^= 1;
+		let a2: Result<st::Box<dyn st::Debug>, _> = bincode::deserialize(&a2);
+		assert!(a2.unwrap_err().to_string().contains("is corrupt"));
+		assert!(matches!(
+			st::error::DeserializeError::last(),
+			Some(st::error::DeserializeError::Corrupt { .. })
+		));
+		let mut a2 = a1.clone();
+		a2[9] = if a2[9] == 1 { 3 } else { 1 };
+		let a2: Result<st::Box<dyn st::Debug>, _> = bincode::deserialize(&a2);
+		assert!(a2
+			.unwrap_err()
+			.to_string()
+			.contains("architecture mismatch: "));
+		a1[11] = if a1[11] == 64 { 32 } else { 64 };
+		let a1: Result<st::Box<dyn st::Debug>, _> = bincode::deserialize(&a1);
+		assert!(a1.unwrap_err().to_string().contains("-bit target"));
+		assert!(matches!(
+			st::error::DeserializeError::last(),
+			Some(st::error::DeserializeError::PointerWidthMismatch { .. })
+		));
 	}
 
+	#[cfg(feature = "eyre")]
+	{
+		// The message and chain can span lines, and aren't mistaken for sections.
+		let report = eyre::eyre!("first line\n\nNot a section:").wrap_err("context\n\nNor this:");
+		let err: st::Box<dyn st::Error + Send + Sync> = report.into();
+		let err: st::Box<dyn st::Error + Send + Sync> =
+			serde_json::from_str(&serde_json::to_string(&err).unwrap()).unwrap();
+		let debug = format!("{err:?}");
+		let (chain, location) = debug.split_once("\n\nLocation:\n").unwrap();
+		assert_eq!(
+			chain,
+			"context\n\nNor this:\n\nCaused by:\n    0: first line\n\nNot a section:"
+		);
+		assert!(location.trim_start().starts_with("tests/test.rs:"));
+		assert!(!location.contains("\n\n"));
+	}
+
+	let slice =
+		bincode::serialize(&st::Box::<[u8]>::from(Box::<[u8]>::from(&[1, 2, 3][..]))).unwrap();
+	let borrowed: st::Ref<[u8]> = bincode::deserialize(&slice).unwrap();
+	assert_eq!(&*borrowed, &[1, 2, 3]);
+	assert!(slice.as_ptr_range().contains(&borrowed.as_ptr()));
+	let slice = bincode::serialize(&borrowed).unwrap();
+	let borrowed: st::Ref<[u8]> = bincode::deserialize(&slice).unwrap();
+	assert_eq!(&*borrowed, &[1, 2, 3]);
+	assert!(slice.as_ptr_range().contains(&borrowed.as_ptr()));
+
+	// Measuring neither invokes the hook, nor records headers in an enclosing `Dedup`.
+	st::hook::set_hook(CountSerialized);
+	let values: Vec<st::Box<dyn st::Debug>> = vec![st::Box::new(1u8), st::Box::new(2u8)];
+	let size = st::serialized_size::<dyn st::Debug>(&*values[0]).unwrap();
+	assert_eq!(SERIALIZED.load(Ordering::Relaxed), 0);
+	let measured = bincode::serialize(&st::dedup::Dedup(Measured(&values))).unwrap();
+	assert!(SERIALIZED.load(Ordering::Relaxed) > 0);
+	st::hook::remove_hook();
+	let st::dedup::Dedup((sizes, deserialized)): Measurements =
+		bincode::deserialize(&measured).unwrap();
+	assert_eq!(sizes, [size, size]);
+	assert_eq!(format!("{deserialized:?}"), "[1, 2]");
+
+	// `bincode::serialize` traverses the value twice, each in a fresh session.
+	let node: Arc<dyn st::Debug> = Arc::new(String::from("shared"));
+	let message = (
+		st::shared::Arc::from(node.clone()),
+		st::shared::Arc::from(node),
+	);
+	let serialized = bincode::serialize(&st::shared::Shared(&message)).unwrap();
+	let st::shared::Shared((a, b)) =
+		bincode::deserialize::<st::shared::Shared<(SharedDebug, SharedDebug)>>(&serialized)
+			.unwrap();
+	assert!(Arc::ptr_eq(&a, &b));
+	assert_eq!(format!("{a:?}"), "\"shared\"");
+
+	// The named encoding reads the type id after the vtable, but the validator still sees it.
+	let string: st::Box<dyn st::Debug> = st::Box::new(String::from("allowed"));
+	let string = serde_json::to_string(&string).unwrap();
+	let number: st::Box<dyn st::Debug> = st::Box::new(123_u32);
+	let number = serde_json::to_string(&number).unwrap();
+	st::validate::with_validator(
+		|info: &st::validate::HeaderInfo<'_>| {
+			if info.is::<String>() {
+				Ok(())
+			} else {
+				Err(st::validate::ValidationError::new(
+					"only strings are allowed",
+				))
+			}
+		},
+		|| {
+			let string: st::Box<dyn st::Debug> = serde_json::from_str(&string).unwrap();
+			assert_eq!(format!("{string:?}"), "\"allowed\"");
+			let number = serde_json::from_str::<st::Box<dyn st::Debug>>(&number);
+			assert!(number
+				.unwrap_err()
+				.to_string()
+				.contains("only strings are allowed"));
+		},
+	);
+
+	let thin: st::Thin<dyn st::Debug> =
+		st::Thin::from(Box::new(CountDrops(1)) as Box<dyn st::Debug>);
+	let json = serde_json::to_string(&thin).unwrap();
+	assert_eq!(
+		json,
+		serde_json::to_string(&(st::Box::new(CountDrops(1)) as st::Box<dyn st::Debug>)).unwrap()
+	);
+	let deserialized: st::Thin<dyn st::Debug> = serde_json::from_str(&json).unwrap();
+	assert_eq!(format!("{deserialized:?}"), "CountDrops(1)");
+	// The value follows the header at its own alignment.
+	assert_eq!((&raw const *deserialized).cast::<u8>() as usize % 16, 0);
+	let deserialized: st::Thin<dyn st::Debug> =
+		bincode::deserialize(&bincode::serialize(&deserialized).unwrap()).unwrap();
+	assert_eq!(format!("{deserialized:?}"), "CountDrops(1)");
+	let dropped = DROPPED.load(Ordering::Relaxed);
+	drop((thin, deserialized, st::Thin::new(CountDrops(2))));
+	assert_eq!(DROPPED.load(Ordering::Relaxed), dropped + 3);
+	let zst: st::Thin<dyn st::Debug> = st::Thin::from(Box::new(()) as Box<dyn st::Debug>);
+	let zst: st::Thin<dyn st::Debug> =
+		serde_json::from_str(&serde_json::to_string(&zst).unwrap()).unwrap();
+	assert_eq!(format!("{zst:?}"), "()");
+	let slice: st::Thin<[u16]> = st::Thin::from(Box::<[u16]>::from(&[1, 2, 3][..]));
+	let slice: st::Thin<[u16]> =
+		bincode::deserialize(&bincode::serialize(&slice).unwrap()).unwrap();
+	assert_eq!(&*slice, &[1, 2, 3]);
+	let empty: st::Thin<str> = serde_json::from_str("\"\"").unwrap();
+	assert_eq!(&*empty, "");
+	assert_eq!(
+		&*st::Thin::<str>::from(Box::<str>::from("thin")).into_box(),
+		"thin"
+	);
+
+	let mut worker: Box<dyn st::Debug> = Box::new(PanicOnDrop(true));
+	let new = st::Box::new(PanicOnDrop(false)) as st::Box<dyn st::Debug>;
+	let new = serde_json::to_string(&new).unwrap();
+	let hook = panic::take_hook();
+	panic::set_hook(Box::new(|_| {}));
+	let result = panic::catch_unwind(panic::AssertUnwindSafe(|| {
+		st::deserialize_in_place(&mut *worker, &mut serde_json::Deserializer::from_str(&new))
+	}));
+	panic::set_hook(hook);
+	assert!(result.is_err());
+	assert_eq!(format!("{worker:?}"), "PanicOnDrop(false)");
+
+	let mut worker: Box<dyn st::Debug> = Box::new(String::from("old"));
+	let new = st::Box::new(String::from("new")) as st::Box<dyn st::Debug>;
+	let new = serde_json::to_string(&new).unwrap();
+	assert!(new.ends_with(r#""value":"new"}"#));
+	let new = format!("{}x", new.strip_suffix('}').unwrap());
+	assert!(
+		st::deserialize_in_place(&mut *worker, &mut serde_json::Deserializer::from_str(&new))
+			.is_err()
+	);
+	assert_eq!(format!("{worker:?}"), "\"old\"");
+
+	let bytes = bincode::serialize(&Bytes(Box::new(123u16))).unwrap();
+	assert_eq!(
+		bincode::deserialize::<Bytes>(&bytes).unwrap().0.hi(),
+		"hi u16! 123"
+	);
+	let bytes = serde_json::to_string(&Bytes(Box::new(123u16))).unwrap();
+	assert_eq!(
+		serde_json::from_str::<Bytes>(&bytes).unwrap().0.hi(),
+		"hi u16! 123"
+	);
+
+	let batch: st::batch::Batch<dyn HelloSerialize> = vec![
+		Box::new(1u16) as Box<dyn HelloSerialize>,
+		Box::new(2u32),
+		Box::new(3u16),
+	]
+	.into();
+	let json = serde_json::to_string(&batch).unwrap();
+	let batch: st::batch::Batch<dyn HelloSerialize> = serde_json::from_str(&json).unwrap();
+	assert_eq!(batch[1].hi(), "hi u32! 2");
+	let json = json.replacen("[1,\"", "[1,\"0", 1);
+	assert!(serde_json::from_str::<st::batch::Batch<dyn HelloSerialize>>(&json).is_err());
+	// Each element is checked against its own concrete type id.
+	let mut json: serde_json::Value = serde_json::to_value(&batch).unwrap();
+	json[3][1][1] = json[3][0][1].clone();
+	let Err(error) = serde_json::from_value::<st::batch::Batch<dyn HelloSerialize>>(json) else {
+		panic!("a mismatched concrete type id was accepted");
+	};
+	assert!(error
+		.to_string()
+		.contains("doesn't match the one it was serialized as"));
+
+	let tagged = bincode::serialize(&Tagged(Box::new(123u16))).unwrap();
+	assert!(bincode::deserialize::<Tagged>(&tagged).is_err());
+	st::tagged::register::<dyn HelloSerialize, u16>();
+	st::tagged::register_as::<dyn HelloSerialize, u32>("u32");
+	assert_eq!(
+		bincode::deserialize::<Tagged>(&tagged).unwrap().0.hi(),
+		"hi u16! 123"
+	);
+	let tagged = serde_json::to_string(&Tagged(Box::new(789u32))).unwrap();
+	assert_eq!(tagged, r#"{"type":"u32","value":789}"#);
+	assert_eq!(
+		serde_json::from_str::<Tagged>(&tagged).unwrap().0.hi(),
+		"hi u32! 789"
+	);
+
 	let original = Abc {
 		a: rc::Rc::new(123u16),
 		b: st::Rc::new(456u16),
@@ -231,7 +672,7 @@ fn main() {
 
 	let exe = env::current_exe().unwrap();
 	for i in 0..100 {
-		println!("{}", i);
+		println!("{i}");
 		let output = process::Command::new(&exe)
 			.stdin(process::Stdio::null())
 			.stdout(process::Stdio::inherit())
@@ -243,9 +684,7 @@ fn main() {
 			)
 			.output()
 			.unwrap();
-		if !output.status.success() {
-			panic!("{}: {:?}", i, output);
-		}
+		assert!(output.status.success(), "{}: {:?}", i, output);
 	}
 }
 